@@ -54,4 +54,72 @@ pub enum BubblegumError {
     UpdateAuthorityIncorrect,
     #[msg("This transaction must be signed by either the leaf owner or leaf delegate")]
     LeafAuthorityMustSign,
+    #[msg("Print editions cannot be compressed")]
+    PrintEditionCannotBeCompressed,
+    #[msg("Collection parents cannot be compressed")]
+    CollectionParentCannotBeCompressed,
+    #[msg("Metadata is not mutable")]
+    MetadataImmutable,
+    #[msg("Primary sale can only be flipped to true")]
+    PrimarySaleCanOnlyBeFlippedToTrue,
+    #[msg("Is mutable can only be flipped to false")]
+    IsMutableCanOnlyBeFlippedToFalse,
+    #[msg("Minter has used up its mint quota")]
+    MintQuotaExceeded,
+    #[msg("Leaf is already frozen")]
+    LeafAlreadyFrozen,
+    #[msg("Leaf is not frozen")]
+    LeafNotFrozen,
+    #[msg("Asset has no uses")]
+    Unusable,
+    #[msg("There are not enough uses left on this asset")]
+    NotEnoughUses,
+    #[msg("Tree still has leaves")]
+    TreeNotEmpty,
+    #[msg("Batch has more leaves than the tree's change log buffer can hold")]
+    BatchTooLarge,
+    #[msg("The same leaf appears more than once in the batch")]
+    DuplicateLeafInBatch,
+    #[msg("Proof lengths don't add up to the number of proof accounts")]
+    ProofLengthMismatch,
+    #[msg("Voucher has not expired")]
+    VoucherNotExpired,
+    #[msg("Tree already has a successor")]
+    SuccessorTreeAlreadySet,
+    #[msg("Invalid successor tree")]
+    InvalidSuccessorTree,
+    #[msg("Tree is full and its successor tree accounts were not provided")]
+    SuccessorTreeAccountsMissing,
+    #[msg("Creator accounts don't match the creators of the leaf")]
+    CreatorAccountsMismatch,
+    #[msg("Collection is not sized")]
+    CollectionNotSized,
+    #[msg("Collection size can't be counted at a future slot")]
+    InvalidCountedAtSlot,
+    #[msg("Leaf is frozen")]
+    LeafFrozen,
+    #[msg("Tree has vouchers that can still put their leaf back")]
+    TreeHasVouchers,
+    #[msg("Tree is the successor of another tree")]
+    TreeIsSuccessor,
+    #[msg("Leaf is non-transferable")]
+    LeafNonTransferable,
+    #[msg("Leaf flags can't be set by this instruction")]
+    InvalidLeafFlags,
+    #[msg("Vouchers of this tree are counted, use the instructions that count them")]
+    VouchersCounted,
+    #[msg("Tree was not made closable")]
+    TreeNotClosable,
+    #[msg("Tree already has mints")]
+    TreeAlreadyMinted,
+    #[msg("Tree still has a print source")]
+    TreeHasPrintSource,
+    #[msg("Public trees can't have a print source")]
+    PublicTreePrintSource,
+    #[msg("Leaf doesn't match the print source")]
+    PrintSourceMismatch,
+    #[msg("Collection has not approved Bubblegum to keep its size")]
+    CollectionNotApproved,
+    #[msg("Attestation is missing or doesn't match")]
+    InvalidAttestation,
 }
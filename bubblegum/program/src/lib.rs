@@ -1,15 +1,22 @@
 use crate::{
     error::BubblegumError,
     state::{
-        leaf_schema::{LeafSchema, Version},
-        metaplex_adapter::{self, Creator, MetadataArgs, TokenProgramVersion},
-        metaplex_anchor::{MasterEdition, MplTokenMetadata, TokenMetadata},
-        NFTDecompressionEvent, NewNFTEvent, TreeConfig, Voucher, ASSET_PREFIX,
-        COLLECTION_CPI_PREFIX, TREE_AUTHORITY_SIZE, VOUCHER_PREFIX, VOUCHER_SIZE,
+        leaf_schema::{LeafSchema, Version, LEAF_FLAG_FROZEN, LEAF_FLAG_NON_TRANSFERABLE},
+        metaplex_adapter::{
+            self, Creator, MetadataArgs, TokenProgramVersion, UpdateArgs, UseMethod,
+        },
+        metaplex_anchor::{MplTokenMetadata, TokenMetadata},
+        BatchMintArgs, BatchVerifyCreatorArgs, CollectionSizeReconciliationEvent, Listing,
+        MetadataUpdateEvent, MinterRecord, NFTDecompressionEvent, NewNFTEvent, PrintSource,
+        TreeConfig, Voucher, VoucherCounted, VoucherExpiry, ASSET_PREFIX, COLLECTION_CPI_PREFIX,
+        LISTING_PREFIX, LISTING_SIZE, MINTER_RECORD_PREFIX, MINTER_RECORD_SIZE,
+        PRINT_SOURCE_PREFIX, PRINT_SOURCE_SIZE, TREE_AUTHORITY_SIZE, VOUCHER_LIFETIME_SECONDS,
+        VOUCHER_PREFIX, VOUCHER_SIZE, VOUCHER_V2_SIZE,
     },
     utils::{
-        append_leaf, assert_metadata_is_mpl_compatible, assert_pubkey_equal, cmp_bytes,
-        cmp_pubkeys, get_asset_id, replace_leaf,
+        append_leaf, assert_ed25519_signature, assert_metadata_is_mpl_compatible,
+        assert_pubkey_equal, assert_tree_is_empty, cmp_bytes, cmp_pubkeys, get_asset_id,
+        get_max_buffer_size, replace_leaf,
     },
 };
 use anchor_lang::{
@@ -20,7 +27,7 @@ use anchor_lang::{
         program::{invoke, invoke_signed},
         program_error::ProgramError,
         program_pack::Pack,
-        system_instruction,
+        system_instruction, sysvar,
     },
     system_program::System,
 };
@@ -102,6 +109,31 @@ pub struct Burn<'info> {
     pub compression_program: Program<'info, SplAccountCompression>,
 }
 
+#[derive(Accounts)]
+pub struct BurnWithMetadata<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK: This is just used as a signing PDA.
+    #[account(
+        seeds = [COLLECTION_CPI_PREFIX.as_ref()],
+        bump,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+}
+
 #[derive(Accounts)]
 pub struct CreatorVerification<'info> {
     #[account(
@@ -122,6 +154,22 @@ pub struct CreatorVerification<'info> {
     pub compression_program: Program<'info, SplAccountCompression>,
 }
 
+#[derive(Accounts)]
+pub struct BatchCreatorVerification<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub payer: Signer<'info>,
+    pub creator: Signer<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
 #[derive(Accounts)]
 pub struct CollectionVerification<'info> {
     #[account(
@@ -199,6 +247,134 @@ pub struct Delegate<'info> {
     pub compression_program: Program<'info, SplAccountCompression>,
 }
 
+#[derive(Accounts)]
+#[instruction(
+    _root: [u8; 32],
+    _data_hash: [u8; 32],
+    _creator_hash: [u8; 32],
+    nonce: u64,
+)]
+pub struct List<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        seeds = [
+            LISTING_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        payer = leaf_owner,
+        space = LISTING_SIZE,
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    _root: [u8; 32],
+    _data_hash: [u8; 32],
+    _creator_hash: [u8; 32],
+    nonce: u64,
+)]
+pub struct Delist<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = leaf_owner,
+        seeds = [
+            LISTING_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump,
+        constraint = listing.seller == leaf_owner.key() @ BubblegumError::AssetOwnerMismatch
+    )]
+    pub listing: Account<'info, Listing>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    _root: [u8; 32],
+    _metadata_args_hash: [u8; 32],
+    _seller_fee_basis_points: u16,
+    _creators: Vec<Creator>,
+    nonce: u64,
+)]
+pub struct Buy<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This account is checked against the listing
+    pub seller: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = seller,
+        seeds = [
+            LISTING_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            nonce.to_le_bytes().as_ref()
+        ],
+        bump,
+        has_one = seller
+    )]
+    pub listing: Account<'info, Listing>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct LeafFreeze<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: This account is neither written to nor read from.
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    pub leaf_delegate: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
 #[derive(Accounts)]
 #[instruction(
     _root: [u8; 32],
@@ -212,7 +388,7 @@ pub struct Redeem<'info> {
         seeds = [merkle_tree.key().as_ref()],
         bump,
     )]
-    /// CHECK: This account is neither written to nor read from.
+    /// CHECK: This account is only read from, to tell if the tree counts vouchers.
     pub tree_authority: Account<'info, TreeConfig>,
     #[account(mut)]
     pub leaf_owner: Signer<'info>,
@@ -238,6 +414,46 @@ pub struct Redeem<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// `Redeem` with a writable tree config, to count the voucher in.
+#[derive(Accounts)]
+#[instruction(
+    _root: [u8; 32],
+    _data_hash: [u8; 32],
+    _creator_hash: [u8; 32],
+    nonce: u64,
+    _index: u32,
+)]
+pub struct RedeemV2<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [
+            VOUCHER_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            &nonce.to_le_bytes()
+        ],
+        payer = leaf_owner,
+        space = VOUCHER_V2_SIZE,
+        bump
+    )]
+    pub voucher: Account<'info, Voucher>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CancelRedeem<'info> {
     #[account(
@@ -266,6 +482,65 @@ pub struct CancelRedeem<'info> {
     pub compression_program: Program<'info, SplAccountCompression>,
 }
 
+/// `CancelRedeem` with a writable tree config, to take the voucher off the count.
+#[derive(Accounts)]
+pub struct CancelRedeemV2<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = leaf_owner,
+        seeds = [
+            VOUCHER_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub voucher: Account<'info, Voucher>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireVoucher<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// Gets the rent of the voucher back.
+    #[account(mut)]
+    /// CHECK: This account is checked in the instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = leaf_owner,
+        seeds = [
+            VOUCHER_PREFIX.as_ref(),
+            merkle_tree.key().as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub voucher: Account<'info, Voucher>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
 #[derive(Accounts)]
 pub struct DecompressV1<'info> {
     #[account(
@@ -317,56 +592,505 @@ pub struct DecompressV1<'info> {
     pub associated_token_program: UncheckedAccount<'info>,
 }
 
+/// `DecompressV1` with a writable tree config, to take the voucher off the count.
 #[derive(Accounts)]
-pub struct Compress<'info> {
+pub struct DecompressV2<'info> {
     #[account(
-        seeds = [merkle_tree.key().as_ref()],
-        bump,
+        mut,
+        close = leaf_owner,
+        seeds = [
+            VOUCHER_PREFIX.as_ref(),
+            voucher.merkle_tree.as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref()
+        ],
+        bump
     )]
-    /// CHECK: This account is neither written to nor read from.
-    pub tree_authority: UncheckedAccount<'info>,
-    /// CHECK: This account is checked in the instruction
+    pub voucher: Box<Account<'info, Voucher>>,
+    #[account(mut)]
     pub leaf_owner: Signer<'info>,
-    /// CHECK: This account is chekced in the instruction
-    pub leaf_delegate: UncheckedAccount<'info>,
-    /// CHECK: This account is not read
-    pub merkle_tree: UncheckedAccount<'info>,
-
     /// CHECK: versioning is handled in the instruction
     #[account(mut)]
-    pub token_account: AccountInfo<'info>,
+    pub token_account: UncheckedAccount<'info>,
     /// CHECK: versioning is handled in the instruction
-    #[account(mut)]
-    pub mint: AccountInfo<'info>,
-    #[account(mut)]
-    pub metadata: Box<Account<'info, TokenMetadata>>,
-    #[account(mut)]
-    pub master_edition: Box<Account<'info, MasterEdition>>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    pub log_wrapper: Program<'info, Wrapper>,
-    pub compression_program: Program<'info, SplAccountCompression>,
-    /// CHECK:
-    pub token_program: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_PREFIX.as_ref(),
+            voucher.merkle_tree.as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        seeds = [mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Initialized in Token Metadata Program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub sysvar_rent: Sysvar<'info, Rent>,
+    /// CHECK:
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+    /// CHECK: versioning is handled in the instruction
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    pub associated_token_program: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [voucher.merkle_tree.as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+}
+
+#[derive(Accounts)]
+pub struct DecompressPrint<'info> {
+    #[account(
+        mut,
+        close = leaf_owner,
+        seeds = [
+            VOUCHER_PREFIX.as_ref(),
+            voucher.merkle_tree.as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub voucher: Box<Account<'info, Voucher>>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: versioning is handled in the instruction
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+    /// CHECK: versioning is handled in the instruction
+    #[account(
+        mut,
+        seeds = [
+            ASSET_PREFIX.as_ref(),
+            voucher.merkle_tree.as_ref(),
+            voucher.leaf_schema.nonce().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(
+        seeds = [mint.key().as_ref()],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+    /// CHECK: Initialized in Token Metadata Program
+    #[account(mut)]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: Initialized in Token Metadata Program
+    #[account(mut)]
+    pub edition: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [voucher.merkle_tree.as_ref()],
+        bump,
+    )]
+    pub tree_authority: Box<Account<'info, TreeConfig>>,
+    #[account(
+        seeds = [PRINT_SOURCE_PREFIX.as_ref(), voucher.merkle_tree.as_ref()],
+        bump,
+    )]
+    pub print_source: Box<Account<'info, PrintSource>>,
+    /// CHECK: checked against the print source
+    #[account(address = print_source.master_mint)]
+    pub master_mint: UncheckedAccount<'info>,
+    /// CHECK: Checked in Token Metadata Program
+    pub master_metadata: UncheckedAccount<'info>,
+    /// CHECK: Checked in Token Metadata Program
+    #[account(mut)]
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: Checked in Token Metadata Program
+    #[account(
+        address = spl_associated_token_account::get_associated_token_address(
+            &tree_authority.key(),
+            &master_mint.key(),
+        )
+    )]
+    pub master_token_account: UncheckedAccount<'info>,
+    /// CHECK: Initialized in Token Metadata Program
+    #[account(mut)]
+    pub edition_marker: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub sysvar_rent: Sysvar<'info, Rent>,
+    /// CHECK:
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+    /// CHECK: versioning is handled in the instruction
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    pub associated_token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Compress<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub leaf_owner: Signer<'info>,
+    /// CHECK: This account is neither written to nor read from.
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub tree_delegate: Signer<'info>,
+    /// CHECK: checked in cpi
+    #[account(mut)]
+    pub token_account: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi
+    #[account(mut)]
+    pub mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub metadata: Box<Account<'info, TokenMetadata>>,
+    /// CHECK: Print editions are rejected in the instruction, the rest is checked in cpi
+    #[account(mut, owner = mpl_token_metadata::id())]
+    pub master_edition: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    /// CHECK:
+    #[account(address = spl_token::id())]
+    pub token_program: UncheckedAccount<'info>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// Tree creator, or collection authority when the leaf has a verified collection.
+    pub authority: Signer<'info>,
+    /// CHECK: Only read when the leaf has a verified collection, checked in the instruction
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: Only read when the leaf has a verified collection, checked in the instruction
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: Optional, the program id is passed when there is no record
+    pub collection_authority_record_pda: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+pub struct ReconcileCollectionSize<'info> {
+    pub collection_authority: Signer<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub collection_metadata: Box<Account<'info, TokenMetadata>>,
+    /// CHECK: Optional, the program id is passed when there is no record
+    pub collection_authority_record_pda: UncheckedAccount<'info>,
+    /// CHECK: This is just used as a signing PDA.
+    #[account(
+        seeds = [COLLECTION_CPI_PREFIX.as_ref()],
+        bump,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: Checked to have signed the reconciliation in the instruction
+    pub attester: UncheckedAccount<'info>,
+    /// CHECK: This account is checked by its address
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
+}
+
+#[derive(Accounts)]
+pub struct Utilize<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: This account is modified in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Wrapper>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreeDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub new_tree_delegate: UncheckedAccount<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTree<'info> {
+    #[account(
+        mut,
+        close = tree_creator,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// Receives the rent of both the tree and its config.
+    #[account(mut)]
+    pub tree_creator: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: This account is closed in the downstream program
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: only checked to be empty
+    #[account(
+        seeds = [PRINT_SOURCE_PREFIX.as_ref(), merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub print_source: UncheckedAccount<'info>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreePublic<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreeClosable<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSuccessorTree<'info> {
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator,
+        realloc = TREE_AUTHORITY_SIZE,
+        realloc::payer = tree_creator,
+        realloc::zero = false,
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [successor_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator @ BubblegumError::InvalidSuccessorTree,
+    )]
+    pub successor_authority: Account<'info, TreeConfig>,
+    /// CHECK: this account is neither read from or written to
+    #[account(
+        constraint = successor_tree.key() != merkle_tree.key() @ BubblegumError::InvalidSuccessorTree
+    )]
+    pub successor_tree: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddMinter<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    pub tree_creator: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub minter: UncheckedAccount<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [
+            MINTER_RECORD_PREFIX.as_ref(),
+            tree_authority.key().as_ref(),
+            minter.key().as_ref(),
+        ],
+        payer = payer,
+        space = MINTER_RECORD_SIZE,
+        bump
+    )]
+    pub minter_record: Account<'info, MinterRecord>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMinter<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    #[account(mut)]
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub minter: UncheckedAccount<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = tree_creator,
+        seeds = [
+            MINTER_RECORD_PREFIX.as_ref(),
+            tree_authority.key().as_ref(),
+            minter.key().as_ref(),
+        ],
+        bump
+    )]
+    pub minter_record: Account<'info, MinterRecord>,
+}
+
+#[derive(Accounts)]
+pub struct SetPrintSource<'info> {
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        has_one = tree_creator
+    )]
+    pub tree_authority: Account<'info, TreeConfig>,
+    /// Holds the master edition token and pays for the print source.
+    #[account(mut)]
+    pub tree_creator: Signer<'info>,
+    /// CHECK: this account is neither read from or written to
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: checked by the token program during the transfer
+    pub master_mint: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in the instruction
+    #[account(
+        owner = token_metadata_program.key(),
+        seeds = [
+            mpl_token_metadata::state::PREFIX.as_ref(),
+            token_metadata_program.key().as_ref(),
+            master_mint.key().as_ref(),
+            mpl_token_metadata::state::EDITION.as_ref(),
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+    /// CHECK: checked by the token program during the transfer
+    #[account(mut)]
+    pub creator_token_account: UncheckedAccount<'info>,
+    /// CHECK: Created in the instruction if needed
+    #[account(
+        mut,
+        address = spl_associated_token_account::get_associated_token_address(
+            &tree_authority.key(),
+            &master_mint.key(),
+        )
+    )]
+    pub source_token_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [PRINT_SOURCE_PREFIX.as_ref(), merkle_tree.key().as_ref()],
+        payer = tree_creator,
+        space = PRINT_SOURCE_SIZE,
+        bump,
+    )]
+    pub print_source: Account<'info, PrintSource>,
+    pub token_metadata_program: Program<'info, MplTokenMetadata>,
     /// CHECK:
-    pub token_metadata_program: UncheckedAccount<'info>,
+    #[account(address = spl_token::id())]
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = spl_associated_token_account::id())]
+    pub associated_token_program: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
+    pub sysvar_rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct SetTreeDelegate<'info> {
+pub struct RemovePrintSource<'info> {
     #[account(
-        mut,
         seeds = [merkle_tree.key().as_ref()],
         bump,
         has_one = tree_creator
     )]
     pub tree_authority: Account<'info, TreeConfig>,
+    /// Gets the master edition token back, along with the rent of the print source.
+    #[account(mut)]
     pub tree_creator: Signer<'info>,
     /// CHECK: this account is neither read from or written to
-    pub new_tree_delegate: UncheckedAccount<'info>,
-    /// CHECK: this account is neither read from or written to
     pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = tree_creator,
+        seeds = [PRINT_SOURCE_PREFIX.as_ref(), merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub print_source: Account<'info, PrintSource>,
+    /// CHECK: checked against the print source
+    #[account(address = print_source.master_mint)]
+    pub master_mint: UncheckedAccount<'info>,
+    /// CHECK: checked by the token program during the transfer
+    #[account(
+        mut,
+        address = spl_associated_token_account::get_associated_token_address(
+            &tree_authority.key(),
+            &master_mint.key(),
+        )
+    )]
+    pub source_token_account: UncheckedAccount<'info>,
+    /// CHECK: checked by the token program during the transfer
+    #[account(mut)]
+    pub creator_token_account: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = spl_token::id())]
+    pub token_program: UncheckedAccount<'info>,
 }
 
 pub fn hash_creators(creators: &[Creator]) -> Result<[u8; 32]> {
@@ -396,22 +1120,95 @@ pub fn hash_metadata(metadata: &MetadataArgs) -> Result<[u8; 32]> {
     .to_bytes())
 }
 
+/// Rebuilds the `MetadataArgs` of a token-metadata NFT, undoing what token-metadata and
+/// `decompress_v1` add on top of the compressed data: string padding and the verified
+/// zero-share creator of the decompression mint authority.
+pub fn metadata_args_from_token_metadata(
+    metadata: &mpl_token_metadata::state::Metadata,
+    mint: &Pubkey,
+) -> MetadataArgs {
+    let (mint_authority, _) = Pubkey::find_program_address(&[mint.as_ref()], &crate::id());
+
+    MetadataArgs {
+        name: metadata
+            .data
+            .name
+            .trim_end_matches(char::from(0))
+            .to_string(),
+        symbol: metadata
+            .data
+            .symbol
+            .trim_end_matches(char::from(0))
+            .to_string(),
+        uri: metadata
+            .data
+            .uri
+            .trim_end_matches(char::from(0))
+            .to_string(),
+        seller_fee_basis_points: metadata.data.seller_fee_basis_points,
+        primary_sale_happened: metadata.primary_sale_happened,
+        is_mutable: metadata.is_mutable,
+        edition_nonce: metadata.edition_nonce,
+        token_standard: metadata.token_standard.as_ref().map(Into::into),
+        collection: metadata.collection.as_ref().map(Into::into),
+        uses: metadata.uses.as_ref().map(Into::into),
+        token_program_version: TokenProgramVersion::Original,
+        creators: metadata
+            .data
+            .creators
+            .iter()
+            .flatten()
+            .filter(|c| !(c.address == mint_authority && c.verified && c.share == 0))
+            .map(Into::into)
+            .collect(),
+    }
+}
+
 pub enum InstructionName {
     Unknown,
     MintV1,
     Redeem,
+    RedeemV2,
+    MintV2,
+    CancelRedeemV2,
+    DecompressV2,
+    SetTreeClosable,
     CancelRedeem,
+    ExpireVoucher,
     Transfer,
+    TransferV2,
     Delegate,
+    DelegateV2,
+    List,
+    Delist,
+    Buy,
     DecompressV1,
     Compress,
     Burn,
+    BurnV2,
+    BurnWithMetadata,
     CreateTree,
+    SetTreeDelegate,
     VerifyCreator,
     UnverifyCreator,
+    VerifyCreatorBatch,
     VerifyCollection,
     UnverifyCollection,
     SetAndVerifyCollection,
+    UpdateMetadata,
+    ReconcileCollectionSize,
+    MintBatchV1,
+    AddMinter,
+    RemoveMinter,
+    SetTreePublic,
+    SetSuccessorTree,
+    Freeze,
+    Thaw,
+    Utilize,
+    CloseTree,
+    SetPrintSource,
+    RemovePrintSource,
+    DecompressPrint,
 }
 
 pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
@@ -424,27 +1221,241 @@ pub fn get_instruction_type(full_bytes: &[u8]) -> InstructionName {
         [145, 98, 192, 118, 184, 147, 118, 104] => InstructionName::MintV1,
         [111, 76, 232, 50, 39, 175, 48, 242] => InstructionName::CancelRedeem,
         [184, 12, 86, 149, 70, 196, 97, 225] => InstructionName::Redeem,
+        [141, 73, 176, 100, 92, 94, 58, 87] => InstructionName::RedeemV2,
+        [120, 121, 23, 146, 173, 110, 199, 205] => InstructionName::MintV2,
+        [53, 127, 141, 66, 244, 166, 37, 218] => InstructionName::CancelRedeemV2,
+        [222, 204, 18, 191, 82, 195, 125, 153] => InstructionName::DecompressV2,
+        [248, 141, 126, 87, 28, 17, 149, 47] => InstructionName::SetTreeClosable,
+        [67, 29, 125, 143, 104, 223, 68, 129] => InstructionName::ExpireVoucher,
         [163, 52, 200, 231, 140, 3, 69, 186] => InstructionName::Transfer,
+        [119, 40, 6, 235, 234, 221, 248, 49] => InstructionName::TransferV2,
         [90, 147, 75, 178, 85, 88, 4, 137] => InstructionName::Delegate,
+        [95, 87, 125, 140, 181, 131, 128, 227] => InstructionName::DelegateV2,
+        [54, 174, 193, 67, 17, 41, 132, 38] => InstructionName::List,
+        [55, 136, 205, 107, 107, 173, 4, 31] => InstructionName::Delist,
+        [102, 6, 61, 18, 1, 218, 235, 234] => InstructionName::Buy,
         [54, 85, 76, 70, 228, 250, 164, 81] => InstructionName::DecompressV1,
         [116, 110, 29, 56, 107, 219, 42, 93] => InstructionName::Burn,
+        [115, 210, 34, 240, 232, 143, 183, 16] => InstructionName::BurnV2,
+        [173, 224, 221, 213, 113, 142, 65, 111] => InstructionName::BurnWithMetadata,
         [82, 193, 176, 117, 176, 21, 115, 253] => InstructionName::Compress,
         [165, 83, 136, 142, 89, 202, 47, 220] => InstructionName::CreateTree,
+        [253, 118, 66, 37, 190, 49, 154, 102] => InstructionName::SetTreeDelegate,
         [52, 17, 96, 132, 71, 4, 85, 194] => InstructionName::VerifyCreator,
         [107, 178, 57, 39, 105, 115, 112, 152] => InstructionName::UnverifyCreator,
+        [197, 72, 123, 212, 171, 15, 210, 84] => InstructionName::VerifyCreatorBatch,
         [56, 113, 101, 253, 79, 55, 122, 169] => InstructionName::VerifyCollection,
         [250, 251, 42, 106, 41, 137, 186, 168] => InstructionName::UnverifyCollection,
         [235, 242, 121, 216, 158, 234, 180, 234] => InstructionName::SetAndVerifyCollection,
+        [170, 182, 43, 239, 97, 78, 225, 186] => InstructionName::UpdateMetadata,
+        [35, 101, 179, 20, 227, 172, 136, 222] => InstructionName::ReconcileCollectionSize,
+        [248, 4, 56, 142, 14, 49, 169, 71] => InstructionName::MintBatchV1,
+        [75, 86, 218, 40, 219, 6, 141, 29] => InstructionName::AddMinter,
+        [241, 69, 84, 16, 164, 232, 131, 79] => InstructionName::RemoveMinter,
+        [62, 248, 224, 246, 35, 172, 162, 17] => InstructionName::SetTreePublic,
+        [151, 20, 103, 74, 89, 125, 211, 143] => InstructionName::SetSuccessorTree,
+        [255, 91, 207, 84, 251, 194, 254, 63] => InstructionName::Freeze,
+        [226, 249, 34, 57, 189, 21, 177, 101] => InstructionName::Thaw,
+        [104, 146, 242, 209, 176, 174, 185, 163] => InstructionName::Utilize,
+        [9, 124, 164, 131, 238, 218, 148, 212] => InstructionName::CloseTree,
+        [88, 172, 227, 196, 113, 183, 112, 205] => InstructionName::SetPrintSource,
+        [226, 10, 128, 173, 51, 83, 164, 95] => InstructionName::RemovePrintSource,
+        [146, 132, 57, 157, 178, 109, 172, 110] => InstructionName::DecompressPrint,
         _ => InstructionName::Unknown,
     }
 }
 
+/// Takes `count` mints off the quota of `minter`, whose `MinterRecord` must be among `accounts`.
+fn consume_mint_quota(
+    tree_authority: &Pubkey,
+    minter: &Pubkey,
+    accounts: &[AccountInfo],
+    count: u64,
+) -> Result<()> {
+    let (record_key, _) = Pubkey::find_program_address(
+        &[
+            MINTER_RECORD_PREFIX.as_ref(),
+            tree_authority.as_ref(),
+            minter.as_ref(),
+        ],
+        &crate::id(),
+    );
+    let record_info = accounts
+        .iter()
+        .find(|a| a.key() == record_key)
+        .ok_or(BubblegumError::TreeAuthorityIncorrect)?;
+
+    let mut record = Account::<MinterRecord>::try_from(record_info)?;
+    record.mint_quota = record
+        .mint_quota
+        .checked_sub(count)
+        .ok_or(BubblegumError::MintQuotaExceeded)?;
+    record.exit(&crate::id())
+}
+
+/// Checks that the tree delegate (or an approved minter, or anyone for public trees) signed.
+/// Returns the signers that count as verified creators.
+fn assert_can_mint(ctx: &Context<MintV1>, count: u64) -> Result<HashSet<Pubkey>> {
+    let incoming_tree_delegate = ctx.accounts.tree_delegate.key();
+    let authority = &ctx.accounts.tree_authority;
+    let tree_creator = authority.tree_creator;
+    let tree_delegate = authority.tree_delegate;
+
+    // Anyone other than the creator and delegate needs a minter record with enough quota,
+    // unless the tree is public.
+    if !authority.is_public
+        && incoming_tree_delegate != tree_creator
+        && incoming_tree_delegate != tree_delegate
+    {
+        consume_mint_quota(
+            &authority.key(),
+            &incoming_tree_delegate,
+            ctx.remaining_accounts,
+            count,
+        )?;
+    }
+
+    // Create a HashSet to store signers to use with creator validation.  Any signer can be
+    // counted as a validated creator.
+    let mut metadata_auth = HashSet::<Pubkey>::new();
+    metadata_auth.insert(ctx.accounts.payer.key());
+    metadata_auth.insert(incoming_tree_delegate);
+
+    // If there are any remaining accounts that are also signers, they can also be used for
+    // creator validation.
+    metadata_auth.extend(
+        ctx.remaining_accounts
+            .iter()
+            .filter(|a| a.is_signer)
+            .map(|a| a.key()),
+    );
+
+    Ok(metadata_auth)
+}
+
+/// Returns the config, tree and authority bump of the first tree in the chain of successors of
+/// a tree that has room for `count` more leaves. The configs of the successors up to that tree,
+/// and the tree itself, must be among `accounts`, whose number also bounds how far the chain is
+/// followed.
+fn successor_for_mint<'info>(
+    authority: &TreeConfig,
+    accounts: &[AccountInfo<'info>],
+    count: u64,
+) -> Result<(Account<'info, TreeConfig>, AccountInfo<'info>, u8)> {
+    let find = |key: Pubkey| {
+        accounts
+            .iter()
+            .find(|a| a.key() == key)
+            .ok_or(BubblegumError::SuccessorTreeAccountsMissing)
+    };
+
+    let mut successor_tree = authority
+        .successor_tree
+        .ok_or(BubblegumError::InsufficientMintCapacity)?;
+    for _ in 0..accounts.len() {
+        let (successor_authority, bump) =
+            Pubkey::find_program_address(&[successor_tree.as_ref()], &crate::id());
+        let successor = Account::<TreeConfig>::try_from(find(successor_authority)?)?;
+        if successor.contains_mint_capacity(count) {
+            let merkle_tree = find(successor_tree)?.clone();
+            return Ok((successor, merkle_tree, bump));
+        }
+        successor_tree = successor
+            .successor_tree
+            .ok_or(BubblegumError::InsufficientMintCapacity)?;
+    }
+    Err(BubblegumError::SuccessorTreeAccountsMissing.into())
+}
+
+/// Mints a leaf with `flags` set, rolling over into the tree's successors like `mint_v1`.
+fn process_mint<'info>(
+    ctx: Context<'_, '_, '_, 'info, MintV1<'info>>,
+    message: MetadataArgs,
+    flags: u8,
+) -> Result<()> {
+    // TODO -> Separate V1 / V1 into seperate instructions
+    let metadata_auth = assert_can_mint(&ctx, 1)?;
+
+    let owner = ctx.accounts.leaf_owner.key();
+    let delegate = ctx.accounts.leaf_delegate.key();
+    let authority = &mut ctx.accounts.tree_authority;
+
+    if authority.contains_mint_capacity(1) {
+        process_mint_v1(
+            message,
+            owner,
+            delegate,
+            flags,
+            metadata_auth,
+            *ctx.bumps.get("tree_authority").unwrap(),
+            authority.num_minted,
+            authority,
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+        )?;
+        authority.increment_mint_count();
+        return Ok(());
+    }
+
+    let (mut successor, merkle_tree, bump) =
+        successor_for_mint(authority, ctx.remaining_accounts, 1)?;
+    process_mint_v1(
+        message,
+        owner,
+        delegate,
+        flags,
+        metadata_auth,
+        bump,
+        successor.num_minted,
+        &mut successor,
+        &merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+    )?;
+    successor.increment_mint_count();
+    successor.exit(&crate::id())
+}
+
+fn process_mint_batch_v1<'info>(
+    mints: &[BatchMintArgs],
+    metadata_auth: HashSet<Pubkey>,
+    authority_bump: u8,
+    authority: &mut Account<'info, TreeConfig>,
+    merkle_tree: &AccountInfo<'info>,
+    wrapper: &Program<'info, Wrapper>,
+    compression_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let first_nonce = authority.num_minted;
+
+    for (nonce, mint) in (first_nonce..).zip(mints.iter()) {
+        process_mint_v1(
+            mint.metadata.clone(),
+            mint.leaf_owner,
+            mint.leaf_delegate,
+            0,
+            metadata_auth.clone(),
+            authority_bump,
+            nonce,
+            authority,
+            merkle_tree,
+            wrapper,
+            compression_program,
+        )?;
+    }
+
+    authority.increment_mint_count_by(mints.len() as u64);
+    Ok(())
+}
+
 fn process_mint_v1<'info>(
     message: MetadataArgs,
     owner: Pubkey,
     delegate: Pubkey,
+    flags: u8,
     metadata_auth: HashSet<Pubkey>,
     authority_bump: u8,
+    nonce: u64,
     authority: &mut Account<'info, TreeConfig>,
     merkle_tree: &AccountInfo<'info>,
     wrapper: &Program<'info, Wrapper>,
@@ -492,19 +1503,20 @@ fn process_mint_v1<'info>(
             .as_ref(),
     );
 
-    let asset_id = get_asset_id(&merkle_tree.key(), authority.num_minted);
-    let leaf = LeafSchema::new_v0(
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+    let leaf = LeafSchema::new_with_flags(
         asset_id,
         owner,
         delegate,
-        authority.num_minted,
+        nonce,
         data_hash.to_bytes(),
         creator_hash.to_bytes(),
+        flags,
     );
     let new_nft = NewNFTEvent {
         version: Version::V1,
         metadata: message,
-        nonce: authority.num_minted,
+        nonce,
     };
 
     emit!(new_nft);
@@ -523,6 +1535,45 @@ fn process_mint_v1<'info>(
     )
 }
 
+/// Sets the `verified` flag of `creator` in `message`, returning the updated data and creator
+/// hashes.
+fn set_creator_verified(
+    message: &mut MetadataArgs,
+    creator: &Pubkey,
+    verify: bool,
+) -> Result<([u8; 32], [u8; 32])> {
+    // Calculate new creator Vec with `verified` set to true for signing creator.
+    let updated_creator_vec = message
+        .creators
+        .iter()
+        .map(|c| {
+            let verified = if c.address == *creator {
+                verify
+            } else {
+                c.verified
+            };
+            Creator {
+                address: c.address,
+                verified,
+                share: c.share,
+            }
+        })
+        .collect::<Vec<Creator>>();
+
+    // Calculate new creator hash.
+    let updated_creator_hash = hash_creators(&updated_creator_vec)?;
+
+    // Update creator Vec in metadata args.
+    message.creators = updated_creator_vec;
+
+    // Calculate new data hash.
+    let updated_data_hash = hash_metadata(message)?;
+
+    Ok((updated_data_hash, updated_creator_hash))
+}
+
+/// Only matches leaves without flags, as `verify_creator` and `unverify_creator` don't take them:
+/// frozen and non-transferable leaves can't have their creators verified or unverified.
 fn process_creator_verification<'info>(
     ctx: Context<'_, '_, '_, 'info, CreatorVerification<'info>>,
     root: [u8; 32],
@@ -561,51 +1612,214 @@ fn process_creator_verification<'info>(
         return Err(BubblegumError::DataHashMismatch.into());
     }
 
-    // Calculate new creator Vec with `verified` set to true for signing creator.
-    let updated_creator_vec = message
-        .creators
-        .iter()
-        .map(|c| {
-            let verified = if c.address == creator.key() {
-                verify
-            } else {
-                c.verified
-            };
-            Creator {
-                address: c.address,
-                verified,
-                share: c.share,
-            }
-        })
-        .collect::<Vec<Creator>>();
+    let (updated_data_hash, updated_creator_hash) =
+        set_creator_verified(&mut message, &creator, verify)?;
 
-    // Calculate new creator hash.
-    let updated_creator_hash = hash_creators(&updated_creator_vec)?;
+    // Build previous leaf struct, new leaf struct, and replace the leaf in the tree.
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+    let previous_leaf = LeafSchema::new_v0(
+        asset_id,
+        owner.key(),
+        delegate.key(),
+        nonce,
+        data_hash,
+        creator_hash,
+    );
+    let new_leaf = LeafSchema::new_v0(
+        asset_id,
+        owner.key(),
+        delegate.key(),
+        nonce,
+        updated_data_hash,
+        updated_creator_hash,
+    );
+    emit!(new_leaf.to_event());
+    replace_leaf(
+        &merkle_tree.key(),
+        *ctx.bumps.get("tree_authority").unwrap(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        ctx.remaining_accounts,
+        root,
+        previous_leaf.to_node(),
+        new_leaf.to_node(),
+        index,
+    )
+}
 
-    // Update creator Vec in metadata args.
-    message.creators = updated_creator_vec;
+/// Fails if `flags`, the current flags of a leaf, mark it frozen.
+fn assert_leaf_not_frozen(flags: u8) -> Result<()> {
+    require!(flags & LEAF_FLAG_FROZEN == 0, BubblegumError::LeafFrozen);
+    Ok(())
+}
 
-    // Calculate new data hash.
-    let updated_data_hash = hash_metadata(&message)?;
+/// Fails unless a leaf with the current flags `flags` can change owners.
+fn assert_leaf_transferable(flags: u8) -> Result<()> {
+    assert_leaf_not_frozen(flags)?;
+    require!(
+        flags & LEAF_FLAG_NON_TRANSFERABLE == 0,
+        BubblegumError::LeafNonTransferable
+    );
+    Ok(())
+}
+
+/// Sets or clears the frozen flag of a leaf whose current flags are `flags`.
+fn process_leaf_freeze<'info>(
+    ctx: Context<'_, '_, '_, 'info, LeafFreeze<'info>>,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    flags: u8,
+    freeze: bool,
+) -> Result<()> {
+    let is_frozen = flags & LEAF_FLAG_FROZEN != 0;
+    if freeze && is_frozen {
+        return Err(BubblegumError::LeafAlreadyFrozen.into());
+    } else if !freeze && !is_frozen {
+        return Err(BubblegumError::LeafNotFrozen.into());
+    }
+    let new_flags = if freeze {
+        flags | LEAF_FLAG_FROZEN
+    } else {
+        flags & !LEAF_FLAG_FROZEN
+    };
+
+    let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+    let owner = ctx.accounts.leaf_owner.key();
+    let delegate = ctx.accounts.leaf_delegate.key();
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+    let previous_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        owner,
+        delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        flags,
+    );
+    let new_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        owner,
+        delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        new_flags,
+    );
+    wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+    emit!(new_leaf.to_event());
+    replace_leaf(
+        &merkle_tree.key(),
+        *ctx.bumps.get("tree_authority").unwrap(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        ctx.remaining_accounts,
+        root,
+        previous_leaf.to_node(),
+        new_leaf.to_node(),
+        index,
+    )
+}
+
+/// Transfers a leaf whose current flags are `flags`.
+fn process_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    flags: u8,
+) -> Result<()> {
+    assert_leaf_transferable(flags)?;
+    // TODO add back version to select hash schema
+    let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+    let owner = ctx.accounts.leaf_owner.to_account_info();
+    let delegate = ctx.accounts.leaf_delegate.to_account_info();
+
+    // Transfers must be initiated by either the leaf owner or leaf delegate.
+    require!(
+        owner.is_signer || delegate.is_signer,
+        BubblegumError::LeafAuthorityMustSign
+    );
+    let new_owner = ctx.accounts.new_leaf_owner.key();
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+    let previous_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        owner.key(),
+        delegate.key(),
+        nonce,
+        data_hash,
+        creator_hash,
+        flags,
+    );
+    // New leafs are instantiated with no delegate
+    let new_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        new_owner,
+        new_owner,
+        nonce,
+        data_hash,
+        creator_hash,
+        flags,
+    );
+    emit!(new_leaf.to_event());
+    replace_leaf(
+        &merkle_tree.key(),
+        *ctx.bumps.get("tree_authority").unwrap(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        ctx.remaining_accounts,
+        root,
+        previous_leaf.to_node(),
+        new_leaf.to_node(),
+        index,
+    )
+}
 
-    // Build previous leaf struct, new leaf struct, and replace the leaf in the tree.
+/// Delegates a leaf whose current flags are `flags`.
+fn process_delegate<'info>(
+    ctx: Context<'_, '_, '_, 'info, Delegate<'info>>,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    flags: u8,
+) -> Result<()> {
+    assert_leaf_not_frozen(flags)?;
+    let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+    let owner = ctx.accounts.leaf_owner.key();
+    let previous_delegate = ctx.accounts.previous_leaf_delegate.key();
+    let new_delegate = ctx.accounts.new_leaf_delegate.key();
     let asset_id = get_asset_id(&merkle_tree.key(), nonce);
-    let previous_leaf = LeafSchema::new_v0(
+    let previous_leaf = LeafSchema::new_with_flags(
         asset_id,
-        owner.key(),
-        delegate.key(),
+        owner,
+        previous_delegate,
         nonce,
         data_hash,
         creator_hash,
+        flags,
     );
-    let new_leaf = LeafSchema::new_v0(
+    let new_leaf = LeafSchema::new_with_flags(
         asset_id,
-        owner.key(),
-        delegate.key(),
+        owner,
+        new_delegate,
         nonce,
-        updated_data_hash,
-        updated_creator_hash,
+        data_hash,
+        creator_hash,
+        flags,
     );
+    wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
     emit!(new_leaf.to_event());
     replace_leaf(
         &merkle_tree.key(),
@@ -622,6 +1836,352 @@ fn process_creator_verification<'info>(
     )
 }
 
+/// Burns a leaf whose current flags are `flags`.
+fn process_burn<'info>(
+    ctx: Context<'_, '_, '_, 'info, Burn<'info>>,
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    flags: u8,
+) -> Result<()> {
+    assert_leaf_not_frozen(flags)?;
+    let owner = ctx.accounts.leaf_owner.to_account_info();
+    let delegate = ctx.accounts.leaf_delegate.to_account_info();
+
+    // Burn must be initiated by either the leaf owner or leaf delegate.
+    require!(
+        owner.is_signer || delegate.is_signer,
+        BubblegumError::LeafAuthorityMustSign
+    );
+    let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+
+    let previous_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        owner.key(),
+        delegate.key(),
+        nonce,
+        data_hash,
+        creator_hash,
+        flags,
+    );
+    emit!(previous_leaf.to_event());
+    let new_leaf = Node::default();
+    wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+    replace_leaf(
+        &merkle_tree.key(),
+        *ctx.bumps.get("tree_authority").unwrap(),
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.tree_authority.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &ctx.accounts.log_wrapper.to_account_info(),
+        ctx.remaining_accounts,
+        root,
+        previous_leaf.to_node(),
+        new_leaf,
+        index,
+    )
+}
+
+/// Redeems a leaf whose current flags are `flags`, returning its voucher. Non-transferable leaves
+/// can't be redeemed, since the NFT they'd be decompressed into could change owners.
+fn process_redeem<'info>(
+    tree_authority: &AccountInfo<'info>,
+    authority_bump: u8,
+    owner: Pubkey,
+    delegate: Pubkey,
+    merkle_tree: &AccountInfo<'info>,
+    log_wrapper: &Program<'info, Wrapper>,
+    compression_program: &AccountInfo<'info>,
+    proof: &[AccountInfo<'info>],
+    root: [u8; 32],
+    data_hash: [u8; 32],
+    creator_hash: [u8; 32],
+    nonce: u64,
+    index: u32,
+    flags: u8,
+) -> Result<Voucher> {
+    assert_leaf_transferable(flags)?;
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+    let previous_leaf = LeafSchema::new_with_flags(
+        asset_id,
+        owner,
+        delegate,
+        nonce,
+        data_hash,
+        creator_hash,
+        flags,
+    );
+    emit!(previous_leaf.to_event());
+    let new_leaf = Node::default();
+    wrap_event(new_leaf.try_to_vec()?, log_wrapper)?;
+    replace_leaf(
+        &merkle_tree.key(),
+        authority_bump,
+        compression_program,
+        tree_authority,
+        merkle_tree,
+        &log_wrapper.to_account_info(),
+        proof,
+        root,
+        previous_leaf.to_node(),
+        new_leaf,
+        index,
+    )?;
+    let expires_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(VOUCHER_LIFETIME_SECONDS)
+        .ok_or(BubblegumError::NumericalOverflowError)?;
+    Ok(Voucher::new(
+        previous_leaf,
+        index,
+        merkle_tree.key(),
+        VoucherExpiry(Some(expires_at)),
+    ))
+}
+
+/// Puts the leaf of `voucher` back in its tree, for its owner `leaf_owner`.
+fn process_cancel_redeem<'info>(
+    voucher: &Voucher,
+    leaf_owner: &Pubkey,
+    tree_authority: &AccountInfo<'info>,
+    authority_bump: u8,
+    merkle_tree: &AccountInfo<'info>,
+    log_wrapper: &Program<'info, Wrapper>,
+    compression_program: &AccountInfo<'info>,
+    proof: &[AccountInfo<'info>],
+    root: [u8; 32],
+) -> Result<()> {
+    match voucher.leaf_schema {
+        LeafSchema::V1 { owner, .. } | LeafSchema::V2 { owner, .. } => assert_pubkey_equal(
+            leaf_owner,
+            &owner,
+            Some(BubblegumError::AssetOwnerMismatch.into()),
+        ),
+    }?;
+    emit!(voucher.leaf_schema.to_event());
+    wrap_event(voucher.leaf_schema.try_to_vec()?, log_wrapper)?;
+
+    replace_leaf(
+        &merkle_tree.key(),
+        authority_bump,
+        compression_program,
+        tree_authority,
+        merkle_tree,
+        &log_wrapper.to_account_info(),
+        proof,
+        root,
+        [0; 32],
+        voucher.leaf_schema.to_node(),
+        voucher.index,
+    )
+}
+
+/// Decompresses the leaf of `voucher` into an NFT with `metadata`, owned by `leaf_owner`.
+fn process_decompress<'info>(
+    voucher: &Voucher,
+    leaf_owner: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    mint_bump: u8,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    metadata_account: &AccountInfo<'info>,
+    master_edition: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    sysvar_rent: &AccountInfo<'info>,
+    token_metadata_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+    metadata: MetadataArgs,
+) -> Result<()> {
+    // Allocate and create mint
+    let incoming_data_hash = hash_metadata(&metadata)?;
+    let event = match voucher.leaf_schema {
+        LeafSchema::V1 {
+            owner,
+            data_hash,
+            nonce,
+            ..
+        }
+        | LeafSchema::V2 {
+            owner,
+            data_hash,
+            nonce,
+            ..
+        } => {
+            if !cmp_bytes(&data_hash, &incoming_data_hash, 32) {
+                return Err(BubblegumError::HashingMismatch.into());
+            }
+            if !cmp_pubkeys(&owner, leaf_owner.key) {
+                return Err(BubblegumError::AssetOwnerMismatch.into());
+            }
+            NFTDecompressionEvent {
+                version: Version::V1,
+                tree_id: voucher.merkle_tree.key(),
+                id: get_asset_id(&voucher.merkle_tree.key(), nonce),
+                nonce,
+            }
+        }
+    };
+    match metadata.token_program_version {
+        TokenProgramVersion::Original => {
+            if mint.data_is_empty() {
+                invoke_signed(
+                    &system_instruction::create_account(
+                        &leaf_owner.key(),
+                        &mint.key(),
+                        Rent::get()?.minimum_balance(SplMint::LEN),
+                        SplMint::LEN as u64,
+                        &spl_token::id(),
+                    ),
+                    &[
+                        leaf_owner.to_account_info(),
+                        mint.to_account_info(),
+                        system_program.to_account_info(),
+                    ],
+                    &[&[
+                        ASSET_PREFIX.as_bytes(),
+                        voucher.merkle_tree.key().as_ref(),
+                        voucher.leaf_schema.nonce().to_le_bytes().as_ref(),
+                        &[mint_bump],
+                    ]],
+                )?;
+                invoke(
+                    &spl_token::instruction::initialize_mint2(
+                        &spl_token::id(),
+                        &mint.key(),
+                        &mint_authority.key(),
+                        None,
+                        0,
+                    )?,
+                    &[token_program.to_account_info(), mint.to_account_info()],
+                )?;
+            }
+            if token_account.data_is_empty() {
+                invoke(
+                    &spl_associated_token_account::instruction::create_associated_token_account(
+                        &leaf_owner.key(),
+                        &leaf_owner.key(),
+                        &mint.key(),
+                        &spl_token::id(),
+                    ),
+                    &[
+                        leaf_owner.to_account_info(),
+                        mint.to_account_info(),
+                        token_account.to_account_info(),
+                        token_program.to_account_info(),
+                        associated_token_program.to_account_info(),
+                        system_program.to_account_info(),
+                        sysvar_rent.to_account_info(),
+                    ],
+                )?;
+            }
+            invoke_signed(
+                &spl_token::instruction::mint_to(
+                    &spl_token::id(),
+                    &mint.key(),
+                    &token_account.key(),
+                    &mint_authority.key(),
+                    &[],
+                    1,
+                )?,
+                &[
+                    mint.to_account_info(),
+                    token_account.to_account_info(),
+                    mint_authority.to_account_info(),
+                    token_program.to_account_info(),
+                ],
+                &[&[mint.key().as_ref(), &[mint_authority_bump]]],
+            )?;
+        }
+        TokenProgramVersion::Token2022 => return Err(ProgramError::InvalidArgument.into()),
+    }
+
+    let metadata_infos = vec![
+        metadata_account.to_account_info(),
+        mint.to_account_info(),
+        mint_authority.to_account_info(),
+        leaf_owner.to_account_info(),
+        token_metadata_program.to_account_info(),
+        token_program.to_account_info(),
+        system_program.to_account_info(),
+        sysvar_rent.to_account_info(),
+    ];
+
+    let master_edition_infos = vec![
+        master_edition.to_account_info(),
+        mint.to_account_info(),
+        mint_authority.to_account_info(),
+        leaf_owner.to_account_info(),
+        metadata_account.to_account_info(),
+        token_metadata_program.to_account_info(),
+        token_program.to_account_info(),
+        system_program.to_account_info(),
+        sysvar_rent.to_account_info(),
+    ];
+
+    msg!("Creating metadata!");
+    invoke_signed(
+        &mpl_token_metadata::instruction::create_metadata_accounts_v2(
+            token_metadata_program.key(),
+            metadata_account.key(),
+            mint.key(),
+            mint_authority.key(),
+            leaf_owner.key(),
+            mint_authority.key(),
+            metadata.name.clone(),
+            metadata.symbol.clone(),
+            metadata.uri.clone(),
+            if !metadata.creators.is_empty() {
+                let mut amended_metadata_creators = metadata.creators;
+                amended_metadata_creators.push(Creator {
+                    address: mint_authority.key(),
+                    verified: true,
+                    share: 0,
+                });
+                Some(
+                    amended_metadata_creators
+                        .iter()
+                        .map(|c| c.adapt())
+                        .collect(),
+                )
+            } else {
+                None
+            },
+            metadata.seller_fee_basis_points,
+            true,
+            metadata.is_mutable,
+            metadata.collection.map(|c| c.adapt()),
+            metadata.uses.map(|u| u.adapt()),
+        ),
+        metadata_infos.as_slice(),
+        &[&[mint.key().as_ref(), &[mint_authority_bump]]],
+    )?;
+
+    msg!("Creating master edition!");
+    invoke_signed(
+        &mpl_token_metadata::instruction::create_master_edition_v3(
+            token_metadata_program.key(),
+            master_edition.key(),
+            mint.key(),
+            mint_authority.key(),
+            mint_authority.key(),
+            metadata_account.key(),
+            leaf_owner.key(),
+            Some(0),
+        ),
+        master_edition_infos.as_slice(),
+        &[&[mint.key().as_ref(), &[mint_authority_bump]]],
+    )?;
+    emit!(event);
+    Ok(())
+}
+
+/// Only matches leaves without flags, as the collection verification instructions don't take
+/// them: frozen and non-transferable leaves can't have their collection verified or unverified.
 fn process_collection_verification<'info>(
     ctx: Context<'_, '_, '_, 'info, CollectionVerification<'info>>,
     root: [u8; 32],
@@ -793,6 +2353,190 @@ fn process_collection_verification<'info>(
     )
 }
 
+fn assert_update_authority<'info>(
+    ctx: &Context<'_, '_, '_, 'info, UpdateMetadata<'info>>,
+    message: &MetadataArgs,
+) -> Result<()> {
+    match &message.collection {
+        // Leaves in a verified collection are updated by the collection authority.
+        Some(collection) if collection.verified => {
+            let collection_mint = ctx.accounts.collection_mint.to_account_info();
+            let collection_metadata = Account::<TokenMetadata>::try_from(
+                &ctx.accounts.collection_metadata.to_account_info(),
+            )?;
+            require!(
+                cmp_pubkeys(&collection.key, collection_mint.key)
+                    && cmp_pubkeys(&collection_metadata.mint, collection_mint.key),
+                BubblegumError::CollectionNotFound
+            );
+
+            let collection_authority_record = ctx
+                .accounts
+                .collection_authority_record_pda
+                .to_account_info();
+            let collection_authority_record =
+                if cmp_pubkeys(collection_authority_record.key, &crate::id()) {
+                    None
+                } else {
+                    Some(&collection_authority_record)
+                };
+
+            assert_has_collection_authority(
+                &ctx.accounts.authority.to_account_info(),
+                &collection_metadata,
+                collection_mint.key,
+                collection_authority_record,
+            )?;
+        }
+        _ => require!(
+            cmp_pubkeys(
+                &ctx.accounts.authority.key(),
+                &ctx.accounts.tree_authority.tree_creator
+            ),
+            BubblegumError::UpdateAuthorityIncorrect
+        ),
+    }
+    Ok(())
+}
+
+// Decrements the size of the sized collection a burnt leaf was verified in. Burns aren't signed by
+// the collection authority, so the collection authority approves `bubblegum_signer` through a
+// collection authority record, and Bubblegum signs as both. Without an approved record the size
+// can't be kept, so the burn fails rather than letting the size drift.
+fn decrement_collection_size<'info>(
+    ctx: &Context<'_, '_, '_, 'info, BurnWithMetadata<'info>>,
+    collection: &Pubkey,
+    collection_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let (collection_mint, collection_metadata_info, collection_authority_record) =
+        match collection_accounts {
+            [mint, metadata, record] => (mint.clone(), metadata.clone(), record.clone()),
+            _ => return Err(BubblegumError::CollectionNotFound.into()),
+        };
+    let token_metadata_program = ctx.accounts.token_metadata_program.to_account_info();
+    let bubblegum_signer = ctx.accounts.bubblegum_signer.to_account_info();
+
+    let (expected_metadata, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_ref(),
+            token_metadata_program.key.as_ref(),
+            collection.as_ref(),
+        ],
+        token_metadata_program.key,
+    );
+    let (expected_record, _) = Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::state::PREFIX.as_ref(),
+            token_metadata_program.key.as_ref(),
+            collection.as_ref(),
+            mpl_token_metadata::state::COLLECTION_AUTHORITY.as_ref(),
+            bubblegum_signer.key.as_ref(),
+        ],
+        token_metadata_program.key,
+    );
+    require!(
+        cmp_pubkeys(collection, collection_mint.key)
+            && cmp_pubkeys(&expected_metadata, collection_metadata_info.key)
+            && cmp_pubkeys(&expected_record, collection_authority_record.key),
+        BubblegumError::CollectionNotFound
+    );
+
+    // There is no size left to keep once the collection NFT itself is burnt.
+    if collection_metadata_info.data_is_empty() {
+        return Ok(());
+    }
+    let collection_metadata = Account::<TokenMetadata>::try_from(&collection_metadata_info)?;
+    // A size that already drifted to zero is left for `reconcile_collection_size` to fix, rather
+    // than keeping the leaf from being burnt.
+    let new_size = match collection_metadata.collection_details {
+        Some(CollectionDetails::V1 { size }) => size.saturating_sub(1),
+        None => return Ok(()),
+    };
+
+    let is_approved = cmp_pubkeys(
+        collection_authority_record.owner,
+        token_metadata_program.key,
+    ) && collection_authority_record
+        .try_borrow_data()?
+        .first()
+        .map_or(false, |key| {
+            *key == mpl_token_metadata::state::Key::CollectionAuthorityRecord as u8
+        });
+    if !is_approved {
+        return Err(BubblegumError::CollectionNotApproved.into());
+    }
+
+    invoke_signed(
+        &mpl_token_metadata::instruction::bubblegum_set_collection_size(
+            token_metadata_program.key(),
+            collection_metadata_info.key(),
+            bubblegum_signer.key(),
+            collection_mint.key(),
+            bubblegum_signer.key(),
+            Some(collection_authority_record.key()),
+            new_size,
+        ),
+        &[
+            collection_metadata_info,
+            bubblegum_signer.clone(),
+            collection_mint,
+            bubblegum_signer,
+            collection_authority_record,
+        ],
+        &[&[
+            COLLECTION_CPI_PREFIX.as_bytes(),
+            &[ctx.bumps["bubblegum_signer"]],
+        ]],
+    )?;
+    Ok(())
+}
+
+/// Applies `update_args` to `message`. Creators can only be marked as verified if they already
+/// were, or if they are the signing `authority`.
+fn apply_metadata_update(
+    message: &mut MetadataArgs,
+    update_args: UpdateArgs,
+    authority: &Pubkey,
+) -> Result<()> {
+    if let Some(name) = update_args.name {
+        message.name = name;
+    }
+    if let Some(symbol) = update_args.symbol {
+        message.symbol = symbol;
+    }
+    if let Some(uri) = update_args.uri {
+        message.uri = uri;
+    }
+    if let Some(creators) = update_args.creators {
+        for creator in creators.iter().filter(|c| c.verified) {
+            let was_verified = message
+                .creators
+                .iter()
+                .any(|c| c.verified && c.address == creator.address);
+            if !was_verified && creator.address != *authority {
+                return Err(BubblegumError::CreatorDidNotVerify.into());
+            }
+        }
+        message.creators = creators;
+    }
+    if let Some(seller_fee_basis_points) = update_args.seller_fee_basis_points {
+        message.seller_fee_basis_points = seller_fee_basis_points;
+    }
+    if let Some(primary_sale_happened) = update_args.primary_sale_happened {
+        if message.primary_sale_happened && !primary_sale_happened {
+            return Err(BubblegumError::PrimarySaleCanOnlyBeFlippedToTrue.into());
+        }
+        message.primary_sale_happened = primary_sale_happened;
+    }
+    if let Some(is_mutable) = update_args.is_mutable {
+        if !message.is_mutable && is_mutable {
+            return Err(BubblegumError::IsMutableCanOnlyBeFlippedToFalse.into());
+        }
+        message.is_mutable = is_mutable;
+    }
+    assert_metadata_is_mpl_compatible(message)
+}
+
 #[program]
 pub mod bubblegum {
     use super::*;
@@ -811,6 +2555,11 @@ pub mod bubblegum {
             tree_delegate: ctx.accounts.tree_creator.key(),
             total_mint_capacity: 1 << max_depth,
             num_minted: 0,
+            is_public: false,
+            successor_tree: None,
+            num_vouchers: 0,
+            is_successor: false,
+            is_closable: false,
         });
         let authority_pda_signer = &[&seeds[..]];
         let cpi_ctx = CpiContext::new_with_signer(
@@ -830,57 +2579,239 @@ pub mod bubblegum {
         Ok(())
     }
 
-    pub fn mint_v1(ctx: Context<MintV1>, message: MetadataArgs) -> Result<()> {
-        // TODO -> Separate V1 / V1 into seperate instructions
-        let payer = ctx.accounts.payer.key();
-        let incoming_tree_delegate = ctx.accounts.tree_delegate.key();
+    /// Closes a closable tree whose leaves have all been burned, decompressed or never minted,
+    /// refunding the rent of the tree and its config to the tree creator. Trees with vouchers
+    /// that can still put their leaf back, with a print source, or that are the successors of
+    /// other trees can't be closed.
+    pub fn close_tree(ctx: Context<CloseTree>) -> Result<()> {
+        let tree_authority = &ctx.accounts.tree_authority;
+        require!(tree_authority.is_closable, BubblegumError::TreeNotClosable);
+        require!(
+            tree_authority.num_vouchers == 0,
+            BubblegumError::TreeHasVouchers
+        );
+        require!(
+            !tree_authority.is_successor,
+            BubblegumError::TreeIsSuccessor
+        );
+        require!(
+            ctx.accounts.print_source.data_is_empty(),
+            BubblegumError::TreeHasPrintSource
+        );
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        assert_tree_is_empty(&merkle_tree)?;
 
-        let owner = ctx.accounts.leaf_owner.key();
-        let delegate = ctx.accounts.leaf_delegate.key();
-        let authority = &mut ctx.accounts.tree_authority;
-        let tree_creator = authority.tree_creator;
-        let tree_delegate = authority.tree_delegate;
-        let merkle_tree = &ctx.accounts.merkle_tree;
+        let seed = merkle_tree.key();
+        let seeds = &[seed.as_ref(), &[*ctx.bumps.get("tree_authority").unwrap()]];
+        let authority_pda_signer = &[&seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            spl_account_compression::cpi::accounts::CloseTree {
+                merkle_tree,
+                authority: ctx.accounts.tree_authority.to_account_info(),
+                recipient: ctx.accounts.tree_creator.to_account_info(),
+            },
+            authority_pda_signer,
+        );
+        spl_account_compression::cpi::close_empty_tree(cpi_ctx)
+    }
+
+    /// Makes a tree that wasn't minted into yet closable. From then on it counts its vouchers, so
+    /// that `close_tree` can tell whether any leaf can still come back.
+    pub fn set_tree_closable(ctx: Context<SetTreeClosable>) -> Result<()> {
+        let tree_authority = &mut ctx.accounts.tree_authority;
+        require!(
+            tree_authority.num_minted == 0,
+            BubblegumError::TreeAlreadyMinted
+        );
+        tree_authority.is_closable = true;
+        Ok(())
+    }
+
+    pub fn set_tree_public(ctx: Context<SetTreePublic>, is_public: bool) -> Result<()> {
+        ctx.accounts.tree_authority.is_public = is_public;
+        Ok(())
+    }
+
+    /// Links the tree that mints roll over into once this tree is full. The successor must
+    /// belong to the same creator and must not have a successor itself, which keeps chains of
+    /// trees free of cycles. It can only be set once.
+    pub fn set_successor_tree(ctx: Context<SetSuccessorTree>) -> Result<()> {
+        if ctx.accounts.tree_authority.successor_tree.is_some() {
+            return Err(BubblegumError::SuccessorTreeAlreadySet.into());
+        }
+        if ctx.accounts.successor_authority.successor_tree.is_some() {
+            return Err(BubblegumError::InvalidSuccessorTree.into());
+        }
+        ctx.accounts.tree_authority.successor_tree = Some(ctx.accounts.successor_tree.key());
+        ctx.accounts.successor_authority.is_successor = true;
+        Ok(())
+    }
+
+    pub fn add_minter(ctx: Context<AddMinter>, mint_quota: u64) -> Result<()> {
+        ctx.accounts.minter_record.set_inner(MinterRecord {
+            minter: ctx.accounts.minter.key(),
+            mint_quota,
+        });
+        Ok(())
+    }
+
+    pub fn remove_minter(_ctx: Context<RemoveMinter>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Lets the leaves of a tree whose data hash is `data_hash` decompress into prints of
+    /// `master_mint` with `decompress_print`, moving the master edition token to the tree
+    /// authority. Anyone can mint into public trees, so they can't have a print source.
+    pub fn set_print_source(ctx: Context<SetPrintSource>, data_hash: [u8; 32]) -> Result<()> {
+        require!(
+            !ctx.accounts.tree_authority.is_public,
+            BubblegumError::PublicTreePrintSource
+        );
+        if ctx.accounts.source_token_account.data_is_empty() {
+            invoke(
+                &spl_associated_token_account::instruction::create_associated_token_account(
+                    &ctx.accounts.tree_creator.key(),
+                    &ctx.accounts.tree_authority.key(),
+                    &ctx.accounts.master_mint.key(),
+                    &spl_token::id(),
+                ),
+                &[
+                    ctx.accounts.tree_creator.to_account_info(),
+                    ctx.accounts.source_token_account.to_account_info(),
+                    ctx.accounts.tree_authority.to_account_info(),
+                    ctx.accounts.master_mint.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    ctx.accounts.associated_token_program.to_account_info(),
+                    ctx.accounts.sysvar_rent.to_account_info(),
+                ],
+            )?;
+        }
+        invoke(
+            &spl_token::instruction::transfer(
+                &spl_token::id(),
+                &ctx.accounts.creator_token_account.key(),
+                &ctx.accounts.source_token_account.key(),
+                &ctx.accounts.tree_creator.key(),
+                &[],
+                1,
+            )?,
+            &[
+                ctx.accounts.creator_token_account.to_account_info(),
+                ctx.accounts.source_token_account.to_account_info(),
+                ctx.accounts.tree_creator.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+        )?;
+        ctx.accounts.print_source.set_inner(PrintSource {
+            master_mint: ctx.accounts.master_mint.key(),
+            data_hash,
+        });
+        Ok(())
+    }
+
+    /// Returns the master edition token of a tree's print source to the tree creator.
+    pub fn remove_print_source(ctx: Context<RemovePrintSource>) -> Result<()> {
+        let merkle_tree = ctx.accounts.merkle_tree.key();
+        let seeds = &[
+            merkle_tree.as_ref(),
+            &[*ctx.bumps.get("tree_authority").unwrap()],
+        ];
+        invoke_signed(
+            &spl_token::instruction::transfer(
+                &spl_token::id(),
+                &ctx.accounts.source_token_account.key(),
+                &ctx.accounts.creator_token_account.key(),
+                &ctx.accounts.tree_authority.key(),
+                &[],
+                1,
+            )?,
+            &[
+                ctx.accounts.source_token_account.to_account_info(),
+                ctx.accounts.creator_token_account.to_account_info(),
+                ctx.accounts.tree_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+        invoke_signed(
+            &spl_token::instruction::close_account(
+                &spl_token::id(),
+                &ctx.accounts.source_token_account.key(),
+                &ctx.accounts.tree_creator.key(),
+                &ctx.accounts.tree_authority.key(),
+                &[],
+            )?,
+            &[
+                ctx.accounts.source_token_account.to_account_info(),
+                ctx.accounts.tree_creator.to_account_info(),
+                ctx.accounts.tree_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+            ],
+            &[&seeds[..]],
+        )?;
+        Ok(())
+    }
+
+    /// Mints into the first successor down the tree's chain of successors with room instead
+    /// once the tree is full, if the configs of the successors up to it and its tree are passed
+    /// as remaining accounts. Whoever can mint into a tree can mint through it into its
+    /// successors.
+    pub fn mint_v1<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintV1<'info>>,
+        message: MetadataArgs,
+    ) -> Result<()> {
+        process_mint(ctx, message, 0)
+    }
 
+    /// Mints a leaf with `flags` set, like `mint_v1`. Only `LEAF_FLAG_NON_TRANSFERABLE` can be
+    /// set at mint; leaves are frozen through `freeze`.
+    pub fn mint_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintV1<'info>>,
+        message: MetadataArgs,
+        flags: u8,
+    ) -> Result<()> {
         require!(
-            incoming_tree_delegate == tree_creator || incoming_tree_delegate == tree_delegate,
-            BubblegumError::TreeAuthorityIncorrect,
+            flags & !LEAF_FLAG_NON_TRANSFERABLE == 0,
+            BubblegumError::InvalidLeafFlags
         );
+        process_mint(ctx, message, flags)
+    }
 
-        if !authority.contains_mint_capacity(1) {
-            return Err(BubblegumError::InsufficientMintCapacity.into());
-        }
-
-        // Create a HashSet to store signers to use with creator validation.  Any signer can be
-        // counted as a validated creator.
-        let mut metadata_auth = HashSet::<Pubkey>::new();
-        metadata_auth.insert(payer);
-        metadata_auth.insert(tree_delegate);
+    /// Rolls over like `mint_v1`. The whole batch goes into the first successor it fits in when
+    /// it doesn't fit in the tree.
+    pub fn mint_batch_v1<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintV1<'info>>,
+        mints: Vec<BatchMintArgs>,
+    ) -> Result<()> {
+        let count = mints.len() as u64;
+        let metadata_auth = assert_can_mint(&ctx, count)?;
 
-        // If there are any remaining accounts that are also signers, they can also be used for
-        // creator validation.
-        metadata_auth.extend(
-            ctx.remaining_accounts
-                .iter()
-                .filter(|a| a.is_signer)
-                .map(|a| a.key()),
-        );
+        if ctx.accounts.tree_authority.contains_mint_capacity(count) {
+            return process_mint_batch_v1(
+                &mints,
+                metadata_auth,
+                *ctx.bumps.get("tree_authority").unwrap(),
+                &mut ctx.accounts.tree_authority,
+                &ctx.accounts.merkle_tree,
+                &ctx.accounts.log_wrapper,
+                &ctx.accounts.compression_program,
+            );
+        }
 
-        process_mint_v1(
-            message,
-            owner,
-            delegate,
+        let (mut successor, merkle_tree, bump) =
+            successor_for_mint(&ctx.accounts.tree_authority, ctx.remaining_accounts, count)?;
+        process_mint_batch_v1(
+            &mints,
             metadata_auth,
-            *ctx.bumps.get("tree_authority").unwrap(),
-            authority,
-            merkle_tree,
+            bump,
+            &mut successor,
+            &merkle_tree,
             &ctx.accounts.log_wrapper,
             &ctx.accounts.compression_program,
         )?;
-
-        authority.increment_mint_count();
-
-        Ok(())
+        successor.exit(&crate::id())
     }
 
     pub fn verify_creator<'info>(
@@ -925,6 +2856,90 @@ pub mod bubblegum {
         )
     }
 
+    /// Verifies the signing creator on every leaf in `leaves`, all from the same tree. The
+    /// leaves are replaced one after the other, so the roots their proofs were computed against
+    /// fall further behind with each one; batches can't have more leaves than the tree keeps
+    /// change logs.
+    pub fn verify_creator_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchCreatorVerification<'info>>,
+        leaves: Vec<BatchVerifyCreatorArgs>,
+    ) -> Result<()> {
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let creator = ctx.accounts.creator.key();
+
+        require!(
+            leaves.len() <= get_max_buffer_size(&merkle_tree)? as usize,
+            BubblegumError::BatchTooLarge,
+        );
+
+        let mut indices = HashSet::new();
+        let mut proofs = ctx.remaining_accounts;
+        for leaf in leaves {
+            // Later replacements of a leaf would not match the leaf the earlier ones left.
+            require!(
+                indices.insert(leaf.index),
+                BubblegumError::DuplicateLeafInBatch,
+            );
+            let proof_len = leaf.proof_len as usize;
+            require!(
+                proof_len <= proofs.len(),
+                BubblegumError::ProofLengthMismatch,
+            );
+            let (proof, rest) = proofs.split_at(proof_len);
+            proofs = rest;
+
+            let mut message = leaf.metadata;
+            if message.creators.is_empty() {
+                return Err(BubblegumError::NoCreatorsPresent.into());
+            }
+            if !message.creators.iter().any(|c| c.address == creator) {
+                return Err(BubblegumError::CreatorNotFound.into());
+            }
+
+            let data_hash = hash_metadata(&message)?;
+            let creator_hash = hash_creators(&message.creators)?;
+            let (updated_data_hash, updated_creator_hash) =
+                set_creator_verified(&mut message, &creator, true)?;
+
+            let asset_id = get_asset_id(&merkle_tree.key(), leaf.nonce);
+            let previous_leaf = LeafSchema::new_with_flags(
+                asset_id,
+                leaf.leaf_owner,
+                leaf.leaf_delegate,
+                leaf.nonce,
+                data_hash,
+                creator_hash,
+                leaf.flags,
+            );
+            let new_leaf = LeafSchema::new_with_flags(
+                asset_id,
+                leaf.leaf_owner,
+                leaf.leaf_delegate,
+                leaf.nonce,
+                updated_data_hash,
+                updated_creator_hash,
+                leaf.flags,
+            );
+            emit!(new_leaf.to_event());
+            replace_leaf(
+                &merkle_tree.key(),
+                *ctx.bumps.get("tree_authority").unwrap(),
+                &ctx.accounts.compression_program.to_account_info(),
+                &ctx.accounts.tree_authority.to_account_info(),
+                &merkle_tree,
+                &ctx.accounts.log_wrapper.to_account_info(),
+                proof,
+                leaf.root,
+                previous_leaf.to_node(),
+                new_leaf.to_node(),
+                leaf.index,
+            )?;
+        }
+
+        require!(proofs.is_empty(), BubblegumError::ProofLengthMismatch);
+        Ok(())
+    }
+
     pub fn verify_collection<'info>(
         ctx: Context<'_, '_, '_, 'info, CollectionVerification<'info>>,
         root: [u8; 32],
@@ -1018,43 +3033,387 @@ pub mod bubblegum {
         )
     }
 
-    pub fn transfer<'info>(
-        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+    /// `flags` are the current flags of the leaf, which it keeps.
+    pub fn update_metadata<'info>(
+        ctx: Context<'_, '_, '_, 'info, UpdateMetadata<'info>>,
         root: [u8; 32],
-        data_hash: [u8; 32],
-        creator_hash: [u8; 32],
         nonce: u64,
         index: u32,
+        current_metadata: MetadataArgs,
+        update_args: UpdateArgs,
+        flags: u8,
     ) -> Result<()> {
-        // TODO add back version to select hash schema
+        if !current_metadata.is_mutable {
+            return Err(BubblegumError::MetadataImmutable.into());
+        }
+        assert_update_authority(&ctx, &current_metadata)?;
+
+        let data_hash = hash_metadata(&current_metadata)?;
+        let creator_hash = hash_creators(&current_metadata.creators)?;
+
+        let mut message = current_metadata;
+        apply_metadata_update(&mut message, update_args, &ctx.accounts.authority.key())?;
+
+        let owner = ctx.accounts.leaf_owner.key();
+        let delegate = ctx.accounts.leaf_delegate.key();
         let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+        let previous_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner,
+            delegate,
+            nonce,
+            data_hash,
+            creator_hash,
+            flags,
+        );
+        let new_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner,
+            delegate,
+            nonce,
+            hash_metadata(&message)?,
+            hash_creators(&message.creators)?,
+            flags,
+        );
+
+        let update = MetadataUpdateEvent {
+            version: Version::V1,
+            id: asset_id,
+            nonce,
+            metadata: message,
+        };
+        emit!(update);
+        wrap_event(update.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+
+        emit!(new_leaf.to_event());
+        replace_leaf(
+            &merkle_tree.key(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.compression_program.to_account_info(),
+            &ctx.accounts.tree_authority.to_account_info(),
+            &ctx.accounts.merkle_tree.to_account_info(),
+            &ctx.accounts.log_wrapper.to_account_info(),
+            ctx.remaining_accounts,
+            root,
+            previous_leaf.to_node(),
+            new_leaf.to_node(),
+            index,
+        )
+    }
+
+    /// Sets the size of a sized collection to `size`, for sizes that drifted from the actual
+    /// count. `size` is the number of verified items `attester` counted off chain at slot
+    /// `counted_at_slot`, which it attests to with an ed25519 signature over the collection mint,
+    /// the size and the slot (the size and slot as little endian `u64`s), verified by an ed25519
+    /// program instruction right before this one. The slot is checked not to be in the future,
+    /// but the count itself is only as good as the attester, so the attester is logged along
+    /// with it as a `CollectionSizeReconciliationEvent`, for anyone to check the signature
+    /// against.
+    pub fn reconcile_collection_size(
+        ctx: Context<ReconcileCollectionSize>,
+        size: u64,
+        counted_at_slot: u64,
+    ) -> Result<()> {
+        require!(
+            counted_at_slot <= Clock::get()?.slot,
+            BubblegumError::InvalidCountedAtSlot
+        );
+        let collection_authority = ctx.accounts.collection_authority.to_account_info();
+        let collection_mint = ctx.accounts.collection_mint.to_account_info();
+        let collection_metadata = &ctx.accounts.collection_metadata;
+        require!(
+            cmp_pubkeys(&collection_metadata.mint, collection_mint.key),
+            BubblegumError::CollectionNotFound
+        );
+        let attestation = [
+            collection_mint.key.as_ref(),
+            size.to_le_bytes().as_ref(),
+            counted_at_slot.to_le_bytes().as_ref(),
+        ]
+        .concat();
+        assert_ed25519_signature(
+            &ctx.accounts.instructions,
+            ctx.accounts.attester.key,
+            &attestation,
+        )?;
+        let previous_size = match collection_metadata.collection_details {
+            Some(CollectionDetails::V1 { size }) => size,
+            None => return Err(BubblegumError::CollectionNotSized.into()),
+        };
+
+        let collection_authority_record = ctx
+            .accounts
+            .collection_authority_record_pda
+            .to_account_info();
+        let collection_authority_record =
+            if cmp_pubkeys(collection_authority_record.key, &crate::id()) {
+                None
+            } else {
+                Some(&collection_authority_record)
+            };
+        assert_has_collection_authority(
+            &collection_authority,
+            collection_metadata,
+            collection_mint.key,
+            collection_authority_record,
+        )?;
+
+        let bubblegum_signer = ctx.accounts.bubblegum_signer.to_account_info();
+        let mut bubblegum_set_collection_size_infos = vec![
+            collection_metadata.to_account_info(),
+            collection_authority.clone(),
+            collection_mint.clone(),
+            bubblegum_signer.clone(),
+        ];
+        if let Some(record) = collection_authority_record {
+            bubblegum_set_collection_size_infos.push(record.clone());
+        }
+        invoke_signed(
+            &mpl_token_metadata::instruction::bubblegum_set_collection_size(
+                ctx.accounts.token_metadata_program.key(),
+                collection_metadata.key(),
+                collection_authority.key(),
+                collection_mint.key(),
+                bubblegum_signer.key(),
+                collection_authority_record.map(|r| r.key()),
+                size,
+            ),
+            bubblegum_set_collection_size_infos.as_slice(),
+            &[&[
+                COLLECTION_CPI_PREFIX.as_bytes(),
+                &[ctx.bumps["bubblegum_signer"]],
+            ]],
+        )?;
+
+        let reconciliation = CollectionSizeReconciliationEvent {
+            version: Version::V1,
+            collection_mint: collection_mint.key(),
+            collection_authority: collection_authority.key(),
+            attester: ctx.accounts.attester.key(),
+            previous_size,
+            size,
+            counted_at_slot,
+        };
+        emit!(reconciliation);
+        wrap_event(reconciliation.try_to_vec()?, &ctx.accounts.log_wrapper)
+    }
+
+    /// Consumes `number_of_uses` uses of the leaf, like `utilize` in token-metadata. Leaves that
+    /// use `UseMethod::Burn` are burned once no uses remain, unless they are frozen. `flags` are
+    /// the current flags of the leaf.
+    pub fn utilize<'info>(
+        ctx: Context<'_, '_, '_, 'info, Utilize<'info>>,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+        current_metadata: MetadataArgs,
+        number_of_uses: u64,
+        flags: u8,
+    ) -> Result<()> {
         let owner = ctx.accounts.leaf_owner.to_account_info();
         let delegate = ctx.accounts.leaf_delegate.to_account_info();
 
-        // Transfers must be initiated by either the leaf owner or leaf delegate.
+        // Uses must be consumed by either the leaf owner or leaf delegate.
         require!(
             owner.is_signer || delegate.is_signer,
             BubblegumError::LeafAuthorityMustSign
         );
-        let new_owner = ctx.accounts.new_leaf_owner.key();
+
+        let data_hash = hash_metadata(&current_metadata)?;
+        let creator_hash = hash_creators(&current_metadata.creators)?;
+
+        let mut message = current_metadata;
+        let uses = message.uses.as_mut().ok_or(BubblegumError::Unusable)?;
+        uses.remaining = uses
+            .remaining
+            .checked_sub(number_of_uses)
+            .ok_or(BubblegumError::NotEnoughUses)?;
+        let must_burn = uses.use_method == UseMethod::Burn && uses.remaining == 0;
+        if must_burn {
+            assert_leaf_not_frozen(flags)?;
+        }
+
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+        let previous_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner.key(),
+            delegate.key(),
+            nonce,
+            data_hash,
+            creator_hash,
+            flags,
+        );
+        let used_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner.key(),
+            delegate.key(),
+            nonce,
+            hash_metadata(&message)?,
+            creator_hash,
+            flags,
+        );
+
+        let update = MetadataUpdateEvent {
+            version: Version::V1,
+            id: asset_id,
+            nonce,
+            metadata: message,
+        };
+        emit!(update);
+        wrap_event(update.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+
+        // As with `burn`, the event of a burned leaf is that of the leaf that was removed.
+        emit!(used_leaf.to_event());
+        let new_leaf = if must_burn {
+            Node::default()
+        } else {
+            used_leaf.to_node()
+        };
+        replace_leaf(
+            &merkle_tree.key(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.compression_program.to_account_info(),
+            &ctx.accounts.tree_authority.to_account_info(),
+            &ctx.accounts.merkle_tree.to_account_info(),
+            &ctx.accounts.log_wrapper.to_account_info(),
+            ctx.remaining_accounts,
+            root,
+            previous_leaf.to_node(),
+            new_leaf,
+            index,
+        )
+    }
+
+    pub fn transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        process_transfer(ctx, root, data_hash, creator_hash, nonce, index, 0)
+    }
+
+    /// Transfers a leaf that may have flags, which `transfer` can't match. Frozen leaves fail
+    /// with `LeafFrozen`.
+    pub fn transfer_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, Transfer<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        process_transfer(ctx, root, data_hash, creator_hash, nonce, index, flags)
+    }
+
+    pub fn delegate<'info>(
+        ctx: Context<'_, '_, '_, 'info, Delegate<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        process_delegate(ctx, root, data_hash, creator_hash, nonce, index, 0)
+    }
+
+    /// Delegates a leaf that may have flags, like `transfer_v2`.
+    pub fn delegate_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, Delegate<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        process_delegate(ctx, root, data_hash, creator_hash, nonce, index, flags)
+    }
+
+    /// Lists a leaf for sale by delegating it to its listing, which records the price. A listing
+    /// left stale by the leaf moving while listed is taken over by the current owner.
+    pub fn list<'info>(
+        ctx: Context<'_, '_, '_, 'info, List<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        price: u64,
+        flags: u8,
+    ) -> Result<()> {
+        assert_leaf_transferable(flags)?;
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let owner = ctx.accounts.leaf_owner.key();
+        let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+        let previous_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner,
+            ctx.accounts.leaf_delegate.key(),
+            nonce,
+            data_hash,
+            creator_hash,
+            flags,
+        );
+        let new_leaf = LeafSchema::new_with_flags(
+            asset_id,
+            owner,
+            ctx.accounts.listing.key(),
+            nonce,
+            data_hash,
+            creator_hash,
+            flags,
+        );
+        ctx.accounts.listing.set_inner(Listing {
+            seller: owner,
+            price,
+        });
+
+        wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+        emit!(new_leaf.to_event());
+        replace_leaf(
+            &merkle_tree.key(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.compression_program.to_account_info(),
+            &ctx.accounts.tree_authority.to_account_info(),
+            &ctx.accounts.merkle_tree.to_account_info(),
+            &ctx.accounts.log_wrapper.to_account_info(),
+            ctx.remaining_accounts,
+            root,
+            previous_leaf.to_node(),
+            new_leaf.to_node(),
+            index,
+        )
+    }
+
+    /// Takes a leaf off sale, giving the delegation back to its owner and closing the listing.
+    pub fn delist<'info>(
+        ctx: Context<'_, '_, '_, 'info, Delist<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
+        let owner = ctx.accounts.leaf_owner.key();
         let asset_id = get_asset_id(&merkle_tree.key(), nonce);
         let previous_leaf = LeafSchema::new_v0(
             asset_id,
-            owner.key(),
-            delegate.key(),
-            nonce,
-            data_hash,
-            creator_hash,
-        );
-        // New leafs are instantiated with no delegate
-        let new_leaf = LeafSchema::new_v0(
-            asset_id,
-            new_owner,
-            new_owner,
+            owner,
+            ctx.accounts.listing.key(),
             nonce,
             data_hash,
             creator_hash,
         );
+        let new_leaf = LeafSchema::new_v0(asset_id, owner, owner, nonce, data_hash, creator_hash);
+
+        wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
         emit!(new_leaf.to_event());
         replace_leaf(
             &merkle_tree.key(),
@@ -1071,35 +3430,96 @@ pub mod bubblegum {
         )
     }
 
-    pub fn delegate<'info>(
-        ctx: Context<'_, '_, '_, 'info, Delegate<'info>>,
+    /// Buys a listed leaf for the listing price. `seller_fee_basis_points` of the price go to
+    /// `creators` by share and the rest to the seller, and then the leaf is transferred to the
+    /// buyer. The leaf's data hash is rebuilt from `metadata_args_hash` and
+    /// `seller_fee_basis_points` and its creator hash from `creators`, so the leaf only matches
+    /// if they are those of its metadata. The remaining accounts are the accounts of
+    /// `creators`, in order, followed by the proof. `flags` are the current flags of the leaf.
+    pub fn buy<'info>(
+        ctx: Context<'_, '_, '_, 'info, Buy<'info>>,
         root: [u8; 32],
-        data_hash: [u8; 32],
-        creator_hash: [u8; 32],
+        metadata_args_hash: [u8; 32],
+        seller_fee_basis_points: u16,
+        creators: Vec<Creator>,
         nonce: u64,
         index: u32,
+        flags: u8,
     ) -> Result<()> {
+        assert_leaf_transferable(flags)?;
+        if ctx.remaining_accounts.len() < creators.len() {
+            return Err(BubblegumError::CreatorAccountsMismatch.into());
+        }
+        let (creator_accounts, proof) = ctx.remaining_accounts.split_at(creators.len());
+
+        let data_hash =
+            keccak::hashv(&[&metadata_args_hash, &seller_fee_basis_points.to_le_bytes()])
+                .to_bytes();
+        let creator_hash = hash_creators(&creators)?;
+
+        let price = ctx.accounts.listing.price;
+        let royalty = (price as u128)
+            .checked_mul(seller_fee_basis_points as u128)
+            .map(|amount| amount / 10000)
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(BubblegumError::NumericalOverflowError)?;
+
+        let buyer = ctx.accounts.buyer.to_account_info();
+        let pay = |to: &AccountInfo<'info>, lamports: u64| {
+            if lamports == 0 {
+                return Ok(());
+            }
+            invoke(
+                &system_instruction::transfer(&buyer.key(), &to.key(), lamports),
+                &[
+                    buyer.clone(),
+                    to.clone(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )
+        };
+
+        let mut paid = 0u64;
+        for (creator, account) in creators.iter().zip(creator_accounts) {
+            assert_pubkey_equal(
+                &account.key(),
+                &creator.address,
+                Some(BubblegumError::CreatorAccountsMismatch.into()),
+            )?;
+            let share = royalty
+                .checked_mul(creator.share as u64)
+                .ok_or(BubblegumError::NumericalOverflowError)?
+                / 100;
+            pay(account, share)?;
+            paid += share;
+        }
+        let proceeds = price
+            .checked_sub(paid)
+            .ok_or(BubblegumError::NumericalOverflowError)?;
+        pay(&ctx.accounts.seller.to_account_info(), proceeds)?;
+
         let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
-        let owner = ctx.accounts.leaf_owner.key();
-        let previous_delegate = ctx.accounts.previous_leaf_delegate.key();
-        let new_delegate = ctx.accounts.new_leaf_delegate.key();
+        let buyer = ctx.accounts.buyer.key();
         let asset_id = get_asset_id(&merkle_tree.key(), nonce);
-        let previous_leaf = LeafSchema::new_v0(
+        let previous_leaf = LeafSchema::new_with_flags(
             asset_id,
-            owner,
-            previous_delegate,
+            ctx.accounts.seller.key(),
+            ctx.accounts.listing.key(),
             nonce,
             data_hash,
             creator_hash,
+            flags,
         );
-        let new_leaf = LeafSchema::new_v0(
+        let new_leaf = LeafSchema::new_with_flags(
             asset_id,
-            owner,
-            new_delegate,
+            buyer,
+            buyer,
             nonce,
             data_hash,
             creator_hash,
+            flags,
         );
+
         wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
         emit!(new_leaf.to_event());
         replace_leaf(
@@ -1109,7 +3529,7 @@ pub mod bubblegum {
             &ctx.accounts.tree_authority.to_account_info(),
             &ctx.accounts.merkle_tree.to_account_info(),
             &ctx.accounts.log_wrapper.to_account_info(),
-            ctx.remaining_accounts,
+            proof,
             root,
             previous_leaf.to_node(),
             new_leaf.to_node(),
@@ -1117,6 +3537,54 @@ pub mod bubblegum {
         )
     }
 
+    /// Freezes a leaf on behalf of its delegate, e.g. for staking. `flags` are the current
+    /// flags of the leaf. Frozen leaves are V2 leaves, which `transfer`, `delegate`, `burn` and
+    /// `redeem` can't match, and `transfer_v2`, `delegate_v2`, `burn_v2` and `redeem_v2` fail
+    /// with `LeafFrozen` until the leaf is thawed.
+    pub fn freeze<'info>(
+        ctx: Context<'_, '_, '_, 'info, LeafFreeze<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        process_leaf_freeze(
+            ctx,
+            root,
+            data_hash,
+            creator_hash,
+            nonce,
+            index,
+            flags,
+            true,
+        )
+    }
+
+    pub fn thaw<'info>(
+        ctx: Context<'_, '_, '_, 'info, LeafFreeze<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        process_leaf_freeze(
+            ctx,
+            root,
+            data_hash,
+            creator_hash,
+            nonce,
+            index,
+            flags,
+            false,
+        )
+    }
+
+    /// Burns a leaf from its hashes. The collection of the leaf isn't known from those, so the
+    /// size of a sized collection is never decremented, see `burn_with_metadata`.
     pub fn burn<'info>(
         ctx: Context<'_, '_, '_, 'info, Burn<'info>>,
         root: [u8; 32],
@@ -1125,6 +3593,37 @@ pub mod bubblegum {
         nonce: u64,
         index: u32,
     ) -> Result<()> {
+        process_burn(ctx, root, data_hash, creator_hash, nonce, index, 0)
+    }
+
+    /// Burns a leaf that may have flags, like `transfer_v2`.
+    pub fn burn_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, Burn<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        process_burn(ctx, root, data_hash, creator_hash, nonce, index, flags)
+    }
+
+    /// Burns the leaf like `burn`, from its full metadata. When the leaf has a verified
+    /// collection, the remaining accounts start with the collection mint, the collection metadata
+    /// (writable) and the collection authority record of `bubblegum_signer`, followed by the
+    /// proof, and the size of a sized collection is decremented. The burn fails if the collection
+    /// authority hasn't approved `bubblegum_signer` with that record. Leaves of such collections
+    /// can still be burned with `burn`, which leaves their size to `reconcile_collection_size`.
+    pub fn burn_with_metadata<'info>(
+        ctx: Context<'_, '_, '_, 'info, BurnWithMetadata<'info>>,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+        current_metadata: MetadataArgs,
+        flags: u8,
+    ) -> Result<()> {
+        assert_leaf_not_frozen(flags)?;
         let owner = ctx.accounts.leaf_owner.to_account_info();
         let delegate = ctx.accounts.leaf_delegate.to_account_info();
 
@@ -1133,16 +3632,29 @@ pub mod bubblegum {
             owner.is_signer || delegate.is_signer,
             BubblegumError::LeafAuthorityMustSign
         );
+        let data_hash = hash_metadata(&current_metadata)?;
+        let creator_hash = hash_creators(&current_metadata.creators)?;
+        let proof = match &current_metadata.collection {
+            Some(collection) if collection.verified => {
+                let split = ctx.remaining_accounts.len().min(3);
+                let (collection_accounts, proof) = ctx.remaining_accounts.split_at(split);
+                decrement_collection_size(&ctx, &collection.key, collection_accounts)?;
+                proof
+            }
+            _ => ctx.remaining_accounts,
+        };
+
         let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
         let asset_id = get_asset_id(&merkle_tree.key(), nonce);
 
-        let previous_leaf = LeafSchema::new_v0(
+        let previous_leaf = LeafSchema::new_with_flags(
             asset_id,
             owner.key(),
             delegate.key(),
             nonce,
             data_hash,
             creator_hash,
+            flags,
         );
         emit!(previous_leaf.to_event());
         let new_leaf = Node::default();
@@ -1154,7 +3666,7 @@ pub mod bubblegum {
             &ctx.accounts.tree_authority.to_account_info(),
             &ctx.accounts.merkle_tree.to_account_info(),
             &ctx.accounts.log_wrapper.to_account_info(),
-            ctx.remaining_accounts,
+            proof,
             root,
             previous_leaf.to_node(),
             new_leaf,
@@ -1162,6 +3674,8 @@ pub mod bubblegum {
         )
     }
 
+    /// Closable trees count their vouchers, which this can't do, so they take `redeem_v2`
+    /// instead.
     pub fn redeem<'info>(
         ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
         root: [u8; 32],
@@ -1170,42 +3684,124 @@ pub mod bubblegum {
         nonce: u64,
         index: u32,
     ) -> Result<()> {
-        let owner = ctx.accounts.leaf_owner.key();
-        let delegate = ctx.accounts.leaf_delegate.key();
-        let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
-        let asset_id = get_asset_id(&merkle_tree.key(), nonce);
-        let previous_leaf =
-            LeafSchema::new_v0(asset_id, owner, delegate, nonce, data_hash, creator_hash);
-        emit!(previous_leaf.to_event());
-        let new_leaf = Node::default();
-        wrap_event(new_leaf.try_to_vec()?, &ctx.accounts.log_wrapper)?;
-        replace_leaf(
-            &merkle_tree.key(),
-            *ctx.bumps.get("tree_authority").unwrap(),
-            &ctx.accounts.compression_program.to_account_info(),
+        require!(
+            !ctx.accounts.tree_authority.is_closable,
+            BubblegumError::VouchersCounted
+        );
+        let voucher = process_redeem(
             &ctx.accounts.tree_authority.to_account_info(),
-            &ctx.accounts.merkle_tree.to_account_info(),
-            &ctx.accounts.log_wrapper.to_account_info(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            ctx.accounts.leaf_owner.key(),
+            ctx.accounts.leaf_delegate.key(),
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
             ctx.remaining_accounts,
             root,
-            previous_leaf.to_node(),
-            new_leaf,
+            data_hash,
+            creator_hash,
+            nonce,
             index,
+            0,
         )?;
-        ctx.accounts
-            .voucher
-            .set_inner(Voucher::new(previous_leaf, index, merkle_tree.key()));
+        ctx.accounts.voucher.set_inner(voucher);
+        Ok(())
+    }
 
+    /// Redeems a leaf that may have flags, like `transfer_v2`. The voucher is counted if the
+    /// tree is closable, and then has to be cancelled, expired or decompressed by instructions
+    /// that take it off the count, like `cancel_redeem_v2` and `decompress_v2`.
+    pub fn redeem_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemV2<'info>>,
+        root: [u8; 32],
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        nonce: u64,
+        index: u32,
+        flags: u8,
+    ) -> Result<()> {
+        let mut voucher = process_redeem(
+            &ctx.accounts.tree_authority.to_account_info(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            ctx.accounts.leaf_owner.key(),
+            ctx.accounts.leaf_delegate.key(),
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+            ctx.remaining_accounts,
+            root,
+            data_hash,
+            creator_hash,
+            nonce,
+            index,
+            flags,
+        )?;
+        if ctx.accounts.tree_authority.is_closable {
+            voucher.counted = VoucherCounted(true);
+            ctx.accounts.tree_authority.increment_voucher_count();
+        }
+        ctx.accounts.voucher.set_inner(voucher);
         Ok(())
     }
 
+    /// Counted vouchers are cancelled with `cancel_redeem_v2` instead.
     pub fn cancel_redeem<'info>(
         ctx: Context<'_, '_, '_, 'info, CancelRedeem<'info>>,
         root: [u8; 32],
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.voucher.counted.0,
+            BubblegumError::VouchersCounted
+        );
+        process_cancel_redeem(
+            &ctx.accounts.voucher,
+            &ctx.accounts.leaf_owner.key(),
+            &ctx.accounts.tree_authority.to_account_info(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+            ctx.remaining_accounts,
+            root,
+        )
+    }
+
+    /// Cancels a voucher like `cancel_redeem`, taking it off the count of its tree if it's
+    /// counted.
+    pub fn cancel_redeem_v2<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelRedeemV2<'info>>,
+        root: [u8; 32],
+    ) -> Result<()> {
+        process_cancel_redeem(
+            &ctx.accounts.voucher,
+            &ctx.accounts.leaf_owner.key(),
+            &ctx.accounts.tree_authority.to_account_info(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.merkle_tree,
+            &ctx.accounts.log_wrapper,
+            &ctx.accounts.compression_program,
+            ctx.remaining_accounts,
+            root,
+        )?;
+        if ctx.accounts.voucher.counted.0 {
+            ctx.accounts.tree_authority.decrement_voucher_count();
+        }
+        Ok(())
+    }
+
+    /// Puts the leaf of an expired voucher back in its tree, like `cancel_redeem`, and refunds
+    /// the rent of the voucher to the leaf owner who paid for it. Anyone can call this.
+    pub fn expire_voucher<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExpireVoucher<'info>>,
+        root: [u8; 32],
     ) -> Result<()> {
         let voucher = &ctx.accounts.voucher;
-        match ctx.accounts.voucher.leaf_schema {
-            LeafSchema::V1 { owner, .. } => assert_pubkey_equal(
+        match voucher.expiry.0 {
+            Some(expires_at) if Clock::get()?.unix_timestamp >= expires_at => {}
+            _ => return Err(BubblegumError::VoucherNotExpired.into()),
+        }
+        match voucher.leaf_schema {
+            LeafSchema::V1 { owner, .. } | LeafSchema::V2 { owner, .. } => assert_pubkey_equal(
                 &ctx.accounts.leaf_owner.key(),
                 &owner,
                 Some(BubblegumError::AssetOwnerMismatch.into()),
@@ -1214,6 +3810,9 @@ pub mod bubblegum {
         let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
         emit!(voucher.leaf_schema.to_event());
         wrap_event(voucher.leaf_schema.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+        if voucher.counted.0 {
+            ctx.accounts.tree_authority.decrement_voucher_count();
+        }
 
         replace_leaf(
             &merkle_tree.key(),
@@ -1230,8 +3829,66 @@ pub mod bubblegum {
         )
     }
 
+    /// Counted vouchers are decompressed with `decompress_v2` instead.
     pub fn decompress_v1(ctx: Context<DecompressV1>, metadata: MetadataArgs) -> Result<()> {
-        // Allocate and create mint
+        require!(
+            !ctx.accounts.voucher.counted.0,
+            BubblegumError::VouchersCounted
+        );
+        process_decompress(
+            &ctx.accounts.voucher,
+            &ctx.accounts.leaf_owner,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint,
+            *ctx.bumps.get("mint").unwrap(),
+            &ctx.accounts.mint_authority,
+            ctx.bumps["mint_authority"],
+            &ctx.accounts.metadata,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.system_program,
+            &ctx.accounts.sysvar_rent.to_account_info(),
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            metadata,
+        )
+    }
+
+    /// Decompresses a voucher like `decompress_v1`, taking it off the count of its tree if it's
+    /// counted.
+    pub fn decompress_v2(ctx: Context<DecompressV2>, metadata: MetadataArgs) -> Result<()> {
+        process_decompress(
+            &ctx.accounts.voucher,
+            &ctx.accounts.leaf_owner,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint,
+            *ctx.bumps.get("mint").unwrap(),
+            &ctx.accounts.mint_authority,
+            ctx.bumps["mint_authority"],
+            &ctx.accounts.metadata,
+            &ctx.accounts.master_edition,
+            &ctx.accounts.system_program,
+            &ctx.accounts.sysvar_rent.to_account_info(),
+            &ctx.accounts.token_metadata_program,
+            &ctx.accounts.token_program,
+            &ctx.accounts.associated_token_program,
+            metadata,
+        )?;
+        if ctx.accounts.voucher.counted.0 {
+            ctx.accounts.tree_authority.decrement_voucher_count();
+        }
+        Ok(())
+    }
+
+    /// Decompresses a redeemed leaf into a print of the tree's print source. The leaf with
+    /// nonce `n` becomes edition `n + 1`, and takes on the data of the master edition. Only
+    /// leaves with the data hash of the print source qualify, and none of a tree that was made
+    /// public after its print source was set.
+    pub fn decompress_print(ctx: Context<DecompressPrint>, metadata: MetadataArgs) -> Result<()> {
+        require!(
+            !ctx.accounts.tree_authority.is_public,
+            BubblegumError::PublicTreePrintSource
+        );
         let incoming_data_hash = hash_metadata(&metadata)?;
         let event = match ctx.accounts.voucher.leaf_schema {
             LeafSchema::V1 {
@@ -1239,10 +3896,19 @@ pub mod bubblegum {
                 data_hash,
                 nonce,
                 ..
+            }
+            | LeafSchema::V2 {
+                owner,
+                data_hash,
+                nonce,
+                ..
             } => {
                 if !cmp_bytes(&data_hash, &incoming_data_hash, 32) {
                     return Err(BubblegumError::HashingMismatch.into());
                 }
+                if !cmp_bytes(&data_hash, &ctx.accounts.print_source.data_hash, 32) {
+                    return Err(BubblegumError::PrintSourceMismatch.into());
+                }
                 if !cmp_pubkeys(&owner, ctx.accounts.leaf_owner.key) {
                     return Err(BubblegumError::AssetOwnerMismatch.into());
                 }
@@ -1255,6 +3921,9 @@ pub mod bubblegum {
             }
         };
         let voucher = &ctx.accounts.voucher;
+        let nonce = voucher.leaf_schema.nonce();
+        let mint = ctx.accounts.mint.key();
+        let mint_authority_seeds = &[mint.as_ref(), &[ctx.bumps["mint_authority"]]];
         match metadata.token_program_version {
             TokenProgramVersion::Original => {
                 if ctx.accounts.mint.data_is_empty() {
@@ -1274,7 +3943,7 @@ pub mod bubblegum {
                         &[&[
                             ASSET_PREFIX.as_bytes(),
                             voucher.merkle_tree.key().as_ref(),
-                            voucher.leaf_schema.nonce().to_le_bytes().as_ref(),
+                            nonce.to_le_bytes().as_ref(),
                             &[*ctx.bumps.get("mint").unwrap()],
                         ]],
                     )?;
@@ -1326,103 +3995,171 @@ pub mod bubblegum {
                         ctx.accounts.mint_authority.to_account_info(),
                         ctx.accounts.token_program.to_account_info(),
                     ],
-                    &[&[
-                        ctx.accounts.mint.key().as_ref(),
-                        &[ctx.bumps["mint_authority"]],
-                    ]],
+                    &[&mint_authority_seeds[..]],
                 )?;
             }
             TokenProgramVersion::Token2022 => return Err(ProgramError::InvalidArgument.into()),
         }
 
-        let metadata_infos = vec![
-            ctx.accounts.metadata.to_account_info(),
-            ctx.accounts.mint.to_account_info(),
-            ctx.accounts.mint_authority.to_account_info(),
-            ctx.accounts.leaf_owner.to_account_info(),
-            ctx.accounts.token_metadata_program.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-            ctx.accounts.sysvar_rent.to_account_info(),
+        let tree_authority_seeds = &[
+            voucher.merkle_tree.as_ref(),
+            &[*ctx.bumps.get("tree_authority").unwrap()],
         ];
-
-        let master_edition_infos = vec![
+        let edition_infos = vec![
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.edition.to_account_info(),
             ctx.accounts.master_edition.to_account_info(),
             ctx.accounts.mint.to_account_info(),
+            ctx.accounts.edition_marker.to_account_info(),
             ctx.accounts.mint_authority.to_account_info(),
             ctx.accounts.leaf_owner.to_account_info(),
-            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.tree_authority.to_account_info(),
+            ctx.accounts.master_token_account.to_account_info(),
+            ctx.accounts.master_metadata.to_account_info(),
             ctx.accounts.token_metadata_program.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
             ctx.accounts.system_program.to_account_info(),
             ctx.accounts.sysvar_rent.to_account_info(),
         ];
 
-        msg!("Creating metadata!");
+        msg!("Printing edition!");
         invoke_signed(
-            &mpl_token_metadata::instruction::create_metadata_accounts_v2(
+            &mpl_token_metadata::instruction::mint_new_edition_from_master_edition_via_token(
                 ctx.accounts.token_metadata_program.key(),
                 ctx.accounts.metadata.key(),
+                ctx.accounts.edition.key(),
+                ctx.accounts.master_edition.key(),
                 ctx.accounts.mint.key(),
                 ctx.accounts.mint_authority.key(),
                 ctx.accounts.leaf_owner.key(),
-                ctx.accounts.mint_authority.key(),
-                metadata.name.clone(),
-                metadata.symbol.clone(),
-                metadata.uri.clone(),
-                if !metadata.creators.is_empty() {
-                    let mut amended_metadata_creators = metadata.creators;
-                    amended_metadata_creators.push(Creator {
-                        address: ctx.accounts.mint_authority.key(),
-                        verified: true,
-                        share: 0,
-                    });
-                    Some(
-                        amended_metadata_creators
-                            .iter()
-                            .map(|c| c.adapt())
-                            .collect(),
-                    )
-                } else {
-                    None
-                },
-                metadata.seller_fee_basis_points,
-                true,
-                metadata.is_mutable,
-                metadata.collection.map(|c| c.adapt()),
-                metadata.uses.map(|u| u.adapt()),
+                ctx.accounts.tree_authority.key(),
+                ctx.accounts.master_token_account.key(),
+                ctx.accounts.leaf_owner.key(),
+                ctx.accounts.master_metadata.key(),
+                ctx.accounts.master_mint.key(),
+                nonce.saturating_add(1),
             ),
-            metadata_infos.as_slice(),
-            &[&[
-                ctx.accounts.mint.key().as_ref(),
-                &[ctx.bumps["mint_authority"]],
-            ]],
+            edition_infos.as_slice(),
+            &[&mint_authority_seeds[..], &tree_authority_seeds[..]],
         )?;
+        if ctx.accounts.voucher.counted.0 {
+            ctx.accounts.tree_authority.decrement_voucher_count();
+        }
+        emit!(event);
+        Ok(())
+    }
 
-        msg!("Creating master edition!");
-        invoke_signed(
-            &mpl_token_metadata::instruction::create_master_edition_v3(
+    pub fn compress<'info>(ctx: Context<'_, '_, '_, 'info, Compress<'info>>) -> Result<()> {
+        let incoming_tree_delegate = ctx.accounts.tree_delegate.key();
+        let authority = &mut ctx.accounts.tree_authority;
+
+        require!(
+            incoming_tree_delegate == authority.tree_creator
+                || incoming_tree_delegate == authority.tree_delegate,
+            BubblegumError::TreeAuthorityIncorrect,
+        );
+
+        if !authority.contains_mint_capacity(1) {
+            return Err(BubblegumError::InsufficientMintCapacity.into());
+        }
+
+        // Prints can't be represented by a leaf, since nothing would tie them back to their
+        // master edition once compressed.
+        if ctx.accounts.master_edition.try_borrow_data()?.first()
+            == Some(&(mpl_token_metadata::state::Key::EditionV1 as u8))
+        {
+            return Err(BubblegumError::PrintEditionCannotBeCompressed.into());
+        }
+
+        // A collection parent has to stay around for its members to be verified against.
+        if ctx.accounts.metadata.collection_details.is_some() {
+            return Err(BubblegumError::CollectionParentCannotBeCompressed.into());
+        }
+
+        let mut message =
+            metadata_args_from_token_metadata(&ctx.accounts.metadata, &ctx.accounts.mint.key());
+        assert_metadata_is_mpl_compatible(&message)?;
+
+        // Burning a verified collection item requires the collection metadata, so that its
+        // size can be decremented. The leaf is written unverified, since it no longer counts
+        // towards that size and burning it would decrement the size again. The collection
+        // authority can verify it once more.
+        let collection_metadata = match &ctx.accounts.metadata.collection {
+            Some(collection) if collection.verified => Some(
+                ctx.remaining_accounts
+                    .first()
+                    .ok_or(BubblegumError::CollectionNotFound)?,
+            ),
+            _ => None,
+        };
+        if let Some(collection) = message.collection.as_mut() {
+            collection.verified = false;
+        }
+
+        let mut burn_infos = vec![
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.leaf_owner.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_account.to_account_info(),
+            ctx.accounts.master_edition.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        ];
+        if let Some(collection_metadata) = collection_metadata {
+            burn_infos.push(collection_metadata.to_account_info());
+        }
+
+        // Burns the token, closes the token account and returns the rent of the metadata and
+        // master edition accounts to the owner.
+        invoke(
+            &mpl_token_metadata::instruction::burn_nft(
                 ctx.accounts.token_metadata_program.key(),
-                ctx.accounts.master_edition.key(),
-                ctx.accounts.mint.key(),
-                ctx.accounts.mint_authority.key(),
-                ctx.accounts.mint_authority.key(),
                 ctx.accounts.metadata.key(),
                 ctx.accounts.leaf_owner.key(),
-                Some(0),
+                ctx.accounts.mint.key(),
+                ctx.accounts.token_account.key(),
+                ctx.accounts.master_edition.key(),
+                ctx.accounts.token_program.key(),
+                collection_metadata.map(|c| c.key()),
             ),
-            master_edition_infos.as_slice(),
-            &[&[
-                ctx.accounts.mint.key().as_ref(),
-                &[ctx.bumps["mint_authority"]],
-            ]],
+            burn_infos.as_slice(),
         )?;
-        emit!(event);
-        Ok(())
-    }
 
-    pub fn compress(_ctx: Context<Compress>) -> Result<()> {
-        // TODO
+        let data_hash = hash_metadata(&message)?;
+        let creator_hash = hash_creators(&message.creators)?;
+
+        let merkle_tree = &ctx.accounts.merkle_tree;
+        let nonce = authority.num_minted;
+        let leaf = LeafSchema::new_v0(
+            get_asset_id(&merkle_tree.key(), nonce),
+            ctx.accounts.leaf_owner.key(),
+            ctx.accounts.leaf_delegate.key(),
+            nonce,
+            data_hash,
+            creator_hash,
+        );
+        let new_nft = NewNFTEvent {
+            version: Version::V1,
+            metadata: message,
+            nonce,
+        };
+
+        emit!(new_nft);
+        wrap_event(new_nft.try_to_vec()?, &ctx.accounts.log_wrapper)?;
+
+        emit!(leaf.to_event());
+
+        append_leaf(
+            &merkle_tree.key(),
+            *ctx.bumps.get("tree_authority").unwrap(),
+            &ctx.accounts.compression_program.to_account_info(),
+            &authority.to_account_info(),
+            &merkle_tree.to_account_info(),
+            &ctx.accounts.log_wrapper.to_account_info(),
+            leaf.to_node(),
+        )?;
+
+        authority.increment_mint_count();
+
         Ok(())
     }
 }
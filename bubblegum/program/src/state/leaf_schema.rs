@@ -12,6 +12,7 @@ pub struct LeafSchemaEvent {
 
 pub enum Version {
     V1,
+    V2,
 }
 
 impl Default for Version {
@@ -24,6 +25,7 @@ impl Version {
     pub fn to_bytes(&self) -> u8 {
         match self {
             Version::V1 => 1,
+            Version::V2 => 2,
         }
     }
 }
@@ -38,8 +40,24 @@ pub enum LeafSchema {
         data_hash: [u8; 32],
         creator_hash: [u8; 32],
     },
+    /// Adds a byte of `LEAF_FLAG_*` bits. Only used for leaves with at least one flag set.
+    V2 {
+        id: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+        nonce: u64,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        flags: u8,
+    },
 }
 
+/// The leaf can't be transferred, delegated, listed, bought, burned or redeemed until it's thawed.
+pub const LEAF_FLAG_FROZEN: u8 = 1 << 0;
+
+/// The leaf can't change owners, so it can't be transferred, listed, bought or redeemed.
+pub const LEAF_FLAG_NON_TRANSFERABLE: u8 = 1 << 1;
+
 impl Default for LeafSchema {
     fn default() -> Self {
         Self::V1 {
@@ -72,27 +90,61 @@ impl LeafSchema {
         }
     }
 
+    /// Leaves without flags are still hashed as V1, so they (and the instructions that rebuild
+    /// them as V1 leaves) are unaffected by flags existing. In turn, those instructions can't
+    /// match flagged leaves, unless they take the current flags of the leaf.
+    pub fn new_with_flags(
+        id: Pubkey,
+        owner: Pubkey,
+        delegate: Pubkey,
+        nonce: u64,
+        data_hash: [u8; 32],
+        creator_hash: [u8; 32],
+        flags: u8,
+    ) -> Self {
+        if flags == 0 {
+            return Self::new_v0(id, owner, delegate, nonce, data_hash, creator_hash);
+        }
+        Self::V2 {
+            id,
+            owner,
+            delegate,
+            nonce,
+            data_hash,
+            creator_hash,
+            flags,
+        }
+    }
+
     pub fn version(&self) -> Version {
         match self {
             LeafSchema::V1 { .. } => Version::V1,
+            LeafSchema::V2 { .. } => Version::V2,
         }
     }
 
     pub fn id(&self) -> Pubkey {
         match self {
-            LeafSchema::V1 { id, .. } => *id,
+            LeafSchema::V1 { id, .. } | LeafSchema::V2 { id, .. } => *id,
         }
     }
 
     pub fn nonce(&self) -> u64 {
         match self {
-            LeafSchema::V1 { nonce, .. } => *nonce,
+            LeafSchema::V1 { nonce, .. } | LeafSchema::V2 { nonce, .. } => *nonce,
         }
     }
 
     pub fn data_hash(&self) -> [u8; 32] {
         match self {
-            LeafSchema::V1 { data_hash, .. } => *data_hash,
+            LeafSchema::V1 { data_hash, .. } | LeafSchema::V2 { data_hash, .. } => *data_hash,
+        }
+    }
+
+    pub fn flags(&self) -> u8 {
+        match self {
+            LeafSchema::V1 { .. } => 0,
+            LeafSchema::V2 { flags, .. } => *flags,
         }
     }
 
@@ -123,6 +175,25 @@ impl LeafSchema {
                 creator_hash.as_ref(),
             ])
             .to_bytes(),
+            LeafSchema::V2 {
+                id,
+                owner,
+                delegate,
+                nonce,
+                data_hash,
+                creator_hash,
+                flags,
+            } => keccak::hashv(&[
+                &[self.version().to_bytes()],
+                id.as_ref(),
+                owner.as_ref(),
+                delegate.as_ref(),
+                nonce.to_le_bytes().as_ref(),
+                data_hash.as_ref(),
+                creator_hash.as_ref(),
+                &[*flags],
+            ])
+            .to_bytes(),
         };
         hashed_leaf
     }
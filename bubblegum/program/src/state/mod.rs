@@ -6,11 +6,21 @@ use anchor_lang::prelude::*;
 use leaf_schema::{LeafSchema, Version};
 use metaplex_adapter::MetadataArgs;
 
-pub const TREE_AUTHORITY_SIZE: usize = 88 + 8;
-pub const VOUCHER_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 32 + 32 + 4 + 32;
+pub const TREE_AUTHORITY_SIZE: usize = 88 + 8 + 33;
+pub const VOUCHER_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 32 + 32 + 4 + 32 + 8;
+/// Vouchers of `redeem_v2`, which have room for a `VoucherCounted`.
+pub const VOUCHER_V2_SIZE: usize = VOUCHER_SIZE + 1;
+/// How long a voucher lasts before anyone can expire it with `expire_voucher`.
+pub const VOUCHER_LIFETIME_SECONDS: i64 = 30 * 24 * 60 * 60;
 pub const VOUCHER_PREFIX: &str = "voucher";
 pub const ASSET_PREFIX: &str = "asset";
 pub const COLLECTION_CPI_PREFIX: &str = "collection_cpi";
+pub const MINTER_RECORD_SIZE: usize = 8 + 32 + 8;
+pub const MINTER_RECORD_PREFIX: &str = "minter";
+pub const PRINT_SOURCE_SIZE: usize = 8 + 32 + 32;
+pub const PRINT_SOURCE_PREFIX: &str = "print_source";
+pub const LISTING_SIZE: usize = 8 + 32 + 8;
+pub const LISTING_PREFIX: &str = "listing";
 
 #[account]
 #[derive(Copy, Debug)]
@@ -19,6 +29,21 @@ pub struct TreeConfig {
     pub tree_delegate: Pubkey,
     pub total_mint_capacity: u64,
     pub num_minted: u64,
+    /// Anyone can mint into public trees, not just the tree creator, delegate and minters.
+    pub is_public: bool,
+    /// The tree that mints roll over into once this one is full, set with
+    /// `set_successor_tree`. Configs of trees created before successors existed are shorter
+    /// and read this as `None`.
+    pub successor_tree: Option<Pubkey>,
+    /// Vouchers of the tree that can still put their leaf back, i.e. that weren't decompressed,
+    /// cancelled or expired yet. Only closable trees count their vouchers. This, `is_successor`
+    /// and `is_closable` still fit in configs created before successors existed.
+    pub num_vouchers: u32,
+    /// Set once another tree names this one as its successor.
+    pub is_successor: bool,
+    /// Set with `set_tree_closable` before the first mint. Closable trees count their vouchers,
+    /// so they only take the voucher instructions that keep the count, like `redeem_v2`.
+    pub is_closable: bool,
 }
 
 impl TreeConfig {
@@ -26,30 +51,155 @@ impl TreeConfig {
         self.num_minted = self.num_minted.saturating_add(1);
     }
 
+    pub fn increment_mint_count_by(&mut self, count: u64) {
+        self.num_minted = self.num_minted.saturating_add(count);
+    }
+
+    pub fn increment_voucher_count(&mut self) {
+        self.num_vouchers = self.num_vouchers.saturating_add(1);
+    }
+
+    pub fn decrement_voucher_count(&mut self) {
+        self.num_vouchers = self.num_vouchers.saturating_sub(1);
+    }
+
     pub fn contains_mint_capacity(&self, requested_capacity: u64) -> bool {
         let remaining_mints = self.total_mint_capacity.saturating_sub(self.num_minted);
         requested_capacity <= remaining_mints
     }
 }
 
+/// Lets `minter` mint into a tree, on top of its creator and delegate, up to `mint_quota`
+/// more leaves.
+#[account]
+#[derive(Copy, Debug)]
+pub struct MinterRecord {
+    pub minter: Pubkey,
+    pub mint_quota: u64,
+}
+
+/// The master edition whose prints the leaves of a tree decompress into with
+/// `decompress_print`. The tree authority holds the master edition token while it is set.
+#[account]
+#[derive(Copy, Debug)]
+pub struct PrintSource {
+    pub master_mint: Pubkey,
+    /// Only leaves with this data hash decompress into prints.
+    pub data_hash: [u8; 32],
+}
+
+/// A leaf listed for sale for `price` lamports. Listed leaves are delegated to their listing,
+/// so that the listing goes stale if the owner moves the leaf in the meantime. Listing the leaf
+/// again overwrites a stale listing, together with its rent.
+#[account]
+#[derive(Copy, Debug)]
+pub struct Listing {
+    pub seller: Pubkey,
+    pub price: u64,
+}
+
 #[account]
 #[derive(Copy)]
 pub struct Voucher {
     pub leaf_schema: LeafSchema,
     pub index: u32,
     pub merkle_tree: Pubkey,
+    pub expiry: VoucherExpiry,
+    pub counted: VoucherCounted,
 }
 
 impl Voucher {
-    pub fn new(leaf_schema: LeafSchema, index: u32, merkle_tree: Pubkey) -> Self {
+    pub fn new(
+        leaf_schema: LeafSchema,
+        index: u32,
+        merkle_tree: Pubkey,
+        expiry: VoucherExpiry,
+    ) -> Self {
         Self {
             leaf_schema,
             index,
             merkle_tree,
+            expiry,
+            counted: VoucherCounted(false),
+        }
+    }
+}
+
+/// Unix timestamp after which a voucher can be expired. Vouchers created before vouchers could
+/// expire end right before it, so it is only (de)serialized when there are bytes left, and
+/// those read as `None` and never expire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VoucherExpiry(pub Option<i64>);
+
+impl AnchorSerialize for VoucherExpiry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match self.0 {
+            Some(expires_at) => expires_at.serialize(writer),
+            None => Ok(()),
         }
     }
 }
 
+impl AnchorDeserialize for VoucherExpiry {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.is_empty() {
+            Ok(VoucherExpiry(None))
+        } else {
+            Ok(VoucherExpiry(Some(i64::deserialize(buf)?)))
+        }
+    }
+}
+
+/// Whether the voucher is counted in the `num_vouchers` of its tree, in which case only the
+/// instructions that keep the count can cancel, expire or decompress it. Counted vouchers always
+/// expire, so this follows their expiry. It is only serialized when set, and reads as `false`
+/// when there are no bytes left, like `VoucherExpiry`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VoucherCounted(pub bool);
+
+impl AnchorSerialize for VoucherCounted {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if self.0 {
+            true.serialize(writer)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl AnchorDeserialize for VoucherCounted {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if buf.is_empty() {
+            Ok(VoucherCounted(false))
+        } else {
+            Ok(VoucherCounted(bool::deserialize(buf)?))
+        }
+    }
+}
+
+/// A single leaf of `mint_batch_v1`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchMintArgs {
+    pub leaf_owner: Pubkey,
+    pub leaf_delegate: Pubkey,
+    pub metadata: MetadataArgs,
+}
+
+/// A single leaf of `verify_creator_batch`. `root` is the root `proof_len` proof accounts were
+/// computed against; they are taken off the remaining accounts in the order of the leaves.
+/// `flags` are the current flags of the leaf.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchVerifyCreatorArgs {
+    pub root: [u8; 32],
+    pub leaf_owner: Pubkey,
+    pub leaf_delegate: Pubkey,
+    pub nonce: u64,
+    pub index: u32,
+    pub metadata: MetadataArgs,
+    pub proof_len: u8,
+    pub flags: u8,
+}
+
 #[event]
 pub struct NewNFTEvent {
     pub version: Version,
@@ -64,3 +214,24 @@ pub struct NFTDecompressionEvent {
     pub tree_id: Pubkey,
     pub nonce: u64,
 }
+
+#[event]
+pub struct MetadataUpdateEvent {
+    pub version: Version,
+    pub id: Pubkey,
+    pub nonce: u64,
+    pub metadata: MetadataArgs,
+}
+
+/// Records the collection size set by `reconcile_collection_size`, along with the attester
+/// that signed for having counted that many verified items at `counted_at_slot`.
+#[event]
+pub struct CollectionSizeReconciliationEvent {
+    pub version: Version,
+    pub collection_mint: Pubkey,
+    pub collection_authority: Pubkey,
+    pub attester: Pubkey,
+    pub previous_size: u64,
+    pub size: u64,
+    pub counted_at_slot: u64,
+}
@@ -1,9 +1,19 @@
-use crate::{error::BubblegumError, state::metaplex_adapter::MetadataArgs, ASSET_PREFIX};
+use crate::{
+    error::BubblegumError,
+    state::{metaplex_adapter::MetadataArgs, TreeConfig},
+    ASSET_PREFIX,
+};
 use anchor_lang::{
     prelude::*,
-    solana_program::{program_memory::sol_memcmp, pubkey::PUBKEY_BYTES},
+    solana_program::{
+        ed25519_program, keccak,
+        program_memory::sol_memcmp,
+        pubkey::PUBKEY_BYTES,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
 };
-use spl_account_compression::Node;
+use spl_account_compression::{state::ConcurrentMerkleTreeHeader, Node};
+use std::mem::size_of;
 
 /// Assert that the provided MetadataArgs are compatible with MPL `Data`
 pub fn assert_metadata_is_mpl_compatible(metadata: &MetadataArgs) -> Result<()> {
@@ -161,3 +171,167 @@ pub fn get_asset_id(tree_id: &Pubkey, nonce: u64) -> Pubkey {
     )
     .0
 }
+
+pub fn get_tree_authority(merkle_tree: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[merkle_tree.as_ref()], &crate::id()).0
+}
+
+/// Reads the chain of trees linked with `set_successor_tree` that starts at `first_tree`, in
+/// order, using `fetch` to read the config of each tree.
+pub fn get_tree_chain<E>(
+    first_tree: Pubkey,
+    mut fetch: impl FnMut(&Pubkey) -> std::result::Result<TreeConfig, E>,
+) -> std::result::Result<Vec<(Pubkey, TreeConfig)>, E> {
+    let mut chain: Vec<(Pubkey, TreeConfig)> = Vec::new();
+    let mut next = Some(first_tree);
+    while let Some(tree) = next {
+        // Successors can't form cycles on chain, but stay safe against bad `fetch` results.
+        if chain.iter().any(|(key, _)| *key == tree) {
+            break;
+        }
+        let config = fetch(&tree)?;
+        next = config.successor_tree;
+        chain.push((tree, config));
+    }
+    Ok(chain)
+}
+
+/// Accounts to append to a mint of `count` leaves into the first tree of `chain` when it is
+/// full, so that it rolls over into the first successor with room for them: the configs of the
+/// successors up to that one, and its tree.
+pub fn get_successor_tree_accounts(chain: &[(Pubkey, TreeConfig)], count: u64) -> Vec<AccountMeta> {
+    let mut accounts = Vec::new();
+    for (tree, config) in chain.iter().skip(1) {
+        accounts.push(AccountMeta::new(get_tree_authority(tree), false));
+        if config.contains_mint_capacity(count) {
+            accounts.push(AccountMeta::new(*tree, false));
+            break;
+        }
+    }
+    accounts
+}
+
+/// Asset ID of the `position`th asset minted into a chain of trees, counting the assets of
+/// each tree after those of the trees before it.
+pub fn get_chained_asset_id(chain: &[(Pubkey, TreeConfig)], position: u64) -> Option<Pubkey> {
+    let mut position = position;
+    for (tree, config) in chain {
+        if position < config.num_minted {
+            return Some(get_asset_id(tree, position));
+        }
+        position -= config.num_minted;
+    }
+    None
+}
+
+/// Finds the tree and nonce of an asset minted into a chain of trees. This derives the ID of
+/// every asset minted before it, so it is meant for clients rather than programs.
+pub fn find_asset_in_tree_chain(
+    chain: &[(Pubkey, TreeConfig)],
+    asset_id: &Pubkey,
+) -> Option<(Pubkey, u64)> {
+    chain.iter().find_map(|(tree, config)| {
+        (0..config.num_minted)
+            .find(|nonce| get_asset_id(tree, *nonce) == *asset_id)
+            .map(|nonce| (*tree, nonce))
+    })
+}
+
+/// Root of a tree of the given depth where every leaf is empty.
+pub fn empty_root(depth: u32) -> Node {
+    (0..depth).fold(Node::default(), |node, _| {
+        keccak::hashv(&[node.as_ref(), node.as_ref()]).to_bytes()
+    })
+}
+
+/// Number of change logs a concurrent merkle tree account keeps, i.e. how many changes a root
+/// can fall behind the current one and still be used to modify the tree.
+pub fn get_max_buffer_size(merkle_tree: &AccountInfo) -> Result<u32> {
+    let data = merkle_tree.try_borrow_data()?;
+    let header_size = size_of::<ConcurrentMerkleTreeHeader>();
+    if data.len() < header_size {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    let header: &ConcurrentMerkleTreeHeader = bytemuck::try_from_bytes(&data[..header_size])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(header.max_buffer_size)
+}
+
+/// Checks that the current root of a concurrent merkle tree account, i.e. the root of its most
+/// recent change log, is that of a tree of empty leaves. The tree comes right after the header,
+/// and starts with its sequence number, active index and buffer size (all `u64`s), followed by
+/// the change log buffer.
+pub fn assert_tree_is_empty(merkle_tree: &AccountInfo) -> Result<()> {
+    let data = merkle_tree.try_borrow_data()?;
+    let header_size = size_of::<ConcurrentMerkleTreeHeader>();
+    if data.len() < header_size + 24 {
+        return Err(ProgramError::InvalidAccountData.into());
+    }
+    let header: &ConcurrentMerkleTreeHeader = bytemuck::try_from_bytes(&data[..header_size])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let mut active_index = [0; 8];
+    active_index.copy_from_slice(&data[header_size + 8..header_size + 16]);
+    let active_index = u64::from_le_bytes(active_index) as usize;
+
+    // Each change log holds the root, a path of `max_depth` nodes, and an index with padding.
+    let change_log_size = 32 + 32 * header.max_depth as usize + 8;
+    let root_offset = header_size + 24 + active_index * change_log_size;
+    let root = data
+        .get(root_offset..root_offset + 32)
+        .ok_or(ProgramError::InvalidAccountData)?;
+
+    if root != empty_root(header.max_depth).as_ref() {
+        return Err(BubblegumError::TreeNotEmpty.into());
+    }
+    Ok(())
+}
+
+/// Checks that the instruction right before the current one is an ed25519 program instruction
+/// verifying a single signature of `signer` over `message`. The ed25519 program fails the
+/// transaction on a bad signature, so this only checks what it verified. Its data starts with
+/// the number of signatures and a padding byte, followed by the offsets of the signature, the
+/// public key and the message, each followed by the index of the instruction holding it (the
+/// message offset by the message size first, all `u16`s). `u16::MAX` instruction indices point
+/// at the ed25519 instruction itself.
+pub fn assert_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let index = current_index
+        .checked_sub(1)
+        .ok_or(BubblegumError::InvalidAttestation)?;
+    let ix = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+    require!(
+        cmp_pubkeys(&ix.program_id, &ed25519_program::id()),
+        BubblegumError::InvalidAttestation
+    );
+
+    let data = ix.data.as_slice();
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        BubblegumError::InvalidAttestation
+    );
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        BubblegumError::InvalidAttestation
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_BYTES)
+        .ok_or(BubblegumError::InvalidAttestation)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(BubblegumError::InvalidAttestation)?;
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        BubblegumError::InvalidAttestation
+    );
+    Ok(())
+}
@@ -0,0 +1,177 @@
+pub mod utils;
+
+use mpl_bubblegum::error::BubblegumError;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Result, Tree};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+// A tree that counts its vouchers, so it can be closed once it's empty.
+async fn closable_tree(context: &BubblegumTestContext) -> Result<Tree<MAX_DEPTH, MAX_BUF_SIZE>> {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    tree.set_tree_closable().await?;
+    Ok(tree)
+}
+
+// Mints a closable tree's first leaf, owned by the payer.
+async fn closable_tree_and_leaf(
+    context: &BubblegumTestContext,
+) -> Result<(Tree<MAX_DEPTH, MAX_BUF_SIZE>, LeafArgs)> {
+    let tree = closable_tree(context).await?;
+    let leaf = LeafArgs::new(&context.payer(), context.default_metadata_args("a", "a"));
+    tree.mint_v1(&tree.tree_delegate, &leaf).await?;
+    Ok((tree, leaf))
+}
+
+#[tokio::test]
+async fn test_close_tree_refunds_creator() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = closable_tree_and_leaf(&context).await.unwrap();
+    tree.burn(&leaf).await.unwrap();
+
+    let creator = tree.creator_pubkey();
+    let balance_before = tree.client().get_balance(creator).await.unwrap();
+    let tree_rent = tree.client().get_balance(tree.tree_pubkey()).await.unwrap();
+    let config_rent = tree.client().get_balance(tree.authority()).await.unwrap();
+
+    tree.close_tree().await.unwrap();
+
+    assert!(!tree.account_exists(tree.tree_pubkey()).await.unwrap());
+    assert!(!tree.account_exists(tree.authority()).await.unwrap());
+
+    // The creator also paid the transaction fee.
+    let balance_after = tree.client().get_balance(creator).await.unwrap();
+    assert!(balance_after > balance_before);
+    assert!(balance_after <= balance_before + tree_rent + config_rent);
+}
+
+#[tokio::test]
+async fn test_close_unused_tree() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = closable_tree(&context).await.unwrap();
+
+    tree.close_tree().await.unwrap();
+    assert!(!tree.account_exists(tree.tree_pubkey()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_close_tree_with_leaves_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = closable_tree_and_leaf(&context).await.unwrap();
+
+    let err = tree.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeNotEmpty);
+
+    // Burning a later leaf doesn't help while an earlier one is still there.
+    let mut second = LeafArgs::new(&context.payer(), context.default_metadata_args("b", "b"));
+    second.nonce = 1;
+    second.index = 1;
+    tree.mint_v1(&tree.tree_delegate, &second).await.unwrap();
+    tree.burn(&second).await.unwrap();
+
+    let err = tree.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeNotEmpty);
+
+    // A redeemed leaf can still come back while its voucher is around. The original
+    // instructions don't count vouchers, so closable trees only take the newer ones.
+    let err = tree.redeem(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::VouchersCounted);
+    tree.redeem_v2(&leaf).await.unwrap();
+    let err = tree.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeHasVouchers);
+    assert_eq!(tree.read_tree_config().await.unwrap().num_vouchers, 1);
+
+    let err = tree.decompress_v1(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::VouchersCounted);
+    tree.decompress_v2(&leaf).await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_vouchers, 0);
+    tree.close_tree().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_cancel_redeem_uncounts_voucher() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = closable_tree_and_leaf(&context).await.unwrap();
+
+    tree.redeem_v2(&leaf).await.unwrap();
+    let err = tree.cancel_redeem(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::VouchersCounted);
+
+    tree.cancel_redeem_v2(&leaf).await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_vouchers, 0);
+
+    tree.burn(&leaf).await.unwrap();
+    tree.close_tree().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_close_tree_that_is_not_closable_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    let err = tree.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeNotClosable);
+    assert!(tree.account_exists(tree.tree_pubkey()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_set_tree_closable_after_mint_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = context
+        .default_create_and_mint::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    // Vouchers redeemed before this point would never have been counted.
+    let err = tree.set_tree_closable().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAlreadyMinted);
+
+    // The tree keeps working with the original instructions, and doesn't count vouchers.
+    tree.redeem_v2(&leaf).await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_vouchers, 0);
+    tree.decompress_v1(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_close_successor_tree_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = closable_tree(&context).await.unwrap();
+    let successor = closable_tree(&context).await.unwrap();
+    tree.set_successor_tree(successor.tree_pubkey())
+        .await
+        .unwrap();
+
+    let err = successor.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeIsSuccessor);
+    assert!(successor
+        .account_exists(successor.tree_pubkey())
+        .await
+        .unwrap());
+
+    tree.close_tree().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_close_tree_requires_tree_creator() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = closable_tree(&context).await.unwrap();
+
+    let impostor = Keypair::new();
+    let mut tx = tree.close_tree_tx();
+    tx.accounts.tree_creator = impostor.pubkey();
+    assert!(tx
+        .set_payer(context.payer().pubkey())
+        .set_signers(&[&context.payer(), &impostor])
+        .execute()
+        .await
+        .is_err());
+    assert!(tree.account_exists(tree.tree_pubkey()).await.unwrap());
+}
@@ -0,0 +1,212 @@
+pub mod utils;
+
+use mpl_bubblegum::{error::BubblegumError, state::metaplex_adapter::Collection};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error, bubblegum_signer, collection_size_attestation,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    ed25519_instruction,
+    nft::{collection_authority_record_pubkey, Nft},
+    LeafArgs, Tree,
+};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+// Creates a collection parent of the payer, sized at `size`.
+async fn sized_collection(context: &BubblegumTestContext, size: u64) -> Nft {
+    let args = context.default_metadata_args("collection", "col");
+    let mut collection = Nft::create(context.client(), &context.payer(), &args, Some(0))
+        .await
+        .unwrap();
+    collection.set_collection_size(size).await.unwrap();
+    collection
+}
+
+// Mints a leaf into `collection` and verifies it there.
+async fn verified_leaf(
+    context: &BubblegumTestContext,
+    collection: &Nft,
+) -> (Tree<MAX_DEPTH, MAX_BUF_SIZE>, LeafArgs) {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let mut metadata = context.default_metadata_args("item", "itm");
+    metadata.collection = Some(Collection {
+        verified: false,
+        key: collection.mint,
+    });
+    let mut leaf = LeafArgs::new(&context.payer(), metadata);
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+
+    tree.verify_collection(&leaf, collection).await.unwrap();
+    leaf.metadata.collection.as_mut().unwrap().verified = true;
+    (tree, leaf)
+}
+
+#[tokio::test]
+async fn test_burn_decrements_collection_size() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 0).await;
+    let (tree, leaf) = verified_leaf(&context, &collection).await;
+    assert_eq!(collection.collection_size().await.unwrap(), Some(1));
+
+    collection
+        .approve_collection_authority(bubblegum_signer())
+        .await
+        .unwrap();
+    tree.burn_with_metadata(&leaf).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_burn_without_approval_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 0).await;
+    let (tree, leaf) = verified_leaf(&context, &collection).await;
+
+    let err = tree.burn_with_metadata(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionNotApproved);
+    assert_eq!(collection.collection_size().await.unwrap(), Some(1));
+
+    // Burns that only pass the hashes never decrement the size, it's left for reconciliation.
+    tree.burn(&leaf).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(1));
+
+    let (tree, leaf) = verified_leaf(&context, &collection).await;
+    collection
+        .approve_collection_authority(bubblegum_signer())
+        .await
+        .unwrap();
+    tree.burn(&leaf).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(2));
+}
+
+#[tokio::test]
+async fn test_burn_checks_collection_accounts() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 0).await;
+    collection
+        .approve_collection_authority(bubblegum_signer())
+        .await
+        .unwrap();
+    let (tree, leaf) = verified_leaf(&context, &collection).await;
+
+    // The collection accounts have to be those of the collection of the leaf.
+    let other = sized_collection(&context, 10).await;
+    let mut tx = tree.burn_with_metadata_tx(&leaf).await.unwrap();
+    tx.additional_accounts[0].pubkey = other.mint;
+    tx.additional_accounts[1].pubkey = other.metadata();
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionNotFound);
+
+    let mut tx = tree.burn_with_metadata_tx(&leaf).await.unwrap();
+    tx.additional_accounts.clear();
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionNotFound);
+
+    let mut tx = tree.burn_with_metadata_tx(&leaf).await.unwrap();
+    tx.additional_accounts[2].pubkey =
+        collection_authority_record_pubkey(&other.mint, &bubblegum_signer());
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionNotFound);
+
+    tree.burn_with_metadata(&leaf).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_reconcile_collection_size() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 5).await;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let slot = tree.client().get_root_slot().await.unwrap();
+
+    tree.reconcile_collection_size(&collection, 3, slot)
+        .await
+        .unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(3));
+
+    // Counts can't be claimed for slots that didn't happen yet.
+    let err = tree
+        .reconcile_collection_size(&collection, 2, slot + 1_000)
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidCountedAtSlot);
+}
+
+#[tokio::test]
+async fn test_reconcile_collection_size_checks_authority() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 5).await;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let slot = tree.client().get_root_slot().await.unwrap();
+
+    let other = Keypair::new();
+    context
+        .fund_account(other.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    let mut tx = tree.reconcile_collection_size_tx(&collection, 0, slot);
+    tx.accounts.collection_authority = other.pubkey();
+    tx.set_payer(other.pubkey()).set_signers(&[&other]);
+    assert!(tx.execute().await.is_err());
+    assert_eq!(collection.collection_size().await.unwrap(), Some(5));
+
+    // Only sized collections can be reconciled.
+    let args = context.default_metadata_args("unsized", "uns");
+    let unsized_collection = Nft::create(context.client(), &context.payer(), &args, Some(0))
+        .await
+        .unwrap();
+    let err = tree
+        .reconcile_collection_size(&unsized_collection, 1, slot)
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionNotSized);
+}
+
+#[tokio::test]
+async fn test_reconcile_collection_size_checks_attestation() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut collection = sized_collection(&context, 5).await;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let slot = tree.client().get_root_slot().await.unwrap();
+    let attestation = collection_size_attestation(&collection.mint, 3, slot);
+
+    let mut tx = tree.reconcile_collection_size_tx(&collection, 3, slot);
+    tx.pre_instructions.clear();
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidAttestation);
+
+    // The attestation has to be signed by the attester, for the size and slot being set.
+    let mut tx = tree.reconcile_collection_size_tx(&collection, 3, slot);
+    tx.pre_instructions = vec![ed25519_instruction(&Keypair::new(), &attestation)];
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidAttestation);
+
+    let mut tx = tree.reconcile_collection_size_tx(&collection, 2, slot);
+    tx.pre_instructions = vec![ed25519_instruction(&collection.owner, &attestation)];
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidAttestation);
+    assert_eq!(collection.collection_size().await.unwrap(), Some(5));
+
+    // The attester doesn't have to be the collection authority, or sign the transaction.
+    let attester = Keypair::new();
+    let mut tx = tree.reconcile_collection_size_tx(&collection, 3, slot);
+    tx.accounts.attester = attester.pubkey();
+    tx.pre_instructions = vec![ed25519_instruction(&attester, &attestation)];
+    tx.execute().await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(3));
+}
@@ -0,0 +1,209 @@
+pub mod utils;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::metaplex_adapter::{Collection, MetadataArgs, TokenStandard},
+};
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error, bubblegum_signer,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    nft::{metadata_pubkey, Nft},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+async fn context_and_tree() -> Result<(BubblegumTestContext, Tree<MAX_DEPTH, MAX_BUF_SIZE>)> {
+    let context = BubblegumTestContext::new().await?;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    Ok((context, tree))
+}
+
+// The arguments `decompress_v1` would have produced for `args`, as seen by the token-metadata
+// accounts it creates.
+async fn decompressed_args(
+    tree: &Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    args: &MetadataArgs,
+    mint: &Pubkey,
+) -> MetadataArgs {
+    let metadata: mpl_bubblegum::state::metaplex_anchor::TokenMetadata =
+        tree.read_account_data(metadata_pubkey(mint)).await.unwrap();
+    assert_eq!(
+        metadata.token_standard,
+        Some(mpl_token_metadata::state::TokenStandard::NonFungible)
+    );
+    assert!(metadata.edition_nonce.is_some());
+
+    let mut expected = args.clone();
+    expected.edition_nonce = metadata.edition_nonce;
+    expected.token_standard = Some(TokenStandard::NonFungible);
+    expected
+}
+
+#[tokio::test]
+async fn test_compress_decompressed_leaf_round_trips() {
+    let (context, tree) = context_and_tree().await.unwrap();
+    let owner = context.payer();
+    let leaf = LeafArgs::new(&owner, context.default_metadata_args("test", "tst"));
+
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.redeem(&leaf).await.unwrap();
+    tree.decompress_v1(&leaf).await.unwrap();
+
+    let mint = tree.asset_mint(leaf.nonce);
+    let nft = Nft::new(context.client(), &owner, mint);
+    let expected = decompressed_args(&tree, &leaf.metadata, &mint).await;
+
+    tree.compress(&nft).await.unwrap();
+
+    // The token and all of its token-metadata accounts are gone.
+    assert!(!tree.account_exists(nft.token_account()).await.unwrap());
+    assert!(!tree.account_exists(nft.metadata()).await.unwrap());
+    assert!(!tree.account_exists(nft.edition()).await.unwrap());
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 2);
+
+    // Redeeming only works when the leaf hashes match, and decompressing again checks the
+    // data hash against the full metadata.
+    let compressed = LeafArgs {
+        metadata: expected,
+        nonce: 1,
+        index: 1,
+        ..leaf.clone()
+    };
+    tree.redeem(&compressed).await.unwrap();
+    tree.decompress_v1(&compressed).await.unwrap();
+
+    let metadata: mpl_bubblegum::state::metaplex_anchor::TokenMetadata = tree
+        .read_account_data(metadata_pubkey(&tree.asset_mint(compressed.nonce)))
+        .await
+        .unwrap();
+    assert_eq!(metadata.data.name.trim_end_matches(char::from(0)), "test");
+    assert_eq!(metadata.data.symbol.trim_end_matches(char::from(0)), "tst");
+}
+
+#[tokio::test]
+async fn test_compress_legacy_nft() {
+    let (context, tree) = context_and_tree().await.unwrap();
+    let owner = context.payer();
+    let args = context.default_metadata_args("legacy", "lgc");
+
+    let nft = Nft::create(context.client(), &owner, &args, Some(0))
+        .await
+        .unwrap();
+    let expected = decompressed_args(&tree, &args, &nft.mint).await;
+
+    tree.compress(&nft).await.unwrap();
+    assert!(!tree.account_exists(nft.metadata()).await.unwrap());
+
+    let compressed = LeafArgs {
+        metadata: expected,
+        nonce: 0,
+        index: 0,
+        ..LeafArgs::new(&owner, args)
+    };
+    tree.redeem(&compressed).await.unwrap();
+    tree.decompress_v1(&compressed).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_compress_then_burn_keeps_collection_size() {
+    let (context, tree) = context_and_tree().await.unwrap();
+    let owner = context.payer();
+    let collection_args = context.default_metadata_args("collection", "col");
+    let mut collection = Nft::create(context.client(), &owner, &collection_args, Some(0))
+        .await
+        .unwrap();
+    collection.set_collection_size(0).await.unwrap();
+    collection
+        .approve_collection_authority(bubblegum_signer())
+        .await
+        .unwrap();
+
+    let mut args = context.default_metadata_args("item", "itm");
+    args.collection = Some(Collection {
+        verified: false,
+        key: collection.mint,
+    });
+    let mut nft = Nft::create(context.client(), &owner, &args, Some(0))
+        .await
+        .unwrap();
+    nft.verify_sized_collection_item(&collection).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(1));
+    let expected = decompressed_args(&tree, &args, &nft.mint).await;
+
+    // Burning the token takes the item off the size, so the leaf is no longer verified.
+    let mut tx = tree.compress_tx(&nft);
+    tx.set_additional_account_metas(&[AccountMeta::new(collection.metadata(), false)]);
+    tx.execute().await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(0));
+
+    let leaf = LeafArgs {
+        metadata: expected,
+        nonce: 0,
+        index: 0,
+        ..LeafArgs::new(&owner, args)
+    };
+    tree.burn_with_metadata(&leaf).await.unwrap();
+    assert_eq!(collection.collection_size().await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_compress_print_fails() {
+    let (context, tree) = context_and_tree().await.unwrap();
+    let owner = context.payer();
+    let args = context.default_metadata_args("master", "mst");
+
+    let mut master = Nft::create(context.client(), &owner, &args, Some(10))
+        .await
+        .unwrap();
+    let print = master.print(1).await.unwrap();
+
+    let err = tree.compress(&print).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::PrintEditionCannotBeCompressed);
+    assert!(tree.account_exists(print.metadata()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_compress_collection_parent_fails() {
+    let (context, tree) = context_and_tree().await.unwrap();
+    let owner = context.payer();
+    let args = context.default_metadata_args("collection", "col");
+
+    let mut parent = Nft::create(context.client(), &owner, &args, Some(0))
+        .await
+        .unwrap();
+    parent.set_collection_size(0).await.unwrap();
+
+    let err = tree.compress(&parent).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CollectionParentCannotBeCompressed);
+    assert!(tree.account_exists(parent.metadata()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_compress_requires_tree_delegate() {
+    let (mut context, tree) = context_and_tree().await.unwrap();
+    let owner = Keypair::new();
+    context
+        .fund_account(owner.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    let args = context.default_metadata_args("test", "tst");
+
+    let nft = Nft::create(context.client(), &owner, &args, Some(0))
+        .await
+        .unwrap();
+
+    // The owner signs in place of the tree delegate.
+    let mut tx = tree.compress_tx(&nft);
+    tx.accounts.tree_delegate = owner.pubkey();
+    let err = tx.set_signers(&[&owner]).execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+    assert!(tree.account_exists(nft.metadata()).await.unwrap());
+}
@@ -0,0 +1,98 @@
+pub mod utils;
+
+use mpl_bubblegum::{error::BubblegumError, state::VOUCHER_LIFETIME_SECONDS};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Tree};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+async fn redeemed_leaf(
+    context: &BubblegumTestContext,
+) -> (Tree<MAX_DEPTH, MAX_BUF_SIZE>, LeafArgs) {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let leaf = LeafArgs::new(
+        &context.payer(),
+        context.default_metadata_args("voucher", "vch"),
+    );
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.redeem(&leaf).await.unwrap();
+    (tree, leaf)
+}
+
+async fn lamports(tree: &Tree<MAX_DEPTH, MAX_BUF_SIZE>, key: &Keypair) -> u64 {
+    tree.client().get_balance(key.pubkey()).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_redeem_sets_voucher_expiry() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = redeemed_leaf(&context).await;
+
+    let voucher = tree.read_voucher(leaf.nonce).await.unwrap();
+    assert!(voucher.expiry.0.is_some());
+}
+
+#[tokio::test]
+async fn test_expire_voucher_before_expiry_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = redeemed_leaf(&context).await;
+
+    context
+        .advance_clock(VOUCHER_LIFETIME_SECONDS / 2)
+        .await
+        .unwrap();
+
+    let err = tree
+        .expire_voucher(&leaf, &context.default_creators[0])
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::VoucherNotExpired);
+    assert!(tree.account_exists(tree.voucher(leaf.nonce)).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_expire_voucher() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = redeemed_leaf(&context).await;
+
+    context
+        .advance_clock(VOUCHER_LIFETIME_SECONDS)
+        .await
+        .unwrap();
+
+    // Anyone can put the leaf back, and the voucher rent goes to the owner who paid it.
+    let owner_lamports = lamports(&tree, &leaf.owner).await;
+    tree.expire_voucher(&leaf, &context.default_creators[0])
+        .await
+        .unwrap();
+    assert!(!tree.account_exists(tree.voucher(leaf.nonce)).await.unwrap());
+    assert!(lamports(&tree, &leaf.owner).await > owner_lamports);
+
+    // The leaf is back in the tree and can be used as before.
+    let new_owner = Keypair::new();
+    tree.transfer(&leaf, new_owner.pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_expire_voucher_checks_leaf_owner() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, mut leaf) = redeemed_leaf(&context).await;
+
+    context
+        .advance_clock(VOUCHER_LIFETIME_SECONDS)
+        .await
+        .unwrap();
+
+    leaf.owner = Keypair::new();
+    let err = tree
+        .expire_voucher(&leaf, &context.default_creators[0])
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::AssetOwnerMismatch);
+}
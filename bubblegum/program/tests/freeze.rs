@@ -0,0 +1,130 @@
+pub mod utils;
+
+use mpl_bubblegum::{error::BubblegumError, state::leaf_schema::LEAF_FLAG_FROZEN};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error, clone_keypair,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+// Mints a leaf owned by the payer and hands it to a funded delegate, like a staking program
+// would be.
+async fn context_tree_and_delegated_leaf() -> Result<(
+    BubblegumTestContext,
+    Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    LeafArgs,
+)> {
+    let mut context = BubblegumTestContext::new().await?;
+    let (tree, mut leaf) = context
+        .default_create_and_mint::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+
+    let delegate = Keypair::new();
+    context
+        .fund_account(delegate.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await?;
+    tree.delegate(&leaf, delegate.pubkey()).await?;
+    leaf.delegate = clone_keypair(&delegate);
+
+    Ok((context, tree, leaf))
+}
+
+#[tokio::test]
+async fn test_frozen_leaf_cannot_move() {
+    let (_, tree, mut leaf) = context_tree_and_delegated_leaf().await.unwrap();
+    tree.freeze(&leaf).await.unwrap();
+
+    // The original instructions only match leaves without flags.
+    assert!(tree.transfer(&leaf, Keypair::new().pubkey()).await.is_err());
+    assert!(tree.burn(&leaf).await.is_err());
+
+    leaf.flags = LEAF_FLAG_FROZEN;
+    let err = tree
+        .transfer_v2(&leaf, Keypair::new().pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafFrozen);
+    let err = tree
+        .delegate_v2(&leaf, Keypair::new().pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafFrozen);
+    let err = tree.burn_v2(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafFrozen);
+    let err = tree.redeem_v2(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafFrozen);
+
+    // Thawing leaves the leaf without flags, so the original instructions match it again.
+    tree.thaw(&leaf).await.unwrap();
+    leaf.flags = 0;
+    tree.transfer(&leaf, Keypair::new().pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_v2_instructions_match_leaves_without_flags() {
+    let (mut context, tree, mut leaf) = context_tree_and_delegated_leaf().await.unwrap();
+
+    let new_delegate = Keypair::new();
+    tree.delegate_v2(&leaf, new_delegate.pubkey())
+        .await
+        .unwrap();
+    leaf.delegate = new_delegate;
+
+    let new_owner = Keypair::new();
+    context
+        .fund_account(new_owner.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    tree.transfer_v2(&leaf, new_owner.pubkey()).await.unwrap();
+    leaf.delegate = clone_keypair(&new_owner);
+    leaf.owner = new_owner;
+
+    tree.burn_v2(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_freeze_requires_leaf_delegate() {
+    let (_, tree, leaf) = context_tree_and_delegated_leaf().await.unwrap();
+
+    // The owner is not the delegate anymore, so signing in its place doesn't match the leaf.
+    let mut tx = tree.freeze_tx(&leaf, 0).await.unwrap();
+    tx.accounts.leaf_delegate = leaf.owner.pubkey();
+    let err = tx
+        .set_payer(leaf.owner.pubkey())
+        .set_signers(&[&leaf.owner])
+        .execute()
+        .await;
+    assert!(err.is_err());
+
+    tree.freeze(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_freeze_and_thaw_check_current_state() {
+    let (_, tree, leaf) = context_tree_and_delegated_leaf().await.unwrap();
+
+    let err = tree
+        .thaw_tx(&leaf, 0)
+        .await
+        .unwrap()
+        .execute()
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafNotFrozen);
+
+    tree.freeze(&leaf).await.unwrap();
+    let err = tree
+        .freeze_tx(&leaf, LEAF_FLAG_FROZEN)
+        .await
+        .unwrap()
+        .execute()
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafAlreadyFrozen);
+}
@@ -0,0 +1,168 @@
+pub mod utils;
+
+use mpl_bubblegum::{error::BubblegumError, state::LISTING_SIZE};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+
+use utils::{
+    assert_bubblegum_error, clone_keypair,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Tree,
+};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+const PRICE: u64 = 100_000_000;
+
+async fn funded_keypair(context: &mut BubblegumTestContext) -> Keypair {
+    let keypair = Keypair::new();
+    context
+        .fund_account(keypair.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    keypair
+}
+
+// Mints a leaf with a 5% seller fee to `seller` and lists it for `PRICE`.
+async fn listed_leaf(
+    context: &BubblegumTestContext,
+    seller: &Keypair,
+) -> (Tree<MAX_DEPTH, MAX_BUF_SIZE>, LeafArgs) {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let mut metadata = context.default_metadata_args("listed", "lst");
+    metadata.seller_fee_basis_points = 500;
+    let leaf = LeafArgs::new(seller, metadata);
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.list(&leaf, PRICE).await.unwrap();
+    (tree, leaf)
+}
+
+async fn balance(tree: &Tree<MAX_DEPTH, MAX_BUF_SIZE>, key: Pubkey) -> u64 {
+    tree.client().get_balance(key).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_buy_pays_royalties_and_transfers() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let seller = funded_keypair(&mut context).await;
+    let buyer = funded_keypair(&mut context).await;
+    let (tree, leaf) = listed_leaf(&context, &seller).await;
+
+    let listing = tree.read_listing(leaf.nonce).await.unwrap();
+    assert_eq!(listing.seller, seller.pubkey());
+    assert_eq!(listing.price, PRICE);
+
+    let creators = leaf
+        .metadata
+        .creators
+        .iter()
+        .map(|creator| creator.address)
+        .collect::<Vec<_>>();
+    let mut before = Vec::new();
+    for key in creators.iter().chain([seller.pubkey()].iter()) {
+        before.push(balance(&tree, *key).await);
+    }
+
+    tree.buy(&leaf, &buyer).await.unwrap();
+    assert!(!tree.account_exists(tree.listing(leaf.nonce)).await.unwrap());
+
+    // 5% of the price is split between creators by share, and the seller gets the rest along
+    // with the listing rent.
+    let listing_rent = tree.rent().await.unwrap().minimum_balance(LISTING_SIZE);
+    let expected = [
+        1_000_000,
+        1_000_000,
+        1_000_000,
+        2_000_000,
+        95_000_000 + listing_rent,
+    ];
+    for ((key, before), expected) in creators
+        .iter()
+        .chain([seller.pubkey()].iter())
+        .zip(before)
+        .zip(expected)
+    {
+        assert_eq!(balance(&tree, *key).await - before, expected);
+    }
+
+    // The buyer owns the leaf now.
+    let mut bought = leaf.clone();
+    bought.owner = clone_keypair(&buyer);
+    bought.delegate = buyer;
+    tree.transfer(&bought, Keypair::new().pubkey())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_buy_checks_creators() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let seller = funded_keypair(&mut context).await;
+    let buyer = funded_keypair(&mut context).await;
+    let (tree, leaf) = listed_leaf(&context, &seller).await;
+
+    // Royalties can't be sent elsewhere.
+    let mut tx = tree.buy_tx(&leaf, &buyer).await.unwrap();
+    tx.additional_accounts[3] = AccountMeta::new(buyer.pubkey(), false);
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CreatorAccountsMismatch);
+
+    // Creators that aren't those of the leaf don't match its creator hash.
+    let mut wrong_shares = leaf.clone();
+    wrong_shares.metadata.creators[0].share = 40;
+    wrong_shares.metadata.creators[3].share = 20;
+    assert!(tree.buy(&wrong_shares, &buyer).await.is_err());
+
+    tree.buy(&leaf, &buyer).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_delist() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let seller = funded_keypair(&mut context).await;
+    let buyer = funded_keypair(&mut context).await;
+    let (tree, leaf) = listed_leaf(&context, &seller).await;
+
+    tree.delist(&leaf).await.unwrap();
+    assert!(!tree.account_exists(tree.listing(leaf.nonce)).await.unwrap());
+    assert!(tree.buy(&leaf, &buyer).await.is_err());
+
+    // The owner is the delegate again.
+    tree.transfer(&leaf, buyer.pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_relist_after_transfer() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let seller = funded_keypair(&mut context).await;
+    let owner = funded_keypair(&mut context).await;
+    let buyer = funded_keypair(&mut context).await;
+    let (tree, leaf) = listed_leaf(&context, &seller).await;
+
+    // The owner moves the listed leaf, which leaves the listing stale.
+    let mut tx = tree.transfer_tx(&leaf, owner.pubkey()).await.unwrap();
+    tx.accounts.leaf_delegate = tree.listing(leaf.nonce);
+    tx.execute().await.unwrap();
+    assert!(tree.buy(&leaf, &buyer).await.is_err());
+
+    let relisted = LeafArgs {
+        owner: clone_keypair(&owner),
+        delegate: clone_keypair(&owner),
+        ..leaf.clone()
+    };
+    tree.list(&relisted, PRICE / 2).await.unwrap();
+    let listing = tree.read_listing(leaf.nonce).await.unwrap();
+    assert_eq!(listing.seller, owner.pubkey());
+    assert_eq!(listing.price, PRICE / 2);
+
+    tree.delist(&relisted).await.unwrap();
+    assert!(!tree.account_exists(tree.listing(leaf.nonce)).await.unwrap());
+}
@@ -0,0 +1,92 @@
+pub mod utils;
+
+use mpl_bubblegum::error::BubblegumError;
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs,
+};
+
+#[tokio::test]
+async fn test_mint_batch_passes() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let tree = context.default_create_tree::<20, 64>().await.unwrap();
+
+    let owners = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for owner in owners.iter() {
+        context
+            .fund_account(owner.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+            .await
+            .unwrap();
+    }
+
+    let mut leaves = owners
+        .iter()
+        .map(|owner| LeafArgs::new(owner, context.small_metadata_args()))
+        .collect::<Vec<_>>();
+    for (nonce, leaf) in leaves.iter_mut().enumerate() {
+        leaf.nonce = nonce as u64;
+        leaf.index = nonce as u32;
+    }
+
+    tree.mint_batch_v1(&tree.tree_delegate, &leaves)
+        .await
+        .unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 3);
+
+    // The last leaf belongs to its own owner, so they can move it.
+    let last = leaves.last().unwrap();
+    tree.transfer(last, Keypair::new().pubkey()).await.unwrap();
+
+    // Minting one by one continues after the batch.
+    let mut next = LeafArgs::new(
+        &context.payer(),
+        context.default_metadata_args("test", "tst"),
+    );
+    next.nonce = 3;
+    next.index = 3;
+    tree.mint_v1(&tree.tree_delegate, &next).await.unwrap();
+    tree.burn(&next).await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 4);
+}
+
+#[tokio::test]
+async fn test_mint_batch_respects_capacity() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    // Depth 3 leaves room for 8 leaves.
+    let tree = context.default_create_tree::<3, 8>().await.unwrap();
+
+    let leaves = (0..9)
+        .map(|_| LeafArgs::new(&context.payer(), context.small_metadata_args()))
+        .collect::<Vec<_>>();
+
+    let err = tree
+        .mint_batch_v1(&tree.tree_delegate, &leaves)
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InsufficientMintCapacity);
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 0);
+
+    tree.mint_batch_v1(&tree.tree_delegate, &leaves[..8])
+        .await
+        .unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 8);
+}
+
+#[tokio::test]
+async fn test_mint_batch_requires_tree_delegate() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = context.default_create_tree::<20, 64>().await.unwrap();
+
+    let leaves = [LeafArgs::new(
+        &context.payer(),
+        context.small_metadata_args(),
+    )];
+    let impostor = &context.default_creators[0];
+
+    let err = tree.mint_batch_v1(impostor, &leaves).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+}
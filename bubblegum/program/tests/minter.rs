@@ -0,0 +1,115 @@
+pub mod utils;
+
+use mpl_bubblegum::error::BubblegumError;
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Result, Tree};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+async fn context_tree_and_minter(
+    mint_quota: u64,
+) -> Result<(BubblegumTestContext, Tree<MAX_DEPTH, MAX_BUF_SIZE>, Keypair)> {
+    let context = BubblegumTestContext::new().await?;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    let minter = Keypair::new();
+    tree.add_minter(minter.pubkey(), mint_quota).await?;
+    Ok((context, tree, minter))
+}
+
+#[tokio::test]
+async fn test_minter_uses_up_quota() {
+    let (context, tree, minter) = context_tree_and_minter(2).await.unwrap();
+
+    let record = tree.read_minter_record(&minter.pubkey()).await.unwrap();
+    assert_eq!(record.minter, minter.pubkey());
+    assert_eq!(record.mint_quota, 2);
+
+    for nonce in 0..2 {
+        let mut leaf = LeafArgs::new(&context.payer(), context.default_metadata_args("a", "b"));
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+        tree.minter_mint_v1_tx(&minter, &leaf)
+            .execute()
+            .await
+            .unwrap();
+    }
+    let record = tree.read_minter_record(&minter.pubkey()).await.unwrap();
+    assert_eq!(record.mint_quota, 0);
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 2);
+
+    let leaf = LeafArgs::new(&context.payer(), context.default_metadata_args("a", "b"));
+    let err = tree
+        .minter_mint_v1_tx(&minter, &leaf)
+        .execute()
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::MintQuotaExceeded);
+
+    // The tree delegate isn't limited by minter quotas.
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_minter_batch_cannot_exceed_quota() {
+    let (context, tree, minter) = context_tree_and_minter(2).await.unwrap();
+
+    let leaves = (0..3)
+        .map(|_| LeafArgs::new(&context.payer(), context.small_metadata_args()))
+        .collect::<Vec<_>>();
+
+    let mut tx = tree.mint_batch_v1_tx(&minter, &leaves);
+    tx.set_payer(context.payer().pubkey())
+        .set_signers(&[&minter, &context.payer()])
+        .set_additional_account_metas(&[AccountMeta::new(
+            tree.minter_record(&minter.pubkey()),
+            false,
+        )]);
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::MintQuotaExceeded);
+
+    tx.data.mints.truncate(2);
+    tx.execute().await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 2);
+}
+
+#[tokio::test]
+async fn test_removed_minter_cannot_mint() {
+    let (context, tree, minter) = context_tree_and_minter(10).await.unwrap();
+    let leaf = LeafArgs::new(&context.payer(), context.default_metadata_args("a", "b"));
+
+    tree.remove_minter(minter.pubkey()).await.unwrap();
+    assert!(!tree
+        .account_exists(tree.minter_record(&minter.pubkey()))
+        .await
+        .unwrap());
+
+    let err = tree
+        .minter_mint_v1_tx(&minter, &leaf)
+        .execute()
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn test_minter_record_of_another_minter_is_rejected() {
+    let (context, tree, minter) = context_tree_and_minter(10).await.unwrap();
+    let leaf = LeafArgs::new(&context.payer(), context.default_metadata_args("a", "b"));
+    let impostor = Keypair::new();
+
+    let mut tx = tree.mint_v1_tx(&impostor, &leaf);
+    tx.set_additional_account_metas(&[AccountMeta::new(
+        tree.minter_record(&minter.pubkey()),
+        false,
+    )]);
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+}
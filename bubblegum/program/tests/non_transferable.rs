@@ -0,0 +1,126 @@
+pub mod utils;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::{
+        leaf_schema::{LEAF_FLAG_FROZEN, LEAF_FLAG_NON_TRANSFERABLE},
+        metaplex_adapter::UpdateArgs,
+    },
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error, clone_keypair,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+// Mints a mutable non-transferable leaf owned by the payer.
+async fn context_tree_and_non_transferable_leaf() -> Result<(
+    BubblegumTestContext,
+    Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    LeafArgs,
+)> {
+    let context = BubblegumTestContext::new().await?;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+
+    let mut metadata = context.default_metadata_args("soulbound", "sbt");
+    metadata.is_mutable = true;
+    let mut leaf = LeafArgs::new(&context.payer(), metadata);
+    leaf.flags = LEAF_FLAG_NON_TRANSFERABLE;
+    tree.mint_v2(&tree.tree_delegate, &leaf).await?;
+
+    Ok((context, tree, leaf))
+}
+
+#[tokio::test]
+async fn test_non_transferable_leaf_cannot_change_owners() {
+    let (_, tree, leaf) = context_tree_and_non_transferable_leaf().await.unwrap();
+
+    let err = tree
+        .transfer_v2(&leaf, Keypair::new().pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafNonTransferable);
+    let err = tree.list(&leaf, 1).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafNonTransferable);
+    let err = tree.redeem_v2(&leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafNonTransferable);
+
+    // The original instructions only match leaves without flags.
+    assert!(tree.transfer(&leaf, Keypair::new().pubkey()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_non_transferable_leaf_keeps_its_flags() {
+    let (mut context, tree, mut leaf) = context_tree_and_non_transferable_leaf().await.unwrap();
+
+    let update_args = UpdateArgs {
+        name: Some("renamed".to_owned()),
+        ..UpdateArgs::default()
+    };
+    tree.update_metadata(&leaf, update_args).await.unwrap();
+    leaf.metadata.name = "renamed".to_owned();
+
+    let delegate = Keypair::new();
+    context
+        .fund_account(delegate.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    tree.delegate_v2(&leaf, delegate.pubkey()).await.unwrap();
+    leaf.delegate = clone_keypair(&delegate);
+
+    // Only succeeds if the leaf in the tree still has its flags.
+    tree.burn_v2(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_buy_checks_leaf_flags() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let (tree, mut leaf) = context
+        .default_create_and_mint::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    tree.list(&leaf, 1).await.unwrap();
+
+    let buyer = Keypair::new();
+    context
+        .fund_account(buyer.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    leaf.flags = LEAF_FLAG_NON_TRANSFERABLE;
+    let err = tree.buy(&leaf, &buyer).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::LeafNonTransferable);
+}
+
+#[tokio::test]
+async fn test_creator_verification_only_matches_leaves_without_flags() {
+    let (context, tree, leaf) = context_tree_and_non_transferable_leaf().await.unwrap();
+
+    // `verify_creator` doesn't take the flags of the leaf, so it can't match this one.
+    let creator = &context.default_creators[0];
+    assert!(tree.verify_creator(&leaf, creator).await.is_err());
+}
+
+#[tokio::test]
+async fn test_mint_v2_rejects_frozen_flag() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    let mut leaf = LeafArgs::new(
+        &context.payer(),
+        context.default_metadata_args("test", "tst"),
+    );
+    leaf.flags = LEAF_FLAG_FROZEN;
+    let err = tree.mint_v2(&tree.tree_delegate, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidLeafFlags);
+}
@@ -0,0 +1,220 @@
+pub mod utils;
+
+use anchor_lang::AnchorDeserialize;
+use mpl_bubblegum::error::BubblegumError;
+use mpl_token_metadata::state::{Edition, Key};
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+use utils::{
+    assert_bubblegum_error,
+    context::BubblegumTestContext,
+    nft::{edition_pubkey, Nft},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+// A tree whose creator holds a master edition that allows `max_supply` prints.
+async fn tree_and_master(
+    context: &BubblegumTestContext,
+    max_supply: u64,
+) -> Result<(Tree<MAX_DEPTH, MAX_BUF_SIZE>, Nft)> {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    let master = Nft::create(
+        context.client(),
+        &tree.tree_creator,
+        &context.default_metadata_args("master", "mst"),
+        Some(max_supply),
+    )
+    .await?;
+    Ok((tree, master))
+}
+
+async fn token_amount(tree: &Tree<MAX_DEPTH, MAX_BUF_SIZE>, token_account: Pubkey) -> u64 {
+    let account = tree
+        .client()
+        .get_account(token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+#[tokio::test]
+async fn test_decompress_print() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+    assert_eq!(
+        tree.read_print_source().await.unwrap().master_mint,
+        master.mint
+    );
+    assert_eq!(token_amount(&tree, master.token_account()).await, 0);
+    assert_eq!(
+        token_amount(&tree, tree.print_source_token_account(&master)).await,
+        1
+    );
+
+    let owner = context.payer();
+    let mut leaves = Vec::new();
+    for nonce in 0..2 {
+        let mut leaf = LeafArgs::new(&owner, context.default_metadata_args("print", "prt"));
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+        tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+        leaves.push(leaf);
+    }
+
+    // Leaves can be decompressed out of order, and keep their place in the edition numbering.
+    for leaf in leaves.iter().rev() {
+        tree.redeem(leaf).await.unwrap();
+        tree.decompress_print(leaf, &master).await.unwrap();
+
+        let mint = tree.asset_mint(leaf.nonce);
+        let print = Nft::new(context.client(), &owner, mint);
+        assert_eq!(token_amount(&tree, print.token_account()).await, 1);
+        assert!(!tree.account_exists(tree.voucher(leaf.nonce)).await.unwrap());
+
+        let account = tree
+            .client()
+            .get_account(edition_pubkey(&mint))
+            .await
+            .unwrap()
+            .unwrap();
+        let edition = Edition::deserialize(&mut account.data.as_slice()).unwrap();
+        assert_eq!(edition.key, Key::EditionV1);
+        assert_eq!(edition.parent, master.edition());
+        assert_eq!(edition.edition, leaf.nonce + 1);
+    }
+}
+
+#[tokio::test]
+async fn test_decompress_print_without_print_source_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+
+    let owner = context.payer();
+    let leaf = LeafArgs::new(&owner, context.default_metadata_args("print", "prt"));
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.redeem(&leaf).await.unwrap();
+
+    assert!(tree.decompress_print(&leaf, &master).await.is_err());
+
+    // The leaf can still be decompressed the usual way.
+    tree.decompress_v1(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_decompress_print_past_max_supply_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 1).await.unwrap();
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+
+    let owner = context.payer();
+    let mut leaf = LeafArgs::new(&owner, context.default_metadata_args("print", "prt"));
+    for nonce in 0..2 {
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+        tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    }
+
+    // The second leaf would become edition 2 of a master that allows a single print.
+    tree.redeem(&leaf).await.unwrap();
+    assert!(tree.decompress_print(&leaf, &master).await.is_err());
+}
+
+#[tokio::test]
+async fn test_remove_print_source() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+    tree.remove_print_source(&master).await.unwrap();
+
+    assert_eq!(master.owner.pubkey(), tree.creator_pubkey());
+    assert_eq!(token_amount(&tree, master.token_account()).await, 1);
+    assert!(!tree.account_exists(tree.print_source()).await.unwrap());
+    assert!(!tree
+        .account_exists(tree.print_source_token_account(&master))
+        .await
+        .unwrap());
+
+    // The print source can be set again afterwards.
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_close_tree_with_print_source_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+    tree.set_tree_closable().await.unwrap();
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+
+    // The master edition would be stuck in the print source once the tree config is gone.
+    let err = tree.close_tree().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeHasPrintSource);
+
+    tree.remove_print_source(&master).await.unwrap();
+    tree.close_tree().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_decompress_print_of_other_leaf_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+    tree.set_print_source(&master, &context.default_metadata_args("print", "prt"))
+        .await
+        .unwrap();
+
+    // Only leaves with the data of the print source can become prints.
+    let leaf = LeafArgs::new(
+        &context.payer(),
+        context.default_metadata_args("other", "oth"),
+    );
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.redeem(&leaf).await.unwrap();
+    let err = tree.decompress_print(&leaf, &master).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::PrintSourceMismatch);
+
+    tree.decompress_v1(&leaf).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_print_source_of_public_tree_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, master) = tree_and_master(&context, 10).await.unwrap();
+    let metadata = context.default_metadata_args("print", "prt");
+
+    tree.set_tree_public(true).await.unwrap();
+    let err = tree.set_print_source(&master, &metadata).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::PublicTreePrintSource);
+
+    // Trees made public after their print source was set don't print either.
+    tree.set_tree_public(false).await.unwrap();
+    tree.set_print_source(&master, &metadata).await.unwrap();
+    let leaf = LeafArgs::new(&context.payer(), metadata);
+    tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+    tree.set_tree_public(true).await.unwrap();
+
+    tree.redeem(&leaf).await.unwrap();
+    let err = tree.decompress_print(&leaf, &master).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::PublicTreePrintSource);
+}
@@ -0,0 +1,124 @@
+pub mod utils;
+
+use mpl_bubblegum::error::BubblegumError;
+use solana_program_test::tokio;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+
+use utils::{
+    assert_bubblegum_error,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+async fn create_public_tree<const DEPTH: usize, const BUF: usize>(
+    context: &BubblegumTestContext,
+) -> Result<Tree<DEPTH, BUF>> {
+    let payer = context.payer();
+    let tree = Tree::<DEPTH, BUF>::with_creator(&payer, context.client());
+    tree.alloc(&payer).await?;
+
+    tree.create(&payer).await?;
+    tree.set_tree_public(true).await?;
+
+    Ok(tree)
+}
+
+// A funded keypair that has nothing to do with the tree.
+async fn stranger(context: &mut BubblegumTestContext) -> Keypair {
+    let stranger = Keypair::new();
+    context
+        .fund_account(stranger.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    stranger
+}
+
+#[tokio::test]
+async fn test_anyone_can_mint_into_public_tree() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let tree = create_public_tree::<MAX_DEPTH, MAX_BUF_SIZE>(&context)
+        .await
+        .unwrap();
+    assert!(tree.read_tree_config().await.unwrap().is_public);
+
+    let minter = stranger(&mut context).await;
+    let leaf = LeafArgs::new(&minter, context.default_metadata_args("badge", "bdg"));
+    tree.mint_v1(&minter, &leaf).await.unwrap();
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 1);
+
+    // The minted leaf is a regular one.
+    tree.transfer(&leaf, Keypair::new().pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_tree_creator_can_toggle_public() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    assert!(!tree.read_tree_config().await.unwrap().is_public);
+
+    let minter = stranger(&mut context).await;
+    let mut leaf = LeafArgs::new(&minter, context.default_metadata_args("badge", "bdg"));
+
+    let err = tree.mint_v1(&minter, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+
+    tree.set_tree_public(true).await.unwrap();
+    tree.mint_v1(&minter, &leaf).await.unwrap();
+
+    tree.set_tree_public(false).await.unwrap();
+    leaf.nonce = 1;
+    leaf.index = 1;
+    let err = tree.mint_v1(&minter, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::TreeAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn test_public_minter_cannot_verify_other_creators() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let tree = create_public_tree::<MAX_DEPTH, MAX_BUF_SIZE>(&context)
+        .await
+        .unwrap();
+
+    let minter = stranger(&mut context).await;
+    let mut metadata = context.default_metadata_args("badge", "bdg");
+    metadata.creators[0].verified = true;
+    let leaf = LeafArgs::new(&minter, metadata);
+
+    let err = tree.mint_v1(&minter, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CreatorDidNotVerify);
+
+    // Creators that sign can still be verified.
+    let creator = &context.default_creators[0];
+    let mut tx = tree.mint_v1_tx(&minter, &leaf);
+    tx.set_additional_account_metas(&[AccountMeta::new_readonly(creator.pubkey(), true)])
+        .set_signers(&[&minter, creator]);
+    tx.execute().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_public_tree_respects_capacity() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    // Depth 3 leaves room for 8 leaves.
+    let tree = create_public_tree::<3, 8>(&context).await.unwrap();
+    let minter = stranger(&mut context).await;
+
+    for nonce in 0..8 {
+        let mut leaf = LeafArgs::new(&minter, context.default_metadata_args("badge", "bdg"));
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+        tree.mint_v1(&minter, &leaf).await.unwrap();
+    }
+
+    let leaf = LeafArgs::new(&minter, context.default_metadata_args("badge", "bdg"));
+    let err = tree.mint_v1(&minter, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InsufficientMintCapacity);
+}
@@ -0,0 +1,203 @@
+pub mod utils;
+
+use std::collections::HashMap;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::TreeConfig,
+    utils::{
+        find_asset_in_tree_chain, get_asset_id, get_chained_asset_id, get_successor_tree_accounts,
+        get_tree_authority, get_tree_chain,
+    },
+};
+use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+use solana_program_test::tokio;
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Result, Tree};
+
+// Depth 3 leaves room for 8 leaves.
+const MAX_DEPTH: usize = 3;
+const MAX_BUF_SIZE: usize = 8;
+
+fn leaves(context: &BubblegumTestContext, count: usize) -> Vec<LeafArgs> {
+    (0..count)
+        .map(|_| LeafArgs::new(&context.payer(), context.small_metadata_args()))
+        .collect()
+}
+
+// Creates two trees of the default creator, the second being the successor of the first.
+async fn linked_trees(
+    context: &BubblegumTestContext,
+) -> Result<(Tree<MAX_DEPTH, MAX_BUF_SIZE>, Tree<MAX_DEPTH, MAX_BUF_SIZE>)> {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    let successor = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+    tree.set_successor_tree(successor.tree_pubkey()).await?;
+    Ok((tree, successor))
+}
+
+async fn read_chain(trees: &[&Tree<MAX_DEPTH, MAX_BUF_SIZE>]) -> Vec<(Pubkey, TreeConfig)> {
+    let mut configs = HashMap::new();
+    for tree in trees {
+        configs.insert(tree.tree_pubkey(), tree.read_tree_config().await.unwrap());
+    }
+    get_tree_chain(trees[0].tree_pubkey(), |tree| {
+        configs.get(tree).copied().ok_or(())
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_mint_rolls_over_into_successor() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, successor) = linked_trees(&context).await.unwrap();
+    assert_eq!(
+        tree.read_tree_config().await.unwrap().successor_tree,
+        Some(successor.tree_pubkey())
+    );
+
+    tree.mint_batch_v1(&tree.tree_delegate, &leaves(&context, 8))
+        .await
+        .unwrap();
+
+    // Rolling over needs the successor accounts.
+    let leaf = LeafArgs::new(&context.payer(), context.small_metadata_args());
+    let err = tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::SuccessorTreeAccountsMissing);
+
+    let chain = read_chain(&[&tree, &successor]).await;
+    let mut tx = tree.mint_v1_tx(&tree.tree_delegate, &leaf);
+    tx.set_additional_account_metas(&get_successor_tree_accounts(&chain, 1));
+    tx.execute().await.unwrap();
+
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 8);
+    assert_eq!(successor.read_tree_config().await.unwrap().num_minted, 1);
+
+    // The ninth asset of the chain is the first one of the successor.
+    let chain = read_chain(&[&tree, &successor]).await;
+    assert_eq!(chain.len(), 2);
+    let asset_id = get_asset_id(&successor.tree_pubkey(), 0);
+    assert_eq!(get_chained_asset_id(&chain, 8), Some(asset_id));
+    assert_eq!(get_chained_asset_id(&chain, 9), None);
+    assert_eq!(
+        find_asset_in_tree_chain(&chain, &asset_id),
+        Some((successor.tree_pubkey(), 0))
+    );
+    assert_eq!(
+        find_asset_in_tree_chain(&chain, &get_asset_id(&tree.tree_pubkey(), 3)),
+        Some((tree.tree_pubkey(), 3))
+    );
+}
+
+#[tokio::test]
+async fn test_mint_batch_rolls_over_whole_batch() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, successor) = linked_trees(&context).await.unwrap();
+
+    for _ in 0..2 {
+        let chain = read_chain(&[&tree, &successor]).await;
+        let mut tx = tree.mint_batch_v1_tx(&tree.tree_delegate, &leaves(&context, 5));
+        tx.set_additional_account_metas(&get_successor_tree_accounts(&chain, 5));
+        tx.execute().await.unwrap();
+    }
+
+    // The second batch didn't fit in the first tree, so all of it went into the successor.
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 5);
+    assert_eq!(successor.read_tree_config().await.unwrap().num_minted, 5);
+}
+
+#[tokio::test]
+async fn test_mint_rolls_over_along_chain() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, successor) = linked_trees(&context).await.unwrap();
+    let last = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    successor
+        .set_successor_tree(last.tree_pubkey())
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        let chain = read_chain(&[&tree, &successor, &last]).await;
+        let mut tx = tree.mint_batch_v1_tx(&tree.tree_delegate, &leaves(&context, 8));
+        tx.set_additional_account_metas(&get_successor_tree_accounts(&chain, 8));
+        tx.execute().await.unwrap();
+    }
+
+    // Both full trees are skipped, which needs the config of the middle one too.
+    let leaf = LeafArgs::new(&context.payer(), context.small_metadata_args());
+    let mut tx = tree.mint_v1_tx(&tree.tree_delegate, &leaf);
+    tx.set_additional_account_metas(&[
+        AccountMeta::new(get_tree_authority(&last.tree_pubkey()), false),
+        AccountMeta::new(last.tree_pubkey(), false),
+    ]);
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::SuccessorTreeAccountsMissing);
+
+    let chain = read_chain(&[&tree, &successor, &last]).await;
+    assert_eq!(get_successor_tree_accounts(&chain, 1).len(), 3);
+    let mut tx = tree.mint_v1_tx(&tree.tree_delegate, &leaf);
+    tx.set_additional_account_metas(&get_successor_tree_accounts(&chain, 1));
+    tx.execute().await.unwrap();
+
+    assert_eq!(tree.read_tree_config().await.unwrap().num_minted, 8);
+    assert_eq!(successor.read_tree_config().await.unwrap().num_minted, 8);
+    assert_eq!(last.read_tree_config().await.unwrap().num_minted, 1);
+
+    let chain = read_chain(&[&tree, &successor, &last]).await;
+    assert_eq!(chain.len(), 3);
+    assert_eq!(
+        get_chained_asset_id(&chain, 16),
+        Some(get_asset_id(&last.tree_pubkey(), 0))
+    );
+}
+
+#[tokio::test]
+async fn test_set_successor_tree_only_once() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, _) = linked_trees(&context).await.unwrap();
+    let other = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    let err = tree
+        .set_successor_tree(other.tree_pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::SuccessorTreeAlreadySet);
+}
+
+#[tokio::test]
+async fn test_invalid_successor_trees() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, successor) = linked_trees(&context).await.unwrap();
+
+    // A tree can't be its own successor, and chains can't loop back.
+    let err = successor
+        .set_successor_tree(successor.tree_pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidSuccessorTree);
+    let err = successor
+        .set_successor_tree(tree.tree_pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidSuccessorTree);
+
+    // The successor has to belong to the same creator.
+    let creator = &context.default_creators[0];
+    let other = Tree::<MAX_DEPTH, MAX_BUF_SIZE>::with_creator(creator, context.client());
+    other.alloc(creator).await.unwrap();
+    other.create(creator).await.unwrap();
+    let err = successor
+        .set_successor_tree(other.tree_pubkey())
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::InvalidSuccessorTree);
+}
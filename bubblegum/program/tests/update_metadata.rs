@@ -0,0 +1,129 @@
+pub mod utils;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::metaplex_adapter::{Creator, UpdateArgs},
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Result, Tree};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+// Mints a mutable leaf, since the default metadata is immutable.
+async fn context_tree_and_mutable_leaf() -> Result<(
+    BubblegumTestContext,
+    Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    LeafArgs,
+)> {
+    let context = BubblegumTestContext::new().await?;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+
+    let mut metadata = context.default_metadata_args("test", "tst");
+    metadata.is_mutable = true;
+    let leaf = LeafArgs::new(&context.payer(), metadata);
+    tree.mint_v1(&tree.tree_delegate, &leaf).await?;
+
+    Ok((context, tree, leaf))
+}
+
+#[tokio::test]
+async fn test_update_metadata_passes() {
+    let (_, tree, mut leaf) = context_tree_and_mutable_leaf().await.unwrap();
+
+    let update_args = UpdateArgs {
+        name: Some("revealed".to_owned()),
+        uri: Some("www.solana.pos/revealed".to_owned()),
+        seller_fee_basis_points: Some(500),
+        primary_sale_happened: Some(true),
+        ..UpdateArgs::default()
+    };
+    tree.update_metadata(&leaf, update_args).await.unwrap();
+
+    // Reflect changes.
+    leaf.metadata.name = "revealed".to_owned();
+    leaf.metadata.uri = "www.solana.pos/revealed".to_owned();
+    leaf.metadata.seller_fee_basis_points = 500;
+    leaf.metadata.primary_sale_happened = true;
+
+    // Only succeeds if the leaf in the tree was built from the updated metadata.
+    let new_owner = Keypair::new();
+    tree.transfer(&leaf, new_owner.pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_update_metadata_can_lock_leaf() {
+    let (_, tree, mut leaf) = context_tree_and_mutable_leaf().await.unwrap();
+
+    let update_args = UpdateArgs {
+        is_mutable: Some(false),
+        ..UpdateArgs::default()
+    };
+    tree.update_metadata(&leaf, update_args).await.unwrap();
+    leaf.metadata.is_mutable = false;
+
+    let update_args = UpdateArgs {
+        name: Some("changed".to_owned()),
+        ..UpdateArgs::default()
+    };
+    let err = tree.update_metadata(&leaf, update_args).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::MetadataImmutable);
+}
+
+#[tokio::test]
+async fn test_update_immutable_metadata_fails() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaf) = context
+        .default_create_and_mint::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    let update_args = UpdateArgs {
+        uri: Some("www.solana.pos/moved".to_owned()),
+        ..UpdateArgs::default()
+    };
+    let err = tree.update_metadata(&leaf, update_args).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::MetadataImmutable);
+}
+
+#[tokio::test]
+async fn test_update_metadata_requires_tree_creator() {
+    let (context, tree, leaf) = context_tree_and_mutable_leaf().await.unwrap();
+    let impostor = &context.default_creators[0];
+
+    let mut tx = tree
+        .update_metadata_tx(&leaf, UpdateArgs::default())
+        .await
+        .unwrap();
+    tx.accounts.authority = impostor.pubkey();
+    let err = tx
+        .set_payer(impostor.pubkey())
+        .set_signers(&[impostor])
+        .execute()
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::UpdateAuthorityIncorrect);
+}
+
+#[tokio::test]
+async fn test_update_metadata_cannot_verify_creators() {
+    let (context, tree, leaf) = context_tree_and_mutable_leaf().await.unwrap();
+
+    let mut creators = leaf.metadata.creators.clone();
+    creators[0] = Creator {
+        verified: true,
+        ..creators[0]
+    };
+    assert_ne!(creators[0].address, context.payer().pubkey());
+
+    let update_args = UpdateArgs {
+        creators: Some(creators),
+        ..UpdateArgs::default()
+    };
+    let err = tree.update_metadata(&leaf, update_args).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CreatorDidNotVerify);
+}
@@ -0,0 +1,110 @@
+pub mod utils;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::metaplex_adapter::{UseMethod, Uses},
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    assert_bubblegum_error, clone_keypair,
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Result, Tree,
+};
+
+const MAX_DEPTH: usize = 20;
+const MAX_BUF_SIZE: usize = 64;
+
+async fn context_tree_and_leaf(
+    uses: Option<Uses>,
+) -> Result<(
+    BubblegumTestContext,
+    Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    LeafArgs,
+)> {
+    let context = BubblegumTestContext::new().await?;
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await?;
+
+    let mut metadata = context.default_metadata_args("ticket", "tkt");
+    metadata.uses = uses;
+    let leaf = LeafArgs::new(&context.payer(), metadata);
+    tree.mint_v1(&tree.tree_delegate, &leaf).await?;
+
+    Ok((context, tree, leaf))
+}
+
+fn uses(use_method: UseMethod, total: u64) -> Option<Uses> {
+    Some(Uses {
+        use_method,
+        remaining: total,
+        total,
+    })
+}
+
+#[tokio::test]
+async fn test_utilize_decrements_remaining_uses() {
+    let (_, tree, mut leaf) = context_tree_and_leaf(uses(UseMethod::Multiple, 3))
+        .await
+        .unwrap();
+
+    tree.utilize(&leaf, 2).await.unwrap();
+    leaf.metadata.uses.as_mut().unwrap().remaining = 1;
+
+    let err = tree.utilize(&leaf, 2).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::NotEnoughUses);
+
+    // Using up a leaf that doesn't burn leaves it in the tree.
+    tree.utilize(&leaf, 1).await.unwrap();
+    leaf.metadata.uses.as_mut().unwrap().remaining = 0;
+    tree.transfer(&leaf, Keypair::new().pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_utilize_burns_used_up_leaf() {
+    let (_, tree, mut leaf) = context_tree_and_leaf(uses(UseMethod::Burn, 2))
+        .await
+        .unwrap();
+
+    tree.utilize(&leaf, 1).await.unwrap();
+    leaf.metadata.uses.as_mut().unwrap().remaining = 1;
+    tree.utilize(&leaf, 1).await.unwrap();
+
+    // Neither the used up leaf nor the previous one are in the tree anymore.
+    leaf.metadata.uses.as_mut().unwrap().remaining = 0;
+    assert!(tree.transfer(&leaf, Keypair::new().pubkey()).await.is_err());
+    leaf.metadata.uses.as_mut().unwrap().remaining = 1;
+    assert!(tree.utilize(&leaf, 1).await.is_err());
+}
+
+#[tokio::test]
+async fn test_utilize_without_uses_fails() {
+    let (_, tree, leaf) = context_tree_and_leaf(None).await.unwrap();
+
+    let err = tree.utilize(&leaf, 1).await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::Unusable);
+}
+
+#[tokio::test]
+async fn test_leaf_delegate_can_utilize() {
+    let (mut context, tree, mut leaf) = context_tree_and_leaf(uses(UseMethod::Single, 1))
+        .await
+        .unwrap();
+
+    let delegate = Keypair::new();
+    context
+        .fund_account(delegate.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+        .await
+        .unwrap();
+    tree.delegate(&leaf, delegate.pubkey()).await.unwrap();
+    leaf.delegate = clone_keypair(&delegate);
+
+    let mut tx = tree.utilize_tx(&leaf, 1).await.unwrap();
+    tx.set_payer(delegate.pubkey())
+        .set_signers(&[&delegate])
+        .execute()
+        .await
+        .unwrap();
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use mpl_bubblegum::state::metaplex_adapter::{Creator, MetadataArgs, TokenProgramVersion};
-use solana_program::pubkey::Pubkey;
+use solana_program::{clock::Clock, pubkey::Pubkey};
 use solana_program_test::{BanksClient, ProgramTestContext};
 use solana_sdk::{
     signature::{Keypair, Signer},
@@ -75,6 +75,18 @@ impl BubblegumTestContext {
             .map_err(Error::BanksClient)
     }
 
+    // Moves the on-chain clock forward, e.g. to get past an expiry.
+    pub async fn advance_clock(&self, seconds: i64) -> Result<()> {
+        let mut clock: Clock = self
+            .client()
+            .get_sysvar()
+            .await
+            .map_err(Error::BanksClient)?;
+        clock.unix_timestamp += seconds;
+        self.program_context.set_sysvar(&clock);
+        Ok(())
+    }
+
     pub fn payer(&self) -> Keypair {
         clone_keypair(&self.program_context.payer)
     }
@@ -121,6 +133,15 @@ impl BubblegumTestContext {
         }
     }
 
+    // Metadata without creators and with short strings, so that batches of leaves fit in a
+    // single transaction.
+    pub fn small_metadata_args(&self) -> MetadataArgs {
+        let mut metadata = self.default_metadata_args("a", "b");
+        metadata.uri = "c".to_owned();
+        metadata.creators.clear();
+        metadata
+    }
+
     pub async fn default_create_tree<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize>(
         &self,
     ) -> Result<Tree<MAX_DEPTH, MAX_BUFFER_SIZE>> {
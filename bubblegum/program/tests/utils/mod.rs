@@ -1,13 +1,33 @@
 pub mod context;
+pub mod nft;
 
-use anchor_lang::{self, AccountDeserialize, InstructionData, ToAccountMetas};
+use anchor_lang::{
+    self, error::ERROR_CODE_OFFSET, AccountDeserialize, AnchorSerialize, InstructionData,
+    ToAccountMetas,
+};
 use bytemuck::{try_from_bytes, PodCastError};
 use mpl_bubblegum::{
+    error::BubblegumError,
     hash_creators, hash_metadata,
-    state::{metaplex_adapter::MetadataArgs, TreeConfig},
+    state::{
+        leaf_schema::{LeafSchema, LEAF_FLAG_FROZEN},
+        metaplex_adapter::{MetadataArgs, UpdateArgs},
+        BatchMintArgs, BatchVerifyCreatorArgs, Listing, MinterRecord, PrintSource, TreeConfig,
+        Voucher, ASSET_PREFIX, COLLECTION_CPI_PREFIX, LISTING_PREFIX, MINTER_RECORD_PREFIX,
+        PRINT_SOURCE_PREFIX, VOUCHER_PREFIX,
+    },
+    utils::{get_asset_id, get_tree_authority},
+};
+use nft::{
+    collection_authority_record_pubkey, edition_marker_pubkey, edition_pubkey, metadata_pubkey, Nft,
 };
 use solana_program::{
-    instruction::Instruction, pubkey::Pubkey, rent::Rent, system_instruction, system_program,
+    instruction::{Instruction, InstructionError},
+    keccak,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program, sysvar,
 };
 use solana_program_test::{BanksClient, BanksClientError, ProgramTest};
 use solana_sdk::{
@@ -15,7 +35,7 @@ use solana_sdk::{
     instruction::AccountMeta,
     signature::{Keypair, Signer, SignerError},
     signer::signers::Signers,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use spl_account_compression::state::ConcurrentMerkleTreeHeader;
 use spl_concurrent_merkle_tree::concurrent_merkle_tree::ConcurrentMerkleTree;
@@ -37,6 +57,16 @@ pub enum Error {
 
 pub type Result<T> = result::Result<T, Error>;
 
+// Checks that the first instruction of the transaction failed with `expected`.
+pub fn assert_bubblegum_error(err: Error, expected: BubblegumError) {
+    match err {
+        Error::BanksClient(BanksClientError::TransactionError(
+            TransactionError::InstructionError(0, InstructionError::Custom(code)),
+        )) => assert_eq!(code, expected as u32 + ERROR_CODE_OFFSET),
+        _ => panic!("unexpected error: {:?}", err),
+    }
+}
+
 pub fn program_test() -> ProgramTest {
     let mut test = ProgramTest::new("mpl_bubblegum", mpl_bubblegum::id(), None);
     test.add_program(
@@ -49,6 +79,7 @@ pub fn program_test() -> ProgramTest {
         spl_account_compression::id(),
         None,
     );
+    test.add_program("mpl_token_metadata", mpl_token_metadata::id(), None);
     test.set_compute_max_units(u64::MAX);
     test
 }
@@ -71,8 +102,61 @@ pub fn clone_keypair(k: &Keypair) -> Keypair {
     Keypair::from_bytes(k.to_bytes().as_slice()).unwrap()
 }
 
+// The PDA Bubblegum signs token-metadata collection CPIs with.
+pub fn bubblegum_signer() -> Pubkey {
+    Pubkey::find_program_address(&[COLLECTION_CPI_PREFIX.as_bytes()], &mpl_bubblegum::id()).0
+}
+
+// The message attesters of `reconcile_collection_size` sign.
+pub fn collection_size_attestation(collection_mint: &Pubkey, size: u64, slot: u64) -> Vec<u8> {
+    [
+        collection_mint.as_ref(),
+        size.to_le_bytes().as_ref(),
+        slot.to_le_bytes().as_ref(),
+    ]
+    .concat()
+}
+
+// An ed25519 program instruction that verifies the signature of `signer` over `message`, with
+// the public key, the signature and the message all in its own data.
+pub fn ed25519_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    let public_key_offset: u16 = 16;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_sdk::ed25519_program::id(),
+        accounts: Vec::new(),
+        data,
+    }
+}
+
 // Computes the `data_hash` and `creator_hash`. Taken from the contract code where something
 // similar is computed. Needs subsequent cleanup/refactoring.
+// The hash of the metadata that goes into the data hash along with the seller fee.
+fn compute_metadata_args_hash(metadata_args: &MetadataArgs) -> Result<[u8; 32]> {
+    let data = metadata_args
+        .try_to_vec()
+        .map_err(|e| Error::Anchor(ProgramError::from(e).into()))?;
+    Ok(keccak::hashv(&[data.as_slice()]).to_bytes())
+}
+
 fn compute_metadata_hashes(metadata_args: &MetadataArgs) -> Result<([u8; 32], [u8; 32])> {
     let data_hash = hash_metadata(metadata_args).map_err(Error::Anchor)?;
     let creator_hash = hash_creators(metadata_args.creators.as_slice()).map_err(Error::Anchor)?;
@@ -95,6 +179,8 @@ pub struct TxBuilder<T, U> {
     // accounts as well if necessary.
     pub accounts: T,
     pub additional_accounts: Vec<AccountMeta>,
+    // Instructions that run right before this one, in the same tx.
+    pub pre_instructions: Vec<Instruction>,
     // Similar to the above, but for instruction data.
     pub data: U,
     // The currently configured payer for the tx.
@@ -119,31 +205,61 @@ where
         self.client.borrow_mut()
     }
 
-    pub async fn execute(&self) -> Result<()> {
+    // The instruction that gets executed, including the additional account metas (if any).
+    pub fn instruction(&self) -> Instruction {
+        let mut ix = instruction(&self.accounts, &self.data);
+        ix.accounts.append(&mut self.additional_accounts.clone());
+        ix
+    }
+
+    async fn signed_tx(&self) -> Result<Transaction> {
         let recent_blockhash = self
             .client()
             .get_latest_blockhash()
             .await
             .map_err(Error::BanksClient)?;
 
-        let mut ix = instruction(&self.accounts, &self.data);
-
-        // Add the additional accounts metas (if any) as well.
-        ix.accounts.append(&mut self.additional_accounts.clone());
-
-        let mut tx = Transaction::new_with_payer(&[ix], Some(&self.payer));
+        let mut instructions = self.pre_instructions.clone();
+        instructions.push(self.instruction());
+        let mut tx = Transaction::new_with_payer(&instructions, Some(&self.payer));
 
         // Using `try_partial_sign` to avoid panics (and get an error when something is
         // wrong instead) no matter what signers are configured.
         tx.try_partial_sign(&self.signers.iter().collect::<Vec<_>>(), recent_blockhash)
             .map_err(Error::Signer)?;
 
+        Ok(tx)
+    }
+
+    pub async fn execute(&self) -> Result<()> {
+        let tx = self.signed_tx().await?;
+
         self.client()
             .process_transaction(tx)
             .await
             .map_err(Error::BanksClient)
     }
 
+    // Same as `execute`, but also returns the log messages of the transaction, which is where
+    // the Anchor events emitted by Bubblegum show up.
+    pub async fn execute_with_logs(&self) -> Result<Vec<String>> {
+        let tx = self.signed_tx().await?;
+
+        let outcome = self
+            .client()
+            .process_transaction_with_metadata(tx)
+            .await
+            .map_err(Error::BanksClient)?;
+        outcome
+            .result
+            .map_err(|err| Error::BanksClient(BanksClientError::TransactionError(err)))?;
+
+        Ok(outcome
+            .metadata
+            .map(|metadata| metadata.log_messages)
+            .unwrap_or_default())
+    }
+
     // Returning `&mut Self` to allow method chaining.
     pub fn set_signers(&mut self, signers: &[&Keypair]) -> &mut Self {
         self.signers = signers.iter().map(|k| clone_keypair(k)).collect();
@@ -180,19 +296,77 @@ pub type CreateBuilder =
 pub type MintV1Builder =
     TxBuilder<mpl_bubblegum::accounts::MintV1, mpl_bubblegum::instruction::MintV1>;
 
+pub type MintV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::MintV1, mpl_bubblegum::instruction::MintV2>;
+
+pub type MintBatchV1Builder =
+    TxBuilder<mpl_bubblegum::accounts::MintV1, mpl_bubblegum::instruction::MintBatchV1>;
+
 pub type BurnBuilder = TxBuilder<mpl_bubblegum::accounts::Burn, mpl_bubblegum::instruction::Burn>;
 
+pub type BurnV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::Burn, mpl_bubblegum::instruction::BurnV2>;
+
+pub type BurnWithMetadataBuilder = TxBuilder<
+    mpl_bubblegum::accounts::BurnWithMetadata,
+    mpl_bubblegum::instruction::BurnWithMetadata,
+>;
+
 pub type TransferBuilder =
     TxBuilder<mpl_bubblegum::accounts::Transfer, mpl_bubblegum::instruction::Transfer>;
 
+pub type TransferV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::Transfer, mpl_bubblegum::instruction::TransferV2>;
+
 pub type DelegateBuilder =
     TxBuilder<mpl_bubblegum::accounts::Delegate, mpl_bubblegum::instruction::Delegate>;
 
+pub type DelegateV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::Delegate, mpl_bubblegum::instruction::DelegateV2>;
+
+pub type ListBuilder = TxBuilder<mpl_bubblegum::accounts::List, mpl_bubblegum::instruction::List>;
+
+pub type DelistBuilder =
+    TxBuilder<mpl_bubblegum::accounts::Delist, mpl_bubblegum::instruction::Delist>;
+
+pub type BuyBuilder = TxBuilder<mpl_bubblegum::accounts::Buy, mpl_bubblegum::instruction::Buy>;
+
 pub type SetTreeDelegateBuilder = TxBuilder<
     mpl_bubblegum::accounts::SetTreeDelegate,
     mpl_bubblegum::instruction::SetTreeDelegate,
 >;
 
+pub type FreezeBuilder =
+    TxBuilder<mpl_bubblegum::accounts::LeafFreeze, mpl_bubblegum::instruction::Freeze>;
+
+pub type ThawBuilder =
+    TxBuilder<mpl_bubblegum::accounts::LeafFreeze, mpl_bubblegum::instruction::Thaw>;
+
+pub type UtilizeBuilder =
+    TxBuilder<mpl_bubblegum::accounts::Utilize, mpl_bubblegum::instruction::Utilize>;
+
+pub type CloseTreeBuilder =
+    TxBuilder<mpl_bubblegum::accounts::CloseTree, mpl_bubblegum::instruction::CloseTree>;
+
+pub type SetTreePublicBuilder =
+    TxBuilder<mpl_bubblegum::accounts::SetTreePublic, mpl_bubblegum::instruction::SetTreePublic>;
+
+pub type SetTreeClosableBuilder = TxBuilder<
+    mpl_bubblegum::accounts::SetTreeClosable,
+    mpl_bubblegum::instruction::SetTreeClosable,
+>;
+
+pub type SetSuccessorTreeBuilder = TxBuilder<
+    mpl_bubblegum::accounts::SetSuccessorTree,
+    mpl_bubblegum::instruction::SetSuccessorTree,
+>;
+
+pub type AddMinterBuilder =
+    TxBuilder<mpl_bubblegum::accounts::AddMinter, mpl_bubblegum::instruction::AddMinter>;
+
+pub type RemoveMinterBuilder =
+    TxBuilder<mpl_bubblegum::accounts::RemoveMinter, mpl_bubblegum::instruction::RemoveMinter>;
+
 pub type VerifyCreatorBuilder = TxBuilder<
     mpl_bubblegum::accounts::CreatorVerification,
     mpl_bubblegum::instruction::VerifyCreator,
@@ -203,12 +377,68 @@ pub type UnverifyCreatorBuilder = TxBuilder<
     mpl_bubblegum::instruction::UnverifyCreator,
 >;
 
+pub type VerifyCreatorBatchBuilder = TxBuilder<
+    mpl_bubblegum::accounts::BatchCreatorVerification,
+    mpl_bubblegum::instruction::VerifyCreatorBatch,
+>;
+
+pub type RedeemBuilder =
+    TxBuilder<mpl_bubblegum::accounts::Redeem, mpl_bubblegum::instruction::Redeem>;
+
+pub type RedeemV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::RedeemV2, mpl_bubblegum::instruction::RedeemV2>;
+
+pub type CancelRedeemBuilder =
+    TxBuilder<mpl_bubblegum::accounts::CancelRedeem, mpl_bubblegum::instruction::CancelRedeem>;
+
+pub type CancelRedeemV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::CancelRedeemV2, mpl_bubblegum::instruction::CancelRedeemV2>;
+
+pub type ExpireVoucherBuilder =
+    TxBuilder<mpl_bubblegum::accounts::ExpireVoucher, mpl_bubblegum::instruction::ExpireVoucher>;
+
+pub type DecompressV1Builder =
+    TxBuilder<mpl_bubblegum::accounts::DecompressV1, mpl_bubblegum::instruction::DecompressV1>;
+
+pub type DecompressV2Builder =
+    TxBuilder<mpl_bubblegum::accounts::DecompressV2, mpl_bubblegum::instruction::DecompressV2>;
+
+pub type SetPrintSourceBuilder =
+    TxBuilder<mpl_bubblegum::accounts::SetPrintSource, mpl_bubblegum::instruction::SetPrintSource>;
+
+pub type RemovePrintSourceBuilder = TxBuilder<
+    mpl_bubblegum::accounts::RemovePrintSource,
+    mpl_bubblegum::instruction::RemovePrintSource,
+>;
+
+pub type DecompressPrintBuilder = TxBuilder<
+    mpl_bubblegum::accounts::DecompressPrint,
+    mpl_bubblegum::instruction::DecompressPrint,
+>;
+
+pub type CompressBuilder =
+    TxBuilder<mpl_bubblegum::accounts::Compress, mpl_bubblegum::instruction::Compress>;
+
+pub type VerifyCollectionBuilder = TxBuilder<
+    mpl_bubblegum::accounts::CollectionVerification,
+    mpl_bubblegum::instruction::VerifyCollection,
+>;
+
+pub type ReconcileCollectionSizeBuilder = TxBuilder<
+    mpl_bubblegum::accounts::ReconcileCollectionSize,
+    mpl_bubblegum::instruction::ReconcileCollectionSize,
+>;
+
+pub type UpdateMetadataBuilder =
+    TxBuilder<mpl_bubblegum::accounts::UpdateMetadata, mpl_bubblegum::instruction::UpdateMetadata>;
+
 pub struct LeafArgs {
     pub owner: Keypair,
     pub delegate: Keypair,
     pub metadata: MetadataArgs,
     pub nonce: u64,
     pub index: u32,
+    pub flags: u8,
 }
 
 impl Clone for LeafArgs {
@@ -219,6 +449,7 @@ impl Clone for LeafArgs {
             metadata: self.metadata.clone(),
             nonce: self.nonce,
             index: self.index,
+            flags: self.flags,
         }
     }
 }
@@ -232,6 +463,7 @@ impl LeafArgs {
             metadata,
             nonce: 0,
             index: 0,
+            flags: 0,
         }
     }
 }
@@ -363,6 +595,7 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         TxBuilder {
             accounts,
             additional_accounts: Vec::new(),
+            pre_instructions: Vec::new(),
             data,
             payer,
             client: self.client.clone(),
@@ -432,6 +665,107 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         self.mint_v1_tx(tree_delegate, args).execute().await
     }
 
+    // Mints the leaf with `args.flags` set.
+    pub fn mint_v2_tx(&self, tree_delegate: &Keypair, args: &LeafArgs) -> MintV2Builder {
+        let tx = self.mint_v1_tx(tree_delegate, args);
+        let data = mpl_bubblegum::instruction::MintV2 {
+            message: args.metadata.clone(),
+            flags: args.flags,
+        };
+        self.tx_builder(
+            tx.accounts,
+            data,
+            args.owner.pubkey(),
+            &[tree_delegate, &args.owner],
+        )
+    }
+
+    pub async fn mint_v2(&self, tree_delegate: &Keypair, args: &LeafArgs) -> Result<()> {
+        self.mint_v2_tx(tree_delegate, args).execute().await
+    }
+
+    // All leaves are paid for by the tree delegate. The nonces and indices of `leaves` are not
+    // read, they follow from the number of leaves already minted.
+    pub fn mint_batch_v1_tx(
+        &self,
+        tree_delegate: &Keypair,
+        leaves: &[LeafArgs],
+    ) -> MintBatchV1Builder {
+        let accounts = mpl_bubblegum::accounts::MintV1 {
+            tree_authority: self.authority(),
+            tree_delegate: tree_delegate.pubkey(),
+            payer: tree_delegate.pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            // Not used by the batch instruction, which takes owners from the data.
+            leaf_owner: tree_delegate.pubkey(),
+            leaf_delegate: tree_delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+        };
+
+        let data = mpl_bubblegum::instruction::MintBatchV1 {
+            mints: leaves
+                .iter()
+                .map(|leaf| BatchMintArgs {
+                    leaf_owner: leaf.owner.pubkey(),
+                    leaf_delegate: leaf.delegate.pubkey(),
+                    metadata: leaf.metadata.clone(),
+                })
+                .collect(),
+        };
+
+        self.tx_builder(accounts, data, tree_delegate.pubkey(), &[tree_delegate])
+    }
+
+    pub async fn mint_batch_v1(&self, tree_delegate: &Keypair, leaves: &[LeafArgs]) -> Result<()> {
+        self.mint_batch_v1_tx(tree_delegate, leaves).execute().await
+    }
+
+    fn leaf_node(&self, leaf: &LeafArgs) -> Result<[u8; 32]> {
+        let (data_hash, creator_hash) = compute_metadata_hashes(&leaf.metadata)?;
+        Ok(LeafSchema::new_with_flags(
+            get_asset_id(&self.tree_pubkey(), leaf.nonce),
+            leaf.owner.pubkey(),
+            leaf.delegate.pubkey(),
+            leaf.nonce,
+            data_hash,
+            creator_hash,
+            leaf.flags,
+        )
+        .to_node())
+    }
+
+    // Computes the root of a tree that holds exactly `leaves`, at indices 0, 1 and so on, along
+    // with the proof of each leaf. Meant for trees small enough to pass whole proofs.
+    pub fn compute_root_and_proofs(
+        &self,
+        leaves: &[LeafArgs],
+    ) -> Result<([u8; 32], Vec<Vec<[u8; 32]>>)> {
+        let mut level = leaves
+            .iter()
+            .map(|leaf| self.leaf_node(leaf))
+            .collect::<Result<Vec<_>>>()?;
+        let mut proofs = vec![Vec::new(); leaves.len()];
+        let mut empty = [0; 32];
+
+        for depth in 0..MAX_DEPTH {
+            for (index, proof) in proofs.iter_mut().enumerate() {
+                let sibling = (index >> depth) ^ 1;
+                proof.push(level.get(sibling).copied().unwrap_or(empty));
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&empty);
+                    keccak::hashv(&[pair[0].as_ref(), right.as_ref()]).to_bytes()
+                })
+                .collect();
+            empty = keccak::hashv(&[empty.as_ref(), empty.as_ref()]).to_bytes();
+        }
+
+        Ok((level.first().copied().unwrap_or(empty), proofs))
+    }
+
     pub async fn decode_root(&self) -> Result<[u8; 32]> {
         let mut tree_account = self.read_account(self.tree_pubkey()).await?;
 
@@ -479,6 +813,65 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         self.burn_tx(args).await?.execute().await
     }
 
+    // Same as `burn_tx`, but sends `args.flags` along for leaves that have flags.
+    pub async fn burn_v2_tx(&self, args: &LeafArgs) -> Result<BurnV2Builder> {
+        let tx = self.burn_tx(args).await?;
+        let data = mpl_bubblegum::instruction::BurnV2 {
+            root: tx.data.root,
+            data_hash: tx.data.data_hash,
+            creator_hash: tx.data.creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags: args.flags,
+        };
+        Ok(self.tx_builder(tx.accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn burn_v2(&self, args: &LeafArgs) -> Result<()> {
+        self.burn_v2_tx(args).await?.execute().await
+    }
+
+    // Leaves with a verified collection get the collection accounts that decrement its size.
+    pub async fn burn_with_metadata_tx(&self, args: &LeafArgs) -> Result<BurnWithMetadataBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::BurnWithMetadata {
+            tree_authority: self.authority(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            bubblegum_signer: bubblegum_signer(),
+            token_metadata_program: mpl_token_metadata::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::BurnWithMetadata {
+            root,
+            nonce: args.nonce,
+            index: args.index,
+            current_metadata: args.metadata.clone(),
+            flags: args.flags,
+        };
+
+        let mut tx = self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]);
+        if let Some(collection) = args.metadata.collection.as_ref().filter(|c| c.verified) {
+            tx.set_additional_account_metas(&[
+                AccountMeta::new_readonly(collection.key, false),
+                AccountMeta::new(metadata_pubkey(&collection.key), false),
+                AccountMeta::new_readonly(
+                    collection_authority_record_pubkey(&collection.key, &bubblegum_signer()),
+                    false,
+                ),
+            ]);
+        }
+        Ok(tx)
+    }
+
+    pub async fn burn_with_metadata(&self, args: &LeafArgs) -> Result<()> {
+        self.burn_with_metadata_tx(args).await?.execute().await
+    }
+
     pub async fn verify_creator_tx(
         &self,
         args: &LeafArgs,
@@ -552,6 +945,165 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
             .await
     }
 
+    // Verifies the collection of the leaf, which has to be `collection`, signed and paid for by
+    // the owner of the collection parent.
+    pub async fn verify_collection_tx(
+        &self,
+        args: &LeafArgs,
+        collection: &Nft,
+    ) -> Result<VerifyCollectionBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let accounts = mpl_bubblegum::accounts::CollectionVerification {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            payer: collection.owner.pubkey(),
+            tree_delegate: self.delegate_pubkey(),
+            collection_authority: collection.owner.pubkey(),
+            collection_mint: collection.mint,
+            collection_metadata: collection.metadata(),
+            edition_account: collection.edition(),
+            bubblegum_signer: bubblegum_signer(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::VerifyCollection {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            message: args.metadata.clone(),
+        };
+
+        Ok(self.tx_builder(
+            accounts,
+            data,
+            collection.owner.pubkey(),
+            &[&collection.owner],
+        ))
+    }
+
+    pub async fn verify_collection(&self, args: &LeafArgs, collection: &Nft) -> Result<()> {
+        self.verify_collection_tx(args, collection)
+            .await?
+            .execute()
+            .await
+    }
+
+    // Sets the size of `collection` as its owner, without a collection authority record. The
+    // owner also attests to the count.
+    pub fn reconcile_collection_size_tx(
+        &self,
+        collection: &Nft,
+        size: u64,
+        counted_at_slot: u64,
+    ) -> ReconcileCollectionSizeBuilder {
+        let accounts = mpl_bubblegum::accounts::ReconcileCollectionSize {
+            collection_authority: collection.owner.pubkey(),
+            collection_mint: collection.mint,
+            collection_metadata: collection.metadata(),
+            collection_authority_record_pda: mpl_bubblegum::id(),
+            bubblegum_signer: bubblegum_signer(),
+            attester: collection.owner.pubkey(),
+            instructions: sysvar::instructions::id(),
+            log_wrapper: spl_noop::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::ReconcileCollectionSize {
+            size,
+            counted_at_slot,
+        };
+
+        let mut tx = self.tx_builder(
+            accounts,
+            data,
+            collection.owner.pubkey(),
+            &[&collection.owner],
+        );
+        tx.pre_instructions = vec![ed25519_instruction(
+            &collection.owner,
+            &collection_size_attestation(&collection.mint, size, counted_at_slot),
+        )];
+        tx
+    }
+
+    pub async fn reconcile_collection_size(
+        &self,
+        collection: &Nft,
+        size: u64,
+        counted_at_slot: u64,
+    ) -> Result<()> {
+        self.reconcile_collection_size_tx(collection, size, counted_at_slot)
+            .execute()
+            .await
+    }
+
+    // Verifies `creator` on the leaves of `tree_leaves` at the positions in `batch`, passing
+    // full proofs against the current root. `tree_leaves` must be all the leaves of the tree.
+    pub fn verify_creator_batch_tx(
+        &self,
+        tree_leaves: &[LeafArgs],
+        batch: &[usize],
+        creator: &Keypair,
+    ) -> Result<VerifyCreatorBatchBuilder> {
+        let (root, proofs) = self.compute_root_and_proofs(tree_leaves)?;
+
+        let accounts = mpl_bubblegum::accounts::BatchCreatorVerification {
+            tree_authority: self.authority(),
+            merkle_tree: self.tree_pubkey(),
+            payer: creator.pubkey(),
+            creator: creator.pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let leaves = batch
+            .iter()
+            .map(|&position| {
+                let leaf = &tree_leaves[position];
+                BatchVerifyCreatorArgs {
+                    root,
+                    leaf_owner: leaf.owner.pubkey(),
+                    leaf_delegate: leaf.delegate.pubkey(),
+                    nonce: leaf.nonce,
+                    index: leaf.index,
+                    metadata: leaf.metadata.clone(),
+                    proof_len: MAX_DEPTH as u8,
+                    flags: leaf.flags,
+                }
+            })
+            .collect();
+        let data = mpl_bubblegum::instruction::VerifyCreatorBatch { leaves };
+
+        let proof_accounts = batch
+            .iter()
+            .flat_map(|&position| proofs[position].iter())
+            .map(|node| Pubkey::new_from_array(*node))
+            .collect::<Vec<_>>();
+
+        let mut tx = self.tx_builder(accounts, data, creator.pubkey(), &[creator]);
+        tx.set_additional_accounts(&proof_accounts);
+        Ok(tx)
+    }
+
+    pub async fn verify_creator_batch(
+        &self,
+        tree_leaves: &[LeafArgs],
+        batch: &[usize],
+        creator: &Keypair,
+    ) -> Result<()> {
+        self.verify_creator_batch_tx(tree_leaves, batch, creator)?
+            .execute()
+            .await
+    }
+
     pub async fn transfer_tx(
         &self,
         args: &LeafArgs,
@@ -585,6 +1137,27 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         self.transfer_tx(args, new_owner).await?.execute().await
     }
 
+    pub async fn transfer_v2_tx(
+        &self,
+        args: &LeafArgs,
+        new_leaf_owner: Pubkey,
+    ) -> Result<TransferV2Builder> {
+        let tx = self.transfer_tx(args, new_leaf_owner).await?;
+        let data = mpl_bubblegum::instruction::TransferV2 {
+            root: tx.data.root,
+            data_hash: tx.data.data_hash,
+            creator_hash: tx.data.creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags: args.flags,
+        };
+        Ok(self.tx_builder(tx.accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn transfer_v2(&self, args: &LeafArgs, new_owner: Pubkey) -> Result<()> {
+        self.transfer_v2_tx(args, new_owner).await?.execute().await
+    }
+
     pub async fn delegate_tx(
         &self,
         args: &LeafArgs,
@@ -619,34 +1192,789 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
         self.delegate_tx(args, new_delegate).await?.execute().await
     }
 
-    pub fn set_tree_delegate_tx(&self, new_tree_delegate: Pubkey) -> SetTreeDelegateBuilder {
-        let accounts = mpl_bubblegum::accounts::SetTreeDelegate {
-            tree_creator: self.creator_pubkey(),
-            new_tree_delegate,
-            merkle_tree: self.tree_pubkey(),
-            tree_authority: self.authority(),
+    pub async fn delegate_v2_tx(
+        &self,
+        args: &LeafArgs,
+        new_leaf_delegate: Pubkey,
+    ) -> Result<DelegateV2Builder> {
+        let tx = self.delegate_tx(args, new_leaf_delegate).await?;
+        let data = mpl_bubblegum::instruction::DelegateV2 {
+            root: tx.data.root,
+            data_hash: tx.data.data_hash,
+            creator_hash: tx.data.creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags: args.flags,
         };
-
-        let data = mpl_bubblegum::instruction::SetTreeDelegate;
-
-        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+        Ok(self.tx_builder(tx.accounts, data, args.owner.pubkey(), &[&args.owner]))
     }
 
-    pub async fn set_tree_delegate(&mut self, new_delegate: &Keypair) -> Result<()> {
-        self.set_tree_delegate_tx(new_delegate.pubkey())
+    pub async fn delegate_v2(&self, args: &LeafArgs, new_delegate: Pubkey) -> Result<()> {
+        self.delegate_v2_tx(args, new_delegate)
+            .await?
             .execute()
-            .await?;
-        self.tree_delegate = clone_keypair(new_delegate);
-        Ok(())
+            .await
     }
 
-    // The following methods provide convenience when reading data from accounts.
-    async fn read_account(&self, key: Pubkey) -> Result<Account> {
-        self.client()
-            .get_account(key)
-            .await
-            .map_err(Error::BanksClient)?
-            .ok_or(Error::AccountNotFound(key))
+    pub fn listing(&self, nonce: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                LISTING_PREFIX.as_ref(),
+                self.tree_pubkey().as_ref(),
+                nonce.to_le_bytes().as_ref(),
+            ],
+            &mpl_bubblegum::id(),
+        )
+        .0
+    }
+
+    // Paid for by the leaf owner, who gets the rent back when the listing closes.
+    pub async fn list_tx(&self, args: &LeafArgs, price: u64) -> Result<ListBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let accounts = mpl_bubblegum::accounts::List {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            listing: self.listing(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::List {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            price,
+            flags: args.flags,
+        };
+
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn list(&self, args: &LeafArgs, price: u64) -> Result<()> {
+        self.list_tx(args, price).await?.execute().await
+    }
+
+    // Expects the leaf to be listed already.
+    pub async fn delist_tx(&self, args: &LeafArgs) -> Result<DelistBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let accounts = mpl_bubblegum::accounts::Delist {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            listing: self.listing(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::Delist {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+        };
+
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn delist(&self, args: &LeafArgs) -> Result<()> {
+        self.delist_tx(args).await?.execute().await
+    }
+
+    // Expects the leaf to be listed already. The creators of the leaf metadata are passed as
+    // writable additional accounts, so that they can receive royalties.
+    pub async fn buy_tx(&self, args: &LeafArgs, buyer: &Keypair) -> Result<BuyBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::Buy {
+            tree_authority: self.authority(),
+            buyer: buyer.pubkey(),
+            seller: args.owner.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            listing: self.listing(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::Buy {
+            root,
+            metadata_args_hash: compute_metadata_args_hash(&args.metadata)?,
+            seller_fee_basis_points: args.metadata.seller_fee_basis_points,
+            creators: args.metadata.creators.clone(),
+            nonce: args.nonce,
+            index: args.index,
+            flags: args.flags,
+        };
+
+        let mut tx = self.tx_builder(accounts, data, buyer.pubkey(), &[buyer]);
+        let creators = args
+            .metadata
+            .creators
+            .iter()
+            .map(|creator| AccountMeta::new(creator.address, false))
+            .collect::<Vec<_>>();
+        tx.set_additional_account_metas(&creators);
+        Ok(tx)
+    }
+
+    pub async fn buy(&self, args: &LeafArgs, buyer: &Keypair) -> Result<()> {
+        self.buy_tx(args, buyer).await?.execute().await
+    }
+
+    fn leaf_freeze_accounts(&self, args: &LeafArgs) -> mpl_bubblegum::accounts::LeafFreeze {
+        mpl_bubblegum::accounts::LeafFreeze {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        }
+    }
+
+    // Signed and paid for by the leaf delegate. `flags` are the current flags of the leaf.
+    pub async fn freeze_tx(&self, args: &LeafArgs, flags: u8) -> Result<FreezeBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let data = mpl_bubblegum::instruction::Freeze {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags,
+        };
+
+        Ok(self.tx_builder(
+            self.leaf_freeze_accounts(args),
+            data,
+            args.delegate.pubkey(),
+            &[&args.delegate],
+        ))
+    }
+
+    pub async fn freeze(&self, args: &LeafArgs) -> Result<()> {
+        self.freeze_tx(args, 0).await?.execute().await
+    }
+
+    pub async fn thaw_tx(&self, args: &LeafArgs, flags: u8) -> Result<ThawBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let data = mpl_bubblegum::instruction::Thaw {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags,
+        };
+
+        Ok(self.tx_builder(
+            self.leaf_freeze_accounts(args),
+            data,
+            args.delegate.pubkey(),
+            &[&args.delegate],
+        ))
+    }
+
+    pub async fn thaw(&self, args: &LeafArgs) -> Result<()> {
+        self.thaw_tx(args, LEAF_FLAG_FROZEN).await?.execute().await
+    }
+
+    pub fn set_tree_delegate_tx(&self, new_tree_delegate: Pubkey) -> SetTreeDelegateBuilder {
+        let accounts = mpl_bubblegum::accounts::SetTreeDelegate {
+            tree_creator: self.creator_pubkey(),
+            new_tree_delegate,
+            merkle_tree: self.tree_pubkey(),
+            tree_authority: self.authority(),
+        };
+
+        let data = mpl_bubblegum::instruction::SetTreeDelegate;
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn set_tree_delegate(&mut self, new_delegate: &Keypair) -> Result<()> {
+        self.set_tree_delegate_tx(new_delegate.pubkey())
+            .execute()
+            .await?;
+        self.tree_delegate = clone_keypair(new_delegate);
+        Ok(())
+    }
+
+    pub fn close_tree_tx(&self) -> CloseTreeBuilder {
+        let accounts = mpl_bubblegum::accounts::CloseTree {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            print_source: self.print_source(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::CloseTree;
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn close_tree(&self) -> Result<()> {
+        self.close_tree_tx().execute().await
+    }
+
+    pub fn set_tree_closable_tx(&self) -> SetTreeClosableBuilder {
+        let accounts = mpl_bubblegum::accounts::SetTreeClosable {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+        };
+
+        let data = mpl_bubblegum::instruction::SetTreeClosable;
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn set_tree_closable(&self) -> Result<()> {
+        self.set_tree_closable_tx().execute().await
+    }
+
+    pub fn set_tree_public_tx(&self, is_public: bool) -> SetTreePublicBuilder {
+        let accounts = mpl_bubblegum::accounts::SetTreePublic {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+        };
+
+        let data = mpl_bubblegum::instruction::SetTreePublic { is_public };
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn set_tree_public(&self, is_public: bool) -> Result<()> {
+        self.set_tree_public_tx(is_public).execute().await
+    }
+
+    // `successor_tree` has to be a tree of the same creator.
+    pub fn set_successor_tree_tx(&self, successor_tree: Pubkey) -> SetSuccessorTreeBuilder {
+        let accounts = mpl_bubblegum::accounts::SetSuccessorTree {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            successor_authority: get_tree_authority(&successor_tree),
+            successor_tree,
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::SetSuccessorTree {};
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn set_successor_tree(&self, successor_tree: Pubkey) -> Result<()> {
+        self.set_successor_tree_tx(successor_tree).execute().await
+    }
+
+    pub fn minter_record(&self, minter: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                MINTER_RECORD_PREFIX.as_ref(),
+                self.authority().as_ref(),
+                minter.as_ref(),
+            ],
+            &mpl_bubblegum::id(),
+        )
+        .0
+    }
+
+    pub fn add_minter_tx(&self, minter: Pubkey, mint_quota: u64) -> AddMinterBuilder {
+        let accounts = mpl_bubblegum::accounts::AddMinter {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            payer: self.creator_pubkey(),
+            minter,
+            merkle_tree: self.tree_pubkey(),
+            minter_record: self.minter_record(&minter),
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::AddMinter { mint_quota };
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn add_minter(&self, minter: Pubkey, mint_quota: u64) -> Result<()> {
+        self.add_minter_tx(minter, mint_quota).execute().await
+    }
+
+    pub fn remove_minter_tx(&self, minter: Pubkey) -> RemoveMinterBuilder {
+        let accounts = mpl_bubblegum::accounts::RemoveMinter {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            minter,
+            merkle_tree: self.tree_pubkey(),
+            minter_record: self.minter_record(&minter),
+        };
+
+        let data = mpl_bubblegum::instruction::RemoveMinter;
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn remove_minter(&self, minter: Pubkey) -> Result<()> {
+        self.remove_minter_tx(minter).execute().await
+    }
+
+    // Mints with an approved minter instead of the tree delegate, passing its minter record.
+    pub fn minter_mint_v1_tx(&self, minter: &Keypair, args: &LeafArgs) -> MintV1Builder {
+        let mut tx = self.mint_v1_tx(minter, args);
+        tx.set_additional_account_metas(&[AccountMeta::new(
+            self.minter_record(&minter.pubkey()),
+            false,
+        )]);
+        tx
+    }
+
+    pub async fn read_minter_record(&self, minter: &Pubkey) -> Result<MinterRecord> {
+        self.read_account_data(self.minter_record(minter)).await
+    }
+
+    // Uses the tree creator as the authority, which is what leaves without a verified
+    // collection expect.
+    pub async fn update_metadata_tx(
+        &self,
+        args: &LeafArgs,
+        update_args: UpdateArgs,
+    ) -> Result<UpdateMetadataBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::UpdateMetadata {
+            tree_authority: self.authority(),
+            authority: self.creator_pubkey(),
+            collection_mint: mpl_bubblegum::id(),
+            collection_metadata: mpl_bubblegum::id(),
+            collection_authority_record_pda: mpl_bubblegum::id(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::UpdateMetadata {
+            root,
+            nonce: args.nonce,
+            index: args.index,
+            current_metadata: args.metadata.clone(),
+            update_args,
+            flags: args.flags,
+        };
+
+        Ok(self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator]))
+    }
+
+    pub async fn update_metadata(&self, args: &LeafArgs, update_args: UpdateArgs) -> Result<()> {
+        self.update_metadata_tx(args, update_args)
+            .await?
+            .execute()
+            .await
+    }
+
+    // Signed and paid for by the leaf owner.
+    pub async fn utilize_tx(&self, args: &LeafArgs, number_of_uses: u64) -> Result<UtilizeBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::Utilize {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::Utilize {
+            root,
+            nonce: args.nonce,
+            index: args.index,
+            current_metadata: args.metadata.clone(),
+            number_of_uses,
+            flags: args.flags,
+        };
+
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn utilize(&self, args: &LeafArgs, number_of_uses: u64) -> Result<()> {
+        self.utilize_tx(args, number_of_uses).await?.execute().await
+    }
+
+    pub fn voucher(&self, nonce: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                VOUCHER_PREFIX.as_ref(),
+                self.tree_pubkey().as_ref(),
+                nonce.to_le_bytes().as_ref(),
+            ],
+            &mpl_bubblegum::id(),
+        )
+        .0
+    }
+
+    // The mint `decompress_v1` creates for the asset with the given nonce.
+    pub fn asset_mint(&self, nonce: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                ASSET_PREFIX.as_ref(),
+                self.tree_pubkey().as_ref(),
+                nonce.to_le_bytes().as_ref(),
+            ],
+            &mpl_bubblegum::id(),
+        )
+        .0
+    }
+
+    pub async fn redeem_tx(&self, args: &LeafArgs) -> Result<RedeemBuilder> {
+        let root = self.decode_root().await?;
+        let (data_hash, creator_hash) = compute_metadata_hashes(&args.metadata)?;
+
+        let accounts = mpl_bubblegum::accounts::Redeem {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            leaf_delegate: args.delegate.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            voucher: self.voucher(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::Redeem {
+            root,
+            data_hash,
+            creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+        };
+
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn redeem(&self, args: &LeafArgs) -> Result<()> {
+        self.redeem_tx(args).await?.execute().await
+    }
+
+    pub async fn redeem_v2_tx(&self, args: &LeafArgs) -> Result<RedeemV2Builder> {
+        let tx = self.redeem_tx(args).await?;
+        let accounts = mpl_bubblegum::accounts::RedeemV2 {
+            tree_authority: tx.accounts.tree_authority,
+            leaf_owner: tx.accounts.leaf_owner,
+            leaf_delegate: tx.accounts.leaf_delegate,
+            merkle_tree: tx.accounts.merkle_tree,
+            voucher: tx.accounts.voucher,
+            log_wrapper: tx.accounts.log_wrapper,
+            compression_program: tx.accounts.compression_program,
+            system_program: tx.accounts.system_program,
+        };
+        let data = mpl_bubblegum::instruction::RedeemV2 {
+            root: tx.data.root,
+            data_hash: tx.data.data_hash,
+            creator_hash: tx.data.creator_hash,
+            nonce: args.nonce,
+            index: args.index,
+            flags: args.flags,
+        };
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn redeem_v2(&self, args: &LeafArgs) -> Result<()> {
+        self.redeem_v2_tx(args).await?.execute().await
+    }
+
+    // Expects the leaf to be redeemed already.
+    pub async fn cancel_redeem_tx(&self, args: &LeafArgs) -> Result<CancelRedeemBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::CancelRedeem {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            voucher: self.voucher(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::CancelRedeem { root };
+
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn cancel_redeem(&self, args: &LeafArgs) -> Result<()> {
+        self.cancel_redeem_tx(args).await?.execute().await
+    }
+
+    pub async fn cancel_redeem_v2_tx(&self, args: &LeafArgs) -> Result<CancelRedeemV2Builder> {
+        let tx = self.cancel_redeem_tx(args).await?;
+        let accounts = mpl_bubblegum::accounts::CancelRedeemV2 {
+            tree_authority: tx.accounts.tree_authority,
+            leaf_owner: tx.accounts.leaf_owner,
+            merkle_tree: tx.accounts.merkle_tree,
+            voucher: tx.accounts.voucher,
+            log_wrapper: tx.accounts.log_wrapper,
+            compression_program: tx.accounts.compression_program,
+        };
+        let data = mpl_bubblegum::instruction::CancelRedeemV2 { root: tx.data.root };
+        Ok(self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner]))
+    }
+
+    pub async fn cancel_redeem_v2(&self, args: &LeafArgs) -> Result<()> {
+        self.cancel_redeem_v2_tx(args).await?.execute().await
+    }
+
+    // Expects the leaf to be redeemed already. Anyone can expire a voucher, so `payer` is
+    // not required to be related to the leaf.
+    pub async fn expire_voucher_tx(
+        &self,
+        args: &LeafArgs,
+        payer: &Keypair,
+    ) -> Result<ExpireVoucherBuilder> {
+        let root = self.decode_root().await?;
+
+        let accounts = mpl_bubblegum::accounts::ExpireVoucher {
+            tree_authority: self.authority(),
+            leaf_owner: args.owner.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            voucher: self.voucher(args.nonce),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::ExpireVoucher { root };
+
+        Ok(self.tx_builder(accounts, data, payer.pubkey(), &[payer]))
+    }
+
+    pub async fn expire_voucher(&self, args: &LeafArgs, payer: &Keypair) -> Result<()> {
+        self.expire_voucher_tx(args, payer).await?.execute().await
+    }
+
+    // Expects the leaf to be redeemed already.
+    pub fn decompress_v1_tx(&self, args: &LeafArgs) -> DecompressV1Builder {
+        let mint = self.asset_mint(args.nonce);
+
+        let accounts = mpl_bubblegum::accounts::DecompressV1 {
+            voucher: self.voucher(args.nonce),
+            leaf_owner: args.owner.pubkey(),
+            token_account: spl_associated_token_account::get_associated_token_address(
+                &args.owner.pubkey(),
+                &mint,
+            ),
+            mint,
+            mint_authority: Pubkey::find_program_address(&[mint.as_ref()], &mpl_bubblegum::id()).0,
+            metadata: metadata_pubkey(&mint),
+            master_edition: edition_pubkey(&mint),
+            system_program: system_program::id(),
+            sysvar_rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::DecompressV1 {
+            metadata: args.metadata.clone(),
+        };
+
+        self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner])
+    }
+
+    pub async fn decompress_v1(&self, args: &LeafArgs) -> Result<()> {
+        self.decompress_v1_tx(args).execute().await
+    }
+
+    pub fn decompress_v2_tx(&self, args: &LeafArgs) -> DecompressV2Builder {
+        let tx = self.decompress_v1_tx(args);
+        let accounts = mpl_bubblegum::accounts::DecompressV2 {
+            voucher: tx.accounts.voucher,
+            leaf_owner: tx.accounts.leaf_owner,
+            token_account: tx.accounts.token_account,
+            mint: tx.accounts.mint,
+            mint_authority: tx.accounts.mint_authority,
+            metadata: tx.accounts.metadata,
+            master_edition: tx.accounts.master_edition,
+            system_program: tx.accounts.system_program,
+            sysvar_rent: tx.accounts.sysvar_rent,
+            token_metadata_program: tx.accounts.token_metadata_program,
+            token_program: tx.accounts.token_program,
+            associated_token_program: tx.accounts.associated_token_program,
+            tree_authority: self.authority(),
+        };
+        let data = mpl_bubblegum::instruction::DecompressV2 {
+            metadata: args.metadata.clone(),
+        };
+        self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner])
+    }
+
+    pub async fn decompress_v2(&self, args: &LeafArgs) -> Result<()> {
+        self.decompress_v2_tx(args).execute().await
+    }
+
+    pub fn print_source(&self) -> Pubkey {
+        Pubkey::find_program_address(
+            &[PRINT_SOURCE_PREFIX.as_ref(), self.tree_pubkey().as_ref()],
+            &mpl_bubblegum::id(),
+        )
+        .0
+    }
+
+    // Where the tree authority keeps the token of the `master` print source.
+    pub fn print_source_token_account(&self, master: &Nft) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(&self.authority(), &master.mint)
+    }
+
+    // Expects `master` to be held by the tree creator. Leaves with `metadata` can print it.
+    pub fn set_print_source_tx(
+        &self,
+        master: &Nft,
+        metadata: &MetadataArgs,
+    ) -> Result<SetPrintSourceBuilder> {
+        let accounts = mpl_bubblegum::accounts::SetPrintSource {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            master_mint: master.mint,
+            master_edition: master.edition(),
+            creator_token_account: master.token_account(),
+            source_token_account: self.print_source_token_account(master),
+            print_source: self.print_source(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+            system_program: system_program::id(),
+            sysvar_rent: sysvar::rent::id(),
+        };
+
+        let (data_hash, _) = compute_metadata_hashes(metadata)?;
+        let data = mpl_bubblegum::instruction::SetPrintSource { data_hash };
+
+        Ok(self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator]))
+    }
+
+    pub async fn set_print_source(&self, master: &Nft, metadata: &MetadataArgs) -> Result<()> {
+        self.set_print_source_tx(master, metadata)?.execute().await
+    }
+
+    pub fn remove_print_source_tx(&self, master: &Nft) -> RemovePrintSourceBuilder {
+        let accounts = mpl_bubblegum::accounts::RemovePrintSource {
+            tree_authority: self.authority(),
+            tree_creator: self.creator_pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            print_source: self.print_source(),
+            master_mint: master.mint,
+            source_token_account: self.print_source_token_account(master),
+            creator_token_account: master.token_account(),
+            token_program: spl_token::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::RemovePrintSource;
+
+        self.tx_builder(accounts, data, self.creator_pubkey(), &[&self.tree_creator])
+    }
+
+    pub async fn remove_print_source(&self, master: &Nft) -> Result<()> {
+        self.remove_print_source_tx(master).execute().await
+    }
+
+    // Expects the leaf to be redeemed already, and `master` to be the print source.
+    pub fn decompress_print_tx(&self, args: &LeafArgs, master: &Nft) -> DecompressPrintBuilder {
+        let mint = self.asset_mint(args.nonce);
+
+        let accounts = mpl_bubblegum::accounts::DecompressPrint {
+            voucher: self.voucher(args.nonce),
+            leaf_owner: args.owner.pubkey(),
+            token_account: spl_associated_token_account::get_associated_token_address(
+                &args.owner.pubkey(),
+                &mint,
+            ),
+            mint,
+            mint_authority: Pubkey::find_program_address(&[mint.as_ref()], &mpl_bubblegum::id()).0,
+            metadata: metadata_pubkey(&mint),
+            edition: edition_pubkey(&mint),
+            tree_authority: self.authority(),
+            print_source: self.print_source(),
+            master_mint: master.mint,
+            master_metadata: master.metadata(),
+            master_edition: master.edition(),
+            master_token_account: self.print_source_token_account(master),
+            edition_marker: edition_marker_pubkey(&master.mint, args.nonce + 1),
+            system_program: system_program::id(),
+            sysvar_rent: sysvar::rent::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::DecompressPrint {
+            metadata: args.metadata.clone(),
+        };
+
+        self.tx_builder(accounts, data, args.owner.pubkey(), &[&args.owner])
+    }
+
+    pub async fn decompress_print(&self, args: &LeafArgs, master: &Nft) -> Result<()> {
+        self.decompress_print_tx(args, master).execute().await
+    }
+
+    // Compresses `nft` into the next leaf of the tree, keeping its owner as the leaf owner
+    // and delegate.
+    pub fn compress_tx(&self, nft: &Nft) -> CompressBuilder {
+        let accounts = mpl_bubblegum::accounts::Compress {
+            tree_authority: self.authority(),
+            leaf_owner: nft.owner.pubkey(),
+            leaf_delegate: nft.owner.pubkey(),
+            merkle_tree: self.tree_pubkey(),
+            tree_delegate: self.delegate_pubkey(),
+            token_account: nft.token_account(),
+            mint: nft.mint,
+            metadata: nft.metadata(),
+            master_edition: nft.edition(),
+            payer: nft.owner.pubkey(),
+            log_wrapper: spl_noop::id(),
+            compression_program: spl_account_compression::id(),
+            token_program: spl_token::id(),
+            token_metadata_program: mpl_token_metadata::id(),
+            system_program: system_program::id(),
+        };
+
+        let data = mpl_bubblegum::instruction::Compress;
+
+        self.tx_builder(
+            accounts,
+            data,
+            nft.owner.pubkey(),
+            &[&self.tree_delegate, &nft.owner],
+        )
+    }
+
+    pub async fn compress(&self, nft: &Nft) -> Result<()> {
+        self.compress_tx(nft).execute().await
+    }
+
+    // The following methods provide convenience when reading data from accounts.
+    async fn read_account(&self, key: Pubkey) -> Result<Account> {
+        self.client()
+            .get_account(key)
+            .await
+            .map_err(Error::BanksClient)?
+            .ok_or(Error::AccountNotFound(key))
     }
 
     // This reads the `Account`, but also deserializes the data to return
@@ -660,7 +1988,27 @@ impl<const MAX_DEPTH: usize, const MAX_BUFFER_SIZE: usize> Tree<MAX_DEPTH, MAX_B
             .and_then(|acc| T::try_deserialize(&mut acc.data.as_slice()).map_err(Error::Anchor))
     }
 
+    pub async fn read_listing(&self, nonce: u64) -> Result<Listing> {
+        self.read_account_data(self.listing(nonce)).await
+    }
+
+    pub async fn read_print_source(&self) -> Result<PrintSource> {
+        self.read_account_data(self.print_source()).await
+    }
+
+    pub async fn read_voucher(&self, nonce: u64) -> Result<Voucher> {
+        self.read_account_data(self.voucher(nonce)).await
+    }
+
     pub async fn read_tree_config(&self) -> Result<TreeConfig> {
         self.read_account_data(self.authority()).await
     }
+
+    pub async fn account_exists(&self, key: Pubkey) -> Result<bool> {
+        self.client()
+            .get_account(key)
+            .await
+            .map(|acc| acc.is_some())
+            .map_err(Error::BanksClient)
+    }
 }
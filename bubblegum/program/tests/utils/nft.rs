@@ -0,0 +1,323 @@
+use anchor_lang::AccountDeserialize;
+use mpl_bubblegum::state::{metaplex_adapter::MetadataArgs, metaplex_anchor::TokenMetadata};
+use mpl_token_metadata::state::{
+    CollectionDetails, COLLECTION_AUTHORITY, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX,
+};
+use solana_program::{program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::BanksClient;
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use super::{clone_keypair, Error, Result};
+
+pub fn metadata_pubkey(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+pub fn edition_pubkey(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+// The marker token-metadata uses to track whether `edition` of the master edition of `mint`
+// was printed already.
+pub fn edition_marker_pubkey(mint: &Pubkey, edition: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            EDITION.as_bytes(),
+            (edition / EDITION_MARKER_BIT_SIZE).to_string().as_bytes(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+// The record through which the update authority of the collection `mint` approves `authority`
+// as a collection authority.
+pub fn collection_authority_record_pubkey(mint: &Pubkey, authority: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+            COLLECTION_AUTHORITY.as_bytes(),
+            authority.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+// An uncompressed NFT living in token-metadata, held by `owner` in its associated token
+// account. The owner is also the mint and update authority, and pays for everything.
+pub struct Nft {
+    pub owner: Keypair,
+    pub mint: Pubkey,
+    client: BanksClient,
+}
+
+impl Nft {
+    // Refers to an NFT that already exists, i.e. one created by `decompress_v1`.
+    pub fn new(client: BanksClient, owner: &Keypair, mint: Pubkey) -> Self {
+        Nft {
+            owner: clone_keypair(owner),
+            mint,
+            client,
+        }
+    }
+
+    pub fn token_account(&self) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(&self.owner.pubkey(), &self.mint)
+    }
+
+    pub fn metadata(&self) -> Pubkey {
+        metadata_pubkey(&self.mint)
+    }
+
+    pub fn edition(&self) -> Pubkey {
+        edition_pubkey(&self.mint)
+    }
+
+    async fn process_tx(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<()> {
+        let recent_blockhash = self
+            .client
+            .get_latest_blockhash()
+            .await
+            .map_err(Error::BanksClient)?;
+
+        self.client
+            .process_transaction(Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.owner.pubkey()),
+                signers,
+                recent_blockhash,
+            ))
+            .await
+            .map_err(Error::BanksClient)
+    }
+
+    // Mints a token and adds the metadata and master edition accounts described by `args`.
+    // The creators in `args` must not be verified, since they don't sign here.
+    pub async fn create(
+        client: BanksClient,
+        owner: &Keypair,
+        args: &MetadataArgs,
+        max_supply: Option<u64>,
+    ) -> Result<Self> {
+        let mint = Keypair::new();
+        let mut nft = Nft::new(client, owner, mint.pubkey());
+        nft.create_mint(&mint).await?;
+
+        let owner = nft.owner.pubkey();
+        let mint = nft.mint;
+        let creators = if args.creators.is_empty() {
+            None
+        } else {
+            Some(args.creators.iter().map(|c| c.adapt()).collect())
+        };
+
+        let instructions = [
+            mpl_token_metadata::instruction::create_metadata_accounts_v2(
+                mpl_token_metadata::id(),
+                nft.metadata(),
+                mint,
+                owner,
+                owner,
+                owner,
+                args.name.clone(),
+                args.symbol.clone(),
+                args.uri.clone(),
+                creators,
+                args.seller_fee_basis_points,
+                true,
+                args.is_mutable,
+                args.collection.as_ref().map(|c| c.adapt()),
+                args.uses.as_ref().map(|u| u.adapt()),
+            ),
+            mpl_token_metadata::instruction::create_master_edition_v3(
+                mpl_token_metadata::id(),
+                nft.edition(),
+                mint,
+                owner,
+                owner,
+                nft.metadata(),
+                owner,
+                max_supply,
+            ),
+        ];
+        let owner_keypair = clone_keypair(&nft.owner);
+        nft.process_tx(&instructions, &[&owner_keypair]).await?;
+
+        Ok(nft)
+    }
+
+    async fn create_mint(&mut self, mint_keypair: &Keypair) -> Result<()> {
+        let owner = self.owner.pubkey();
+        let mint = self.mint;
+        let rent = self.client.get_rent().await.map_err(Error::BanksClient)?;
+
+        let instructions = [
+            system_instruction::create_account(
+                &owner,
+                &mint,
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint,
+                &owner,
+                Some(&owner),
+                0,
+            )
+            .unwrap(),
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &owner,
+                &owner,
+                &mint,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &mint,
+                &self.token_account(),
+                &owner,
+                &[],
+                1,
+            )
+            .unwrap(),
+        ];
+        let owner_keypair = clone_keypair(&self.owner);
+        self.process_tx(&instructions, &[&owner_keypair, mint_keypair])
+            .await
+    }
+
+    // Prints `edition` of this master edition into a new NFT held by the same owner.
+    pub async fn print(&mut self, edition: u64) -> Result<Nft> {
+        let mint = Keypair::new();
+        let mut print = Nft::new(self.client.clone(), &self.owner, mint.pubkey());
+        print.create_mint(&mint).await?;
+
+        let owner = self.owner.pubkey();
+        let instructions = [
+            mpl_token_metadata::instruction::mint_new_edition_from_master_edition_via_token(
+                mpl_token_metadata::id(),
+                print.metadata(),
+                print.edition(),
+                self.edition(),
+                print.mint,
+                owner,
+                owner,
+                owner,
+                self.token_account(),
+                owner,
+                self.metadata(),
+                self.mint,
+                edition,
+            ),
+        ];
+        let owner_keypair = clone_keypair(&self.owner);
+        self.process_tx(&instructions, &[&owner_keypair]).await?;
+
+        Ok(print)
+    }
+
+    // Turns this NFT into a sized collection parent.
+    pub async fn set_collection_size(&mut self, size: u64) -> Result<()> {
+        let instructions = [mpl_token_metadata::instruction::set_collection_size(
+            mpl_token_metadata::id(),
+            self.metadata(),
+            self.owner.pubkey(),
+            self.mint,
+            None,
+            size,
+        )];
+        let owner_keypair = clone_keypair(&self.owner);
+        self.process_tx(&instructions, &[&owner_keypair]).await
+    }
+
+    // The size of this collection parent, if it's sized.
+    pub async fn collection_size(&mut self) -> Result<Option<u64>> {
+        let account = self
+            .client
+            .get_account(self.metadata())
+            .await
+            .map_err(Error::BanksClient)?
+            .ok_or(Error::AccountNotFound(self.metadata()))?;
+        let metadata =
+            TokenMetadata::try_deserialize(&mut account.data.as_slice()).map_err(Error::Anchor)?;
+        Ok(metadata
+            .collection_details
+            .as_ref()
+            .map(|CollectionDetails::V1 { size }| *size))
+    }
+
+    // Verifies this NFT as an item of the sized collection parent `collection`, owned by its
+    // update authority.
+    pub async fn verify_sized_collection_item(&mut self, collection: &Nft) -> Result<()> {
+        let instructions = [
+            mpl_token_metadata::instruction::verify_sized_collection_item(
+                mpl_token_metadata::id(),
+                self.metadata(),
+                collection.owner.pubkey(),
+                self.owner.pubkey(),
+                collection.mint,
+                collection.metadata(),
+                collection.edition(),
+                None,
+            ),
+        ];
+        let owner_keypair = clone_keypair(&self.owner);
+        let collection_owner_keypair = clone_keypair(&collection.owner);
+        let mut signers = vec![&owner_keypair];
+        if collection_owner_keypair.pubkey() != owner_keypair.pubkey() {
+            signers.push(&collection_owner_keypair);
+        }
+        self.process_tx(&instructions, &signers).await
+    }
+
+    // Approves `authority` as a collection authority of this collection parent.
+    pub async fn approve_collection_authority(&mut self, authority: Pubkey) -> Result<()> {
+        let owner = self.owner.pubkey();
+        let instructions = [
+            mpl_token_metadata::instruction::approve_collection_authority(
+                mpl_token_metadata::id(),
+                collection_authority_record_pubkey(&self.mint, &authority),
+                authority,
+                owner,
+                owner,
+                self.metadata(),
+                self.mint,
+            ),
+        ];
+        let owner_keypair = clone_keypair(&self.owner);
+        self.process_tx(&instructions, &[&owner_keypair]).await
+    }
+}
@@ -0,0 +1,145 @@
+pub mod utils;
+
+use mpl_bubblegum::{
+    error::BubblegumError,
+    state::{
+        leaf_schema::LEAF_FLAG_NON_TRANSFERABLE,
+        metaplex_adapter::{Creator, MetadataArgs},
+    },
+};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{assert_bubblegum_error, context::BubblegumTestContext, LeafArgs, Tree};
+
+// Small enough for a batch of three leaves and their full proofs to fit in a transaction.
+const MAX_DEPTH: usize = 3;
+const MAX_BUF_SIZE: usize = 8;
+
+fn metadata_args_with_creator(context: &BubblegumTestContext, creator: &Keypair) -> MetadataArgs {
+    let mut metadata = context.small_metadata_args();
+    metadata.creators = vec![Creator {
+        address: creator.pubkey(),
+        verified: false,
+        share: 100,
+    }];
+    metadata
+}
+
+async fn tree_with_leaves(
+    context: &BubblegumTestContext,
+    count: u64,
+) -> (Tree<MAX_DEPTH, MAX_BUF_SIZE>, Vec<LeafArgs>) {
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+
+    let creator = &context.default_creators[0];
+    let mut leaves = Vec::new();
+    for nonce in 0..count {
+        let mut leaf = LeafArgs::new(
+            &Keypair::new(),
+            metadata_args_with_creator(context, creator),
+        );
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+        tree.mint_v1(&tree.tree_delegate, &leaf).await.unwrap();
+        leaves.push(leaf);
+    }
+    (tree, leaves)
+}
+
+#[tokio::test]
+async fn test_verify_creator_batch_passes() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, mut leaves) = tree_with_leaves(&context, 3).await;
+    let creator = &context.default_creators[0];
+
+    // Every proof is against the root from before the batch, which changes with each leaf.
+    let (root, _) = tree.compute_root_and_proofs(&leaves).unwrap();
+    assert_eq!(tree.decode_root().await.unwrap(), root);
+
+    tree.verify_creator_batch(&leaves, &[2, 0, 1], creator)
+        .await
+        .unwrap();
+
+    for leaf in leaves.iter_mut() {
+        leaf.metadata.creators[0].verified = true;
+    }
+    let (root, _) = tree.compute_root_and_proofs(&leaves).unwrap();
+    assert_eq!(tree.decode_root().await.unwrap(), root);
+}
+
+#[tokio::test]
+async fn test_verify_creator_batch_keeps_leaf_flags() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let tree = context
+        .default_create_tree::<MAX_DEPTH, MAX_BUF_SIZE>()
+        .await
+        .unwrap();
+    let creator = &context.default_creators[0];
+
+    let mut leaf = LeafArgs::new(
+        &Keypair::new(),
+        metadata_args_with_creator(&context, creator),
+    );
+    leaf.flags = LEAF_FLAG_NON_TRANSFERABLE;
+    tree.mint_v2(&tree.tree_delegate, &leaf).await.unwrap();
+    let mut leaves = vec![leaf];
+
+    tree.verify_creator_batch(&leaves, &[0], creator)
+        .await
+        .unwrap();
+
+    leaves[0].metadata.creators[0].verified = true;
+    let (root, _) = tree.compute_root_and_proofs(&leaves).unwrap();
+    assert_eq!(tree.decode_root().await.unwrap(), root);
+}
+
+#[tokio::test]
+async fn test_verify_creator_batch_rejects_duplicate_leaves() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaves) = tree_with_leaves(&context, 2).await;
+    let creator = &context.default_creators[0];
+
+    let err = tree
+        .verify_creator_batch(&leaves, &[1, 1], creator)
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::DuplicateLeafInBatch);
+}
+
+#[tokio::test]
+async fn test_verify_creator_batch_checks_proof_lengths() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaves) = tree_with_leaves(&context, 2).await;
+    let creator = &context.default_creators[0];
+
+    let mut tx = tree
+        .verify_creator_batch_tx(&leaves, &[0, 1], creator)
+        .unwrap();
+    tx.additional_accounts.pop();
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::ProofLengthMismatch);
+
+    let mut tx = tree
+        .verify_creator_batch_tx(&leaves, &[0], creator)
+        .unwrap();
+    let extra = tx.additional_accounts[0].clone();
+    tx.additional_accounts.push(extra);
+    let err = tx.execute().await.unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::ProofLengthMismatch);
+}
+
+#[tokio::test]
+async fn test_verify_creator_batch_requires_leaf_creator() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let (tree, leaves) = tree_with_leaves(&context, 2).await;
+
+    let err = tree
+        .verify_creator_batch(&leaves, &[0, 1], &context.default_creators[1])
+        .await
+        .unwrap_err();
+    assert_bubblegum_error(err, BubblegumError::CreatorNotFound);
+}
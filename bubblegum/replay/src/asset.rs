@@ -0,0 +1,62 @@
+use crate::merkle::Node;
+use anchor_lang::prelude::*;
+use mpl_bubblegum::state::metaplex_adapter::MetadataArgs;
+
+/// Where an asset currently lives.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AssetState {
+    /// The asset is a leaf in its tree.
+    Compressed,
+    /// The leaf was cleared by `redeem` and a voucher holds it until it is decompressed or
+    /// the redeem is cancelled.
+    Redeemed,
+    /// The asset was turned into a token-metadata NFT.
+    Decompressed,
+    Burned,
+}
+
+/// The replayed state of a compressed asset.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Asset {
+    pub id: Pubkey,
+    pub tree: Pubkey,
+    pub nonce: u64,
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    /// `LEAF_FLAG_*` bits of the leaf, e.g. whether it is frozen.
+    pub flags: u8,
+    pub leaf_hash: Node,
+    /// Only known for assets whose mint (or a later metadata change) was ingested.
+    pub metadata: Option<MetadataArgs>,
+    pub state: AssetState,
+}
+
+impl Asset {
+    /// Bubblegum only ever appends leaves while incrementing the mint count, so the nonce of
+    /// an asset is also the index of its leaf.
+    pub fn index(&self) -> u32 {
+        self.nonce as u32
+    }
+}
+
+/// Everything needed to pass a leaf to `transfer`, `burn`, `delegate` and friends. The proof
+/// goes into the remaining accounts, from the leaf level up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetProof {
+    pub tree: Pubkey,
+    pub root: Node,
+    pub leaf: Node,
+    pub index: u32,
+    pub proof: Vec<Node>,
+}
+
+impl AssetProof {
+    pub fn proof_pubkeys(&self) -> Vec<Pubkey> {
+        self.proof
+            .iter()
+            .map(|node| Pubkey::new_from_array(*node))
+            .collect()
+    }
+}
@@ -0,0 +1,811 @@
+//! Decodes Bubblegum instructions into their arguments and named accounts, for indexers that
+//! need more than the [`InstructionName`] `get_instruction_type` returns.
+
+use crate::error::{ReplayError, Result};
+use anchor_lang::AnchorDeserialize;
+use mpl_bubblegum::{accounts, get_instruction_type, instruction, InstructionName};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+/// The accounts and arguments of a decoded instruction. Accounts past the ones the instruction
+/// declares, such as proof nodes and minter records, end up in `remaining_accounts`.
+pub struct Decoded<A, D> {
+    pub accounts: A,
+    pub args: D,
+    pub remaining_accounts: Vec<Pubkey>,
+}
+
+pub enum BubblegumInstruction {
+    CreateTree(Decoded<accounts::CreateTree, instruction::CreateTree>),
+    SetTreeDelegate(Decoded<accounts::SetTreeDelegate, instruction::SetTreeDelegate>),
+    CloseTree(Decoded<accounts::CloseTree, instruction::CloseTree>),
+    SetTreePublic(Decoded<accounts::SetTreePublic, instruction::SetTreePublic>),
+    SetTreeClosable(Decoded<accounts::SetTreeClosable, instruction::SetTreeClosable>),
+    SetSuccessorTree(Decoded<accounts::SetSuccessorTree, instruction::SetSuccessorTree>),
+    AddMinter(Decoded<accounts::AddMinter, instruction::AddMinter>),
+    RemoveMinter(Decoded<accounts::RemoveMinter, instruction::RemoveMinter>),
+    SetPrintSource(Decoded<accounts::SetPrintSource, instruction::SetPrintSource>),
+    RemovePrintSource(Decoded<accounts::RemovePrintSource, instruction::RemovePrintSource>),
+    MintV1(Decoded<accounts::MintV1, instruction::MintV1>),
+    MintV2(Decoded<accounts::MintV1, instruction::MintV2>),
+    MintBatchV1(Decoded<accounts::MintV1, instruction::MintBatchV1>),
+    VerifyCreator(Decoded<accounts::CreatorVerification, instruction::VerifyCreator>),
+    UnverifyCreator(Decoded<accounts::CreatorVerification, instruction::UnverifyCreator>),
+    VerifyCreatorBatch(
+        Decoded<accounts::BatchCreatorVerification, instruction::VerifyCreatorBatch>,
+    ),
+    VerifyCollection(Decoded<accounts::CollectionVerification, instruction::VerifyCollection>),
+    UnverifyCollection(Decoded<accounts::CollectionVerification, instruction::UnverifyCollection>),
+    SetAndVerifyCollection(
+        Decoded<accounts::CollectionVerification, instruction::SetAndVerifyCollection>,
+    ),
+    UpdateMetadata(Decoded<accounts::UpdateMetadata, instruction::UpdateMetadata>),
+    ReconcileCollectionSize(
+        Decoded<accounts::ReconcileCollectionSize, instruction::ReconcileCollectionSize>,
+    ),
+    Utilize(Decoded<accounts::Utilize, instruction::Utilize>),
+    Transfer(Decoded<accounts::Transfer, instruction::Transfer>),
+    TransferV2(Decoded<accounts::Transfer, instruction::TransferV2>),
+    Delegate(Decoded<accounts::Delegate, instruction::Delegate>),
+    DelegateV2(Decoded<accounts::Delegate, instruction::DelegateV2>),
+    List(Decoded<accounts::List, instruction::List>),
+    Delist(Decoded<accounts::Delist, instruction::Delist>),
+    Buy(Decoded<accounts::Buy, instruction::Buy>),
+    Freeze(Decoded<accounts::LeafFreeze, instruction::Freeze>),
+    Thaw(Decoded<accounts::LeafFreeze, instruction::Thaw>),
+    Burn(Decoded<accounts::Burn, instruction::Burn>),
+    BurnV2(Decoded<accounts::Burn, instruction::BurnV2>),
+    BurnWithMetadata(Decoded<accounts::BurnWithMetadata, instruction::BurnWithMetadata>),
+    Redeem(Decoded<accounts::Redeem, instruction::Redeem>),
+    RedeemV2(Decoded<accounts::RedeemV2, instruction::RedeemV2>),
+    CancelRedeem(Decoded<accounts::CancelRedeem, instruction::CancelRedeem>),
+    CancelRedeemV2(Decoded<accounts::CancelRedeemV2, instruction::CancelRedeemV2>),
+    ExpireVoucher(Decoded<accounts::ExpireVoucher, instruction::ExpireVoucher>),
+    DecompressV1(Decoded<accounts::DecompressV1, instruction::DecompressV1>),
+    DecompressV2(Decoded<accounts::DecompressV2, instruction::DecompressV2>),
+    DecompressPrint(Decoded<accounts::DecompressPrint, instruction::DecompressPrint>),
+    Compress(Decoded<accounts::Compress, instruction::Compress>),
+}
+
+/// Fills in the fields of `accounts::$accounts` from the instruction accounts, in the order
+/// they are listed here, which must be the order the program declares them in.
+macro_rules! decode {
+    ($ix:expr, $variant:ident, $accounts:ident { $($field:ident),* $(,)? }) => {{
+        let keys: Vec<Pubkey> = $ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        let mut index = 0;
+        let accounts = accounts::$accounts {
+            $($field: {
+                let key = *keys.get(index).ok_or(ReplayError::MissingAccount(index))?;
+                index += 1;
+                key
+            },)*
+        };
+        BubblegumInstruction::$variant(Decoded {
+            accounts,
+            args: instruction::$variant::deserialize(&mut &$ix.data[8..])?,
+            remaining_accounts: keys[index..].to_vec(),
+        })
+    }};
+}
+
+/// Decodes an instruction sent to Bubblegum.
+pub fn decode_bubblegum_instruction(ix: &Instruction) -> Result<BubblegumInstruction> {
+    if ix.program_id != mpl_bubblegum::id() || ix.data.len() < 8 {
+        return Err(ReplayError::UnknownInstruction);
+    }
+
+    let decoded = match get_instruction_type(&ix.data) {
+        InstructionName::CreateTree => decode!(
+            ix,
+            CreateTree,
+            CreateTree {
+                tree_authority,
+                merkle_tree,
+                payer,
+                tree_creator,
+                log_wrapper,
+                compression_program,
+                system_program,
+            }
+        ),
+        InstructionName::SetTreeDelegate => decode!(
+            ix,
+            SetTreeDelegate,
+            SetTreeDelegate {
+                tree_authority,
+                tree_creator,
+                new_tree_delegate,
+                merkle_tree,
+            }
+        ),
+        InstructionName::CloseTree => decode!(
+            ix,
+            CloseTree,
+            CloseTree {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+                print_source,
+                compression_program,
+            }
+        ),
+        InstructionName::SetTreePublic => decode!(
+            ix,
+            SetTreePublic,
+            SetTreePublic {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+            }
+        ),
+        InstructionName::SetTreeClosable => decode!(
+            ix,
+            SetTreeClosable,
+            SetTreeClosable {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+            }
+        ),
+        InstructionName::SetSuccessorTree => decode!(
+            ix,
+            SetSuccessorTree,
+            SetSuccessorTree {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+                successor_authority,
+                successor_tree,
+                system_program,
+            }
+        ),
+        InstructionName::AddMinter => decode!(
+            ix,
+            AddMinter,
+            AddMinter {
+                tree_authority,
+                tree_creator,
+                payer,
+                minter,
+                merkle_tree,
+                minter_record,
+                system_program,
+            }
+        ),
+        InstructionName::RemoveMinter => decode!(
+            ix,
+            RemoveMinter,
+            RemoveMinter {
+                tree_authority,
+                tree_creator,
+                minter,
+                merkle_tree,
+                minter_record,
+            }
+        ),
+        InstructionName::SetPrintSource => decode!(
+            ix,
+            SetPrintSource,
+            SetPrintSource {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+                master_mint,
+                master_edition,
+                creator_token_account,
+                source_token_account,
+                print_source,
+                token_metadata_program,
+                token_program,
+                associated_token_program,
+                system_program,
+                sysvar_rent,
+            }
+        ),
+        InstructionName::RemovePrintSource => decode!(
+            ix,
+            RemovePrintSource,
+            RemovePrintSource {
+                tree_authority,
+                tree_creator,
+                merkle_tree,
+                print_source,
+                master_mint,
+                source_token_account,
+                creator_token_account,
+                token_program,
+            }
+        ),
+        InstructionName::MintV1 => decode!(
+            ix,
+            MintV1,
+            MintV1 {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::MintV2 => decode!(
+            ix,
+            MintV2,
+            MintV1 {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::MintBatchV1 => decode!(
+            ix,
+            MintBatchV1,
+            MintV1 {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::VerifyCreator => decode!(
+            ix,
+            VerifyCreator,
+            CreatorVerification {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                creator,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::UnverifyCreator => decode!(
+            ix,
+            UnverifyCreator,
+            CreatorVerification {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                creator,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::VerifyCreatorBatch => decode!(
+            ix,
+            VerifyCreatorBatch,
+            BatchCreatorVerification {
+                tree_authority,
+                merkle_tree,
+                payer,
+                creator,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::VerifyCollection => decode!(
+            ix,
+            VerifyCollection,
+            CollectionVerification {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                collection_authority,
+                collection_mint,
+                collection_metadata,
+                edition_account,
+                bubblegum_signer,
+                log_wrapper,
+                compression_program,
+                token_metadata_program,
+            }
+        ),
+        InstructionName::UnverifyCollection => decode!(
+            ix,
+            UnverifyCollection,
+            CollectionVerification {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                collection_authority,
+                collection_mint,
+                collection_metadata,
+                edition_account,
+                bubblegum_signer,
+                log_wrapper,
+                compression_program,
+                token_metadata_program,
+            }
+        ),
+        InstructionName::SetAndVerifyCollection => decode!(
+            ix,
+            SetAndVerifyCollection,
+            CollectionVerification {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                payer,
+                tree_delegate,
+                collection_authority,
+                collection_mint,
+                collection_metadata,
+                edition_account,
+                bubblegum_signer,
+                log_wrapper,
+                compression_program,
+                token_metadata_program,
+            }
+        ),
+        InstructionName::UpdateMetadata => decode!(
+            ix,
+            UpdateMetadata,
+            UpdateMetadata {
+                tree_authority,
+                authority,
+                collection_mint,
+                collection_metadata,
+                collection_authority_record_pda,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::ReconcileCollectionSize => decode!(
+            ix,
+            ReconcileCollectionSize,
+            ReconcileCollectionSize {
+                collection_authority,
+                collection_mint,
+                collection_metadata,
+                collection_authority_record_pda,
+                bubblegum_signer,
+                attester,
+                instructions,
+                log_wrapper,
+                token_metadata_program,
+            }
+        ),
+        InstructionName::Utilize => decode!(
+            ix,
+            Utilize,
+            Utilize {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::Transfer => decode!(
+            ix,
+            Transfer,
+            Transfer {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                new_leaf_owner,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::TransferV2 => decode!(
+            ix,
+            TransferV2,
+            Transfer {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                new_leaf_owner,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::Delegate => decode!(
+            ix,
+            Delegate,
+            Delegate {
+                tree_authority,
+                leaf_owner,
+                previous_leaf_delegate,
+                new_leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::DelegateV2 => decode!(
+            ix,
+            DelegateV2,
+            Delegate {
+                tree_authority,
+                leaf_owner,
+                previous_leaf_delegate,
+                new_leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::List => decode!(
+            ix,
+            List,
+            List {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                listing,
+                log_wrapper,
+                compression_program,
+                system_program,
+            }
+        ),
+        InstructionName::Delist => decode!(
+            ix,
+            Delist,
+            Delist {
+                tree_authority,
+                leaf_owner,
+                merkle_tree,
+                listing,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::Buy => decode!(
+            ix,
+            Buy,
+            Buy {
+                tree_authority,
+                buyer,
+                seller,
+                merkle_tree,
+                listing,
+                log_wrapper,
+                compression_program,
+                system_program,
+            }
+        ),
+        InstructionName::Freeze => decode!(
+            ix,
+            Freeze,
+            LeafFreeze {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::Thaw => decode!(
+            ix,
+            Thaw,
+            LeafFreeze {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::Burn => decode!(
+            ix,
+            Burn,
+            Burn {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::BurnV2 => decode!(
+            ix,
+            BurnV2,
+            Burn {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::BurnWithMetadata => decode!(
+            ix,
+            BurnWithMetadata,
+            BurnWithMetadata {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                log_wrapper,
+                compression_program,
+                bubblegum_signer,
+                token_metadata_program,
+            }
+        ),
+        InstructionName::Redeem => decode!(
+            ix,
+            Redeem,
+            Redeem {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                voucher,
+                log_wrapper,
+                compression_program,
+                system_program,
+            }
+        ),
+        InstructionName::RedeemV2 => decode!(
+            ix,
+            RedeemV2,
+            RedeemV2 {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                voucher,
+                log_wrapper,
+                compression_program,
+                system_program,
+            }
+        ),
+        InstructionName::CancelRedeem => decode!(
+            ix,
+            CancelRedeem,
+            CancelRedeem {
+                tree_authority,
+                leaf_owner,
+                merkle_tree,
+                voucher,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::CancelRedeemV2 => decode!(
+            ix,
+            CancelRedeemV2,
+            CancelRedeemV2 {
+                tree_authority,
+                leaf_owner,
+                merkle_tree,
+                voucher,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::ExpireVoucher => decode!(
+            ix,
+            ExpireVoucher,
+            ExpireVoucher {
+                tree_authority,
+                leaf_owner,
+                merkle_tree,
+                voucher,
+                log_wrapper,
+                compression_program,
+            }
+        ),
+        InstructionName::DecompressV1 => decode!(
+            ix,
+            DecompressV1,
+            DecompressV1 {
+                voucher,
+                leaf_owner,
+                token_account,
+                mint,
+                mint_authority,
+                metadata,
+                master_edition,
+                system_program,
+                sysvar_rent,
+                token_metadata_program,
+                token_program,
+                associated_token_program,
+            }
+        ),
+        InstructionName::DecompressV2 => decode!(
+            ix,
+            DecompressV2,
+            DecompressV2 {
+                voucher,
+                leaf_owner,
+                token_account,
+                mint,
+                mint_authority,
+                metadata,
+                master_edition,
+                system_program,
+                sysvar_rent,
+                token_metadata_program,
+                token_program,
+                associated_token_program,
+                tree_authority,
+            }
+        ),
+        InstructionName::DecompressPrint => decode!(
+            ix,
+            DecompressPrint,
+            DecompressPrint {
+                voucher,
+                leaf_owner,
+                token_account,
+                mint,
+                mint_authority,
+                metadata,
+                edition,
+                tree_authority,
+                print_source,
+                master_mint,
+                master_metadata,
+                master_edition,
+                master_token_account,
+                edition_marker,
+                system_program,
+                sysvar_rent,
+                token_metadata_program,
+                token_program,
+                associated_token_program,
+            }
+        ),
+        InstructionName::Compress => decode!(
+            ix,
+            Compress,
+            Compress {
+                tree_authority,
+                leaf_owner,
+                leaf_delegate,
+                merkle_tree,
+                tree_delegate,
+                token_account,
+                mint,
+                metadata,
+                master_edition,
+                payer,
+                log_wrapper,
+                compression_program,
+                token_program,
+                token_metadata_program,
+                system_program,
+            }
+        ),
+        InstructionName::Unknown => return Err(ReplayError::UnknownInstruction),
+    };
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use solana_program::instruction::AccountMeta;
+
+    fn transfer() -> (accounts::Transfer, Instruction) {
+        let accounts = accounts::Transfer {
+            tree_authority: Pubkey::new_unique(),
+            leaf_owner: Pubkey::new_unique(),
+            leaf_delegate: Pubkey::new_unique(),
+            new_leaf_owner: Pubkey::new_unique(),
+            merkle_tree: Pubkey::new_unique(),
+            log_wrapper: Pubkey::new_unique(),
+            compression_program: Pubkey::new_unique(),
+        };
+        let data = instruction::Transfer {
+            root: [1; 32],
+            data_hash: [2; 32],
+            creator_hash: [3; 32],
+            nonce: 4,
+            index: 5,
+        };
+        let ix = Instruction {
+            program_id: mpl_bubblegum::id(),
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        };
+        (accounts, ix)
+    }
+
+    #[test]
+    fn decodes_accounts_args_and_proof() {
+        let (accounts, mut ix) = transfer();
+        let proof = Pubkey::new_unique();
+        ix.accounts.push(AccountMeta::new_readonly(proof, false));
+
+        match decode_bubblegum_instruction(&ix).unwrap() {
+            BubblegumInstruction::Transfer(decoded) => {
+                assert_eq!(decoded.accounts.leaf_owner, accounts.leaf_owner);
+                assert_eq!(decoded.accounts.new_leaf_owner, accounts.new_leaf_owner);
+                assert_eq!(decoded.accounts.merkle_tree, accounts.merkle_tree);
+                assert_eq!(decoded.args.root, [1; 32]);
+                assert_eq!(decoded.args.creator_hash, [3; 32]);
+                assert_eq!(decoded.args.nonce, 4);
+                assert_eq!(decoded.args.index, 5);
+                assert_eq!(decoded.remaining_accounts, vec![proof]);
+            }
+            _ => panic!("expected a transfer"),
+        }
+    }
+
+    #[test]
+    fn decodes_set_tree_delegate() {
+        let accounts = accounts::SetTreeDelegate {
+            tree_authority: Pubkey::new_unique(),
+            tree_creator: Pubkey::new_unique(),
+            new_tree_delegate: Pubkey::new_unique(),
+            merkle_tree: Pubkey::new_unique(),
+        };
+        let ix = Instruction {
+            program_id: mpl_bubblegum::id(),
+            accounts: accounts.to_account_metas(None),
+            data: instruction::SetTreeDelegate.data(),
+        };
+
+        match decode_bubblegum_instruction(&ix).unwrap() {
+            BubblegumInstruction::SetTreeDelegate(decoded) => {
+                assert_eq!(
+                    decoded.accounts.new_tree_delegate,
+                    accounts.new_tree_delegate
+                );
+                assert!(decoded.remaining_accounts.is_empty());
+            }
+            _ => panic!("expected set_tree_delegate"),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_accounts_and_other_programs() {
+        let (_, mut ix) = transfer();
+        ix.accounts.truncate(4);
+        assert!(matches!(
+            decode_bubblegum_instruction(&ix),
+            Err(ReplayError::MissingAccount(4))
+        ));
+
+        let (_, mut ix) = transfer();
+        ix.program_id = Pubkey::new_unique();
+        assert!(matches!(
+            decode_bubblegum_instruction(&ix),
+            Err(ReplayError::UnknownInstruction)
+        ));
+
+        let (_, mut ix) = transfer();
+        ix.data[0] ^= 1;
+        assert!(matches!(
+            decode_bubblegum_instruction(&ix),
+            Err(ReplayError::UnknownInstruction)
+        ));
+    }
+}
@@ -0,0 +1,26 @@
+use solana_program::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("Tree {0} was neither created in an ingested transaction nor registered")]
+    UnknownTree(Pubkey),
+
+    #[error("Asset {0} not found")]
+    AssetNotFound(Pubkey),
+
+    #[error("Not a known Bubblegum instruction")]
+    UnknownInstruction,
+
+    #[error("Instruction is missing its account at index {0}")]
+    MissingAccount(usize),
+
+    #[error("Failed to (de)serialize data: {0}")]
+    Serialization(#[from] std::io::Error),
+
+    #[cfg(feature = "sled-store")]
+    #[error("Storage error: {0}")]
+    Sled(#[from] sled::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ReplayError>;
@@ -0,0 +1,116 @@
+//! Decodes the Anchor events Bubblegum logs as `Program data: <base64>` lines.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use mpl_bubblegum::state::{
+    leaf_schema::LeafSchemaEvent, MetadataUpdateEvent, NFTDecompressionEvent, NewNFTEvent,
+};
+use solana_program::pubkey::Pubkey;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub enum BubblegumEvent {
+    LeafSchema(LeafSchemaEvent),
+    NewNft(NewNFTEvent),
+    MetadataUpdate(MetadataUpdateEvent),
+    Decompression(NFTDecompressionEvent),
+}
+
+fn decode<T: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.len() < 8 || data[..8] != T::discriminator() {
+        return None;
+    }
+    T::deserialize(&mut &data[8..]).ok()
+}
+
+impl BubblegumEvent {
+    /// Returns `None` for anything that isn't one of the events above.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        decode(data)
+            .map(BubblegumEvent::LeafSchema)
+            .or_else(|| decode(data).map(BubblegumEvent::NewNft))
+            .or_else(|| decode(data).map(BubblegumEvent::MetadataUpdate))
+            .or_else(|| decode(data).map(BubblegumEvent::Decompression))
+    }
+}
+
+/// Decodes the event in a `Program data: <base64>` log line, which must have been logged by
+/// Bubblegum. Returns `None` for any other line.
+pub fn decode_bubblegum_event(log: &str) -> Option<BubblegumEvent> {
+    let data = base64::decode(log.strip_prefix(PROGRAM_DATA)?).ok()?;
+    BubblegumEvent::decode(&data)
+}
+
+/// Groups the events Bubblegum emitted by top-level instruction, following the
+/// `Program <id> invoke [n]` and `Program <id> success` lines to tell which program is
+/// running. Top-level instructions of other programs get an empty group, so the result lines
+/// up with the instructions of the transaction.
+pub fn parse_logs(logs: &[String]) -> Vec<Vec<BubblegumEvent>> {
+    let bubblegum = mpl_bubblegum::id().to_string();
+    let mut groups = Vec::new();
+    let mut stack: Vec<&str> = Vec::new();
+
+    for log in logs {
+        if log.starts_with(PROGRAM_DATA) {
+            if stack.last() == Some(&bubblegum.as_str()) {
+                if let (Some(event), Some(group)) = (decode_bubblegum_event(log), groups.last_mut())
+                {
+                    group.push(event);
+                }
+            }
+            continue;
+        }
+
+        let words: Vec<&str> = log.split_whitespace().collect();
+        if words.len() < 3 || words[0] != "Program" || words[1].parse::<Pubkey>().is_err() {
+            continue;
+        }
+
+        match words[2] {
+            "invoke" => {
+                if stack.is_empty() {
+                    groups.push(Vec::new());
+                }
+                stack.push(words[1]);
+            }
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+    use mpl_bubblegum::state::leaf_schema::{LeafSchema, Version};
+
+    #[test]
+    fn decodes_program_data_lines() {
+        let event = LeafSchemaEvent {
+            version: Version::V1,
+            schema: LeafSchema::new_v0(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                7,
+                [1; 32],
+                [2; 32],
+            ),
+            leaf_hash: [3; 32],
+        };
+        let log = format!("{}{}", PROGRAM_DATA, base64::encode(event.data()));
+
+        match decode_bubblegum_event(&log) {
+            Some(BubblegumEvent::LeafSchema(decoded)) => {
+                assert_eq!(decoded.schema.nonce(), 7);
+                assert_eq!(decoded.leaf_hash, [3; 32]);
+            }
+            _ => panic!("expected a leaf schema event"),
+        }
+        assert!(decode_bubblegum_event("Program log: Instruction: Transfer").is_none());
+    }
+}
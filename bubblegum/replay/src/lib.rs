@@ -0,0 +1,26 @@
+//! Replays Bubblegum transactions into off-chain copies of their Merkle trees, so that the
+//! current state and proofs of compressed assets can be served without an external indexer.
+//!
+//! Successful transactions are fed to a [`Replayer`] together with their program logs, from
+//! which the Anchor events emitted by Bubblegum are decoded.
+//!
+//! Indexers that keep their own state can use [`decode_bubblegum_instruction`] and
+//! [`decode_bubblegum_event`] on their own.
+
+pub mod asset;
+pub mod decoder;
+pub mod error;
+pub mod events;
+pub mod merkle;
+pub mod replayer;
+pub mod store;
+
+pub use asset::{Asset, AssetProof, AssetState};
+pub use decoder::{decode_bubblegum_instruction, BubblegumInstruction, Decoded};
+pub use error::ReplayError;
+pub use events::{decode_bubblegum_event, BubblegumEvent};
+pub use replayer::Replayer;
+pub use store::{MemoryStore, Store};
+
+#[cfg(feature = "sled-store")]
+pub use store::SledStore;
@@ -0,0 +1,128 @@
+//! A plain Merkle tree with the same hashing as spl-account-compression. Only the final state
+//! is kept, so proofs are always against the latest root.
+
+use crate::{error::Result, store::Store};
+use solana_program::{keccak, pubkey::Pubkey};
+
+pub type Node = [u8; 32];
+
+pub const EMPTY_LEAF: Node = [0; 32];
+
+fn hash_pair(left: &Node, right: &Node) -> Node {
+    keccak::hashv(&[left, right]).to_bytes()
+}
+
+/// Root of an empty subtree of the given height.
+pub fn empty_node(level: u32) -> Node {
+    (0..level).fold(EMPTY_LEAF, |node, _| hash_pair(&node, &node))
+}
+
+fn node_or_empty<S: Store>(store: &S, tree: &Pubkey, level: u32, index: u64) -> Result<Node> {
+    Ok(store
+        .node(tree, level, index)?
+        .unwrap_or_else(|| empty_node(level)))
+}
+
+/// Writes `leaf` at `index` and rehashes its path up to the root.
+pub fn set_leaf<S: Store>(
+    store: &mut S,
+    tree: &Pubkey,
+    depth: u32,
+    index: u32,
+    leaf: Node,
+) -> Result<()> {
+    let mut index = index as u64;
+    let mut node = leaf;
+    store.put_node(tree, 0, index, node)?;
+
+    for level in 0..depth {
+        let sibling = node_or_empty(store, tree, level, index ^ 1)?;
+        node = if index & 1 == 0 {
+            hash_pair(&node, &sibling)
+        } else {
+            hash_pair(&sibling, &node)
+        };
+        index >>= 1;
+        store.put_node(tree, level + 1, index, node)?;
+    }
+
+    Ok(())
+}
+
+pub fn root<S: Store>(store: &S, tree: &Pubkey, depth: u32) -> Result<Node> {
+    node_or_empty(store, tree, depth, 0)
+}
+
+pub fn leaf<S: Store>(store: &S, tree: &Pubkey, index: u32) -> Result<Node> {
+    node_or_empty(store, tree, 0, index as u64)
+}
+
+/// Sibling nodes from the leaf level up to, but excluding, the root.
+pub fn proof<S: Store>(store: &S, tree: &Pubkey, depth: u32, index: u32) -> Result<Vec<Node>> {
+    let mut index = index as u64;
+    let mut proof = Vec::with_capacity(depth as usize);
+
+    for level in 0..depth {
+        proof.push(node_or_empty(store, tree, level, index ^ 1)?);
+        index >>= 1;
+    }
+
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::MemoryStore;
+
+    fn recompute(leaf: Node, proof: &[Node], index: u32) -> Node {
+        proof
+            .iter()
+            .enumerate()
+            .fold(leaf, |node, (level, sibling)| {
+                if (index >> level) & 1 == 0 {
+                    hash_pair(&node, sibling)
+                } else {
+                    hash_pair(sibling, &node)
+                }
+            })
+    }
+
+    #[test]
+    fn empty_tree_has_empty_root() {
+        let store = MemoryStore::default();
+        let tree = Pubkey::new_unique();
+        assert_eq!(root(&store, &tree, 14).unwrap(), empty_node(14));
+    }
+
+    #[test]
+    fn proofs_recompute_root() {
+        let mut store = MemoryStore::default();
+        let tree = Pubkey::new_unique();
+        let depth = 5;
+
+        for index in [0, 1, 7, 31] {
+            set_leaf(&mut store, &tree, depth, index, [index as u8 + 1; 32]).unwrap();
+        }
+
+        let expected_root = root(&store, &tree, depth).unwrap();
+        for index in [0, 1, 2, 7, 31] {
+            let leaf = leaf(&store, &tree, index).unwrap();
+            let proof = proof(&store, &tree, depth, index).unwrap();
+            assert_eq!(proof.len(), depth as usize);
+            assert_eq!(recompute(leaf, &proof, index), expected_root);
+        }
+    }
+
+    #[test]
+    fn clearing_all_leaves_restores_empty_root() {
+        let mut store = MemoryStore::default();
+        let tree = Pubkey::new_unique();
+
+        set_leaf(&mut store, &tree, 3, 2, [9; 32]).unwrap();
+        assert_ne!(root(&store, &tree, 3).unwrap(), empty_node(3));
+
+        set_leaf(&mut store, &tree, 3, 2, EMPTY_LEAF).unwrap();
+        assert_eq!(root(&store, &tree, 3).unwrap(), empty_node(3));
+    }
+}
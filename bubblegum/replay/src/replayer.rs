@@ -0,0 +1,397 @@
+use crate::{
+    asset::{Asset, AssetProof, AssetState},
+    error::{ReplayError, Result},
+    events::{parse_logs, BubblegumEvent},
+    merkle::{self, Node, EMPTY_LEAF},
+    store::{MemoryStore, Store},
+};
+use anchor_lang::AnchorDeserialize;
+use mpl_bubblegum::{
+    get_instruction_type,
+    state::{
+        leaf_schema::{LeafSchema, LeafSchemaEvent},
+        metaplex_adapter::{Collection, MetadataArgs, UseMethod},
+    },
+    utils::get_asset_id,
+    InstructionName,
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+/// Mints into a full tree roll over into one of its successors, which is then among the
+/// remaining accounts. The tree a leaf went into is the one its asset ID derives from.
+fn minted_tree(
+    name: &InstructionName,
+    tree: Pubkey,
+    instruction: &Instruction,
+    schema: &LeafSchema,
+) -> Pubkey {
+    if !matches!(
+        name,
+        InstructionName::MintV1 | InstructionName::MintV2 | InstructionName::MintBatchV1
+    ) {
+        return tree;
+    }
+    let (LeafSchema::V1 { id, nonce, .. } | LeafSchema::V2 { id, nonce, .. }) = schema;
+    if get_asset_id(&tree, *nonce) == *id {
+        return tree;
+    }
+    instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .find(|key| get_asset_id(key, *nonce) == *id)
+        .unwrap_or(tree)
+}
+
+/// Position of the merkle tree in the accounts of each instruction that touches one.
+fn tree_account_index(instruction: &InstructionName) -> Option<usize> {
+    match instruction {
+        InstructionName::CreateTree | InstructionName::VerifyCreatorBatch => Some(1),
+        InstructionName::CancelRedeem
+        | InstructionName::CancelRedeemV2
+        | InstructionName::ExpireVoucher
+        | InstructionName::Delist => Some(2),
+        InstructionName::MintV1
+        | InstructionName::MintV2
+        | InstructionName::MintBatchV1
+        | InstructionName::Redeem
+        | InstructionName::RedeemV2
+        | InstructionName::Burn
+        | InstructionName::BurnV2
+        | InstructionName::BurnWithMetadata
+        | InstructionName::Compress
+        | InstructionName::VerifyCreator
+        | InstructionName::UnverifyCreator
+        | InstructionName::VerifyCollection
+        | InstructionName::UnverifyCollection
+        | InstructionName::SetAndVerifyCollection
+        | InstructionName::Freeze
+        | InstructionName::Thaw
+        | InstructionName::Utilize
+        | InstructionName::List
+        | InstructionName::Buy => Some(3),
+        InstructionName::Transfer
+        | InstructionName::TransferV2
+        | InstructionName::Delegate
+        | InstructionName::DelegateV2 => Some(4),
+        InstructionName::UpdateMetadata => Some(7),
+        InstructionName::SetTreeDelegate
+        | InstructionName::DecompressV1
+        | InstructionName::DecompressV2
+        | InstructionName::DecompressPrint
+        | InstructionName::AddMinter
+        | InstructionName::RemoveMinter
+        | InstructionName::SetTreePublic
+        | InstructionName::SetTreeClosable
+        | InstructionName::SetSuccessorTree
+        | InstructionName::ReconcileCollectionSize
+        | InstructionName::CloseTree
+        | InstructionName::SetPrintSource
+        | InstructionName::RemovePrintSource
+        | InstructionName::Unknown => None,
+    }
+}
+
+/// Rebuilds Bubblegum trees and assets from the transactions that changed them. Transactions
+/// must be ingested in the order they landed, and only if they succeeded.
+pub struct Replayer<S: Store = MemoryStore> {
+    store: S,
+}
+
+impl Replayer<MemoryStore> {
+    pub fn new() -> Self {
+        Self::with_store(MemoryStore::default())
+    }
+}
+
+impl Default for Replayer<MemoryStore> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Store> Replayer<S> {
+    pub fn with_store(store: S) -> Self {
+        Replayer { store }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Starts tracking a tree. Trees are registered automatically when their `create_tree`
+    /// transaction is ingested; this is for trees that were created before replaying started
+    /// from their first mint.
+    pub fn register_tree(&mut self, tree: &Pubkey, max_depth: u32) -> Result<()> {
+        self.store.put_tree_depth(tree, max_depth)
+    }
+
+    /// Ingests a successful transaction given its top-level instructions and its log messages.
+    pub fn ingest_transaction(
+        &mut self,
+        instructions: &[Instruction],
+        logs: &[String],
+    ) -> Result<()> {
+        for (instruction, events) in instructions.iter().zip(parse_logs(logs)) {
+            if instruction.program_id == mpl_bubblegum::id() {
+                self.ingest_instruction(instruction, events)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies a single Bubblegum instruction along with the events it emitted.
+    pub fn ingest_instruction(
+        &mut self,
+        instruction: &Instruction,
+        events: Vec<BubblegumEvent>,
+    ) -> Result<()> {
+        if instruction.data.len() < 8 {
+            return Ok(());
+        }
+        let name = get_instruction_type(&instruction.data);
+        let args = &mut &instruction.data[8..];
+
+        let tree = match tree_account_index(&name) {
+            Some(index) => Some(
+                instruction
+                    .accounts
+                    .get(index)
+                    .ok_or(ReplayError::MissingAccount(index))?
+                    .pubkey,
+            ),
+            None => None,
+        };
+
+        if let (InstructionName::CreateTree, Some(tree)) = (&name, tree) {
+            let args = mpl_bubblegum::instruction::CreateTree::deserialize(args)?;
+            return self.register_tree(&tree, args.max_depth);
+        }
+
+        // Metadata from a `NewNFTEvent` belongs to the leaf event that follows it.
+        let mut pending_metadata = None;
+        let mut batch_metadata = verified_batch_metadata(&name, instruction, args)?.into_iter();
+
+        for event in events {
+            match event {
+                BubblegumEvent::NewNft(event) => pending_metadata = Some(event.metadata),
+                BubblegumEvent::MetadataUpdate(event) => pending_metadata = Some(event.metadata),
+                BubblegumEvent::Decompression(event) => {
+                    let mut asset = self.asset_or_err(&event.id)?;
+                    asset.state = AssetState::Decompressed;
+                    self.store.put_asset(&asset)?;
+                }
+                BubblegumEvent::LeafSchema(event) => {
+                    if let Some(tree) = tree {
+                        let tree = minted_tree(&name, tree, instruction, &event.schema);
+                        let metadata =
+                            match pending_metadata.take().or_else(|| batch_metadata.next()) {
+                                Some(metadata) => Some(metadata),
+                                None => verified_metadata(&name, instruction, args)?,
+                            };
+                        self.apply_leaf(&name, &tree, event, metadata)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply_leaf(
+        &mut self,
+        name: &InstructionName,
+        tree: &Pubkey,
+        event: LeafSchemaEvent,
+        metadata: Option<MetadataArgs>,
+    ) -> Result<()> {
+        let depth = self
+            .store
+            .tree_depth(tree)?
+            .ok_or(ReplayError::UnknownTree(*tree))?;
+
+        // Burn and redeem log the leaf they removed, everything else logs the new leaf. So does
+        // utilize when it uses up a leaf that burns.
+        let used_up = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.uses.as_ref())
+            .map_or(false, |uses| {
+                uses.use_method == UseMethod::Burn && uses.remaining == 0
+            });
+        let (state, leaf) = match name {
+            InstructionName::Burn | InstructionName::BurnV2 | InstructionName::BurnWithMetadata => {
+                (AssetState::Burned, EMPTY_LEAF)
+            }
+            InstructionName::Utilize if used_up => (AssetState::Burned, EMPTY_LEAF),
+            InstructionName::Redeem | InstructionName::RedeemV2 => {
+                (AssetState::Redeemed, EMPTY_LEAF)
+            }
+            _ => (AssetState::Compressed, event.leaf_hash),
+        };
+
+        let flags = event.schema.flags();
+        let (LeafSchema::V1 {
+            id,
+            owner,
+            delegate,
+            nonce,
+            data_hash,
+            creator_hash,
+        }
+        | LeafSchema::V2 {
+            id,
+            owner,
+            delegate,
+            nonce,
+            data_hash,
+            creator_hash,
+            ..
+        }) = event.schema;
+
+        let previous_metadata = self.store.asset(&id)?.and_then(|asset| asset.metadata);
+        let asset = Asset {
+            id,
+            tree: *tree,
+            nonce,
+            owner,
+            delegate,
+            data_hash,
+            creator_hash,
+            flags,
+            leaf_hash: event.leaf_hash,
+            metadata: metadata.or(previous_metadata),
+            state,
+        };
+
+        merkle::set_leaf(&mut self.store, tree, depth, asset.index(), leaf)?;
+        self.store.put_asset(&asset)
+    }
+
+    fn asset_or_err(&self, id: &Pubkey) -> Result<Asset> {
+        self.store.asset(id)?.ok_or(ReplayError::AssetNotFound(*id))
+    }
+
+    pub fn get_asset(&self, id: &Pubkey) -> Result<Option<Asset>> {
+        self.store.asset(id)
+    }
+
+    /// Proof of the current leaf of a compressed asset against the latest replayed root.
+    pub fn get_proof(&self, id: &Pubkey) -> Result<AssetProof> {
+        let asset = self.asset_or_err(id)?;
+        let depth = self
+            .store
+            .tree_depth(&asset.tree)?
+            .ok_or(ReplayError::UnknownTree(asset.tree))?;
+        let index = asset.index();
+
+        Ok(AssetProof {
+            tree: asset.tree,
+            root: merkle::root(&self.store, &asset.tree, depth)?,
+            leaf: merkle::leaf(&self.store, &asset.tree, index)?,
+            index,
+            proof: merkle::proof(&self.store, &asset.tree, depth, index)?,
+        })
+    }
+
+    /// Assets held by `owner` that are still leaves in their tree.
+    pub fn get_assets_by_owner(&self, owner: &Pubkey) -> Result<Vec<Asset>> {
+        Ok(self
+            .store
+            .assets_by_owner(owner)?
+            .into_iter()
+            .filter(|asset| asset.state == AssetState::Compressed)
+            .collect())
+    }
+
+    pub fn root(&self, tree: &Pubkey) -> Result<Node> {
+        let depth = self
+            .store
+            .tree_depth(tree)?
+            .ok_or(ReplayError::UnknownTree(*tree))?;
+        merkle::root(&self.store, tree, depth)
+    }
+}
+
+/// Creator and collection verification don't log the metadata they produce, so it is rebuilt
+/// from the instruction arguments.
+fn verified_metadata(
+    name: &InstructionName,
+    instruction: &Instruction,
+    args: &mut &[u8],
+) -> Result<Option<MetadataArgs>> {
+    use mpl_bubblegum::instruction as ix;
+
+    let metadata = match name {
+        InstructionName::VerifyCreator | InstructionName::UnverifyCreator => {
+            let (mut message, verify) = if let InstructionName::VerifyCreator = name {
+                (ix::VerifyCreator::deserialize(args)?.message, true)
+            } else {
+                (ix::UnverifyCreator::deserialize(args)?.message, false)
+            };
+            let creator = instruction
+                .accounts
+                .get(5)
+                .ok_or(ReplayError::MissingAccount(5))?
+                .pubkey;
+            for c in message.creators.iter_mut() {
+                if c.address == creator {
+                    c.verified = verify;
+                }
+            }
+            message
+        }
+        InstructionName::VerifyCollection | InstructionName::UnverifyCollection => {
+            let (mut message, verify) = if let InstructionName::VerifyCollection = name {
+                (ix::VerifyCollection::deserialize(args)?.message, true)
+            } else {
+                (ix::UnverifyCollection::deserialize(args)?.message, false)
+            };
+            if let Some(collection) = message.collection.as_mut() {
+                collection.verified = verify;
+            }
+            message
+        }
+        InstructionName::SetAndVerifyCollection => {
+            let args = ix::SetAndVerifyCollection::deserialize(args)?;
+            let mut message = args.message;
+            message.collection = Some(Collection {
+                verified: true,
+                key: Pubkey::new_from_array(args.collection),
+            });
+            message
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(metadata))
+}
+
+/// `verify_creator_batch` logs one leaf for each of its leaf arguments, in the same order.
+fn verified_batch_metadata(
+    name: &InstructionName,
+    instruction: &Instruction,
+    args: &mut &[u8],
+) -> Result<Vec<MetadataArgs>> {
+    if !matches!(name, InstructionName::VerifyCreatorBatch) {
+        return Ok(Vec::new());
+    }
+
+    let creator = instruction
+        .accounts
+        .get(3)
+        .ok_or(ReplayError::MissingAccount(3))?
+        .pubkey;
+    let leaves = mpl_bubblegum::instruction::VerifyCreatorBatch::deserialize(args)?.leaves;
+    Ok(leaves
+        .into_iter()
+        .map(|leaf| {
+            let mut message = leaf.metadata;
+            for c in message.creators.iter_mut() {
+                if c.address == creator {
+                    c.verified = true;
+                }
+            }
+            message
+        })
+        .collect())
+}
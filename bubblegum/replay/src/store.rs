@@ -0,0 +1,178 @@
+use crate::{asset::Asset, error::Result, merkle::Node};
+use solana_program::pubkey::Pubkey;
+use std::collections::{BTreeSet, HashMap};
+
+/// Backing storage for replayed trees and assets.
+pub trait Store {
+    fn tree_depth(&self, tree: &Pubkey) -> Result<Option<u32>>;
+
+    fn put_tree_depth(&mut self, tree: &Pubkey, depth: u32) -> Result<()>;
+
+    fn node(&self, tree: &Pubkey, level: u32, index: u64) -> Result<Option<Node>>;
+
+    fn put_node(&mut self, tree: &Pubkey, level: u32, index: u64, node: Node) -> Result<()>;
+
+    fn asset(&self, id: &Pubkey) -> Result<Option<Asset>>;
+
+    /// Inserts or replaces the asset, keeping the owner index in sync.
+    fn put_asset(&mut self, asset: &Asset) -> Result<()>;
+
+    /// Assets last seen with `owner`, in whatever state they are.
+    fn assets_by_owner(&self, owner: &Pubkey) -> Result<Vec<Asset>>;
+}
+
+#[derive(Default)]
+pub struct MemoryStore {
+    depths: HashMap<Pubkey, u32>,
+    nodes: HashMap<(Pubkey, u32, u64), Node>,
+    assets: HashMap<Pubkey, Asset>,
+    owners: HashMap<Pubkey, BTreeSet<Pubkey>>,
+}
+
+impl Store for MemoryStore {
+    fn tree_depth(&self, tree: &Pubkey) -> Result<Option<u32>> {
+        Ok(self.depths.get(tree).copied())
+    }
+
+    fn put_tree_depth(&mut self, tree: &Pubkey, depth: u32) -> Result<()> {
+        self.depths.insert(*tree, depth);
+        Ok(())
+    }
+
+    fn node(&self, tree: &Pubkey, level: u32, index: u64) -> Result<Option<Node>> {
+        Ok(self.nodes.get(&(*tree, level, index)).copied())
+    }
+
+    fn put_node(&mut self, tree: &Pubkey, level: u32, index: u64, node: Node) -> Result<()> {
+        self.nodes.insert((*tree, level, index), node);
+        Ok(())
+    }
+
+    fn asset(&self, id: &Pubkey) -> Result<Option<Asset>> {
+        Ok(self.assets.get(id).cloned())
+    }
+
+    fn put_asset(&mut self, asset: &Asset) -> Result<()> {
+        if let Some(previous) = self.assets.get(&asset.id) {
+            if let Some(ids) = self.owners.get_mut(&previous.owner) {
+                ids.remove(&asset.id);
+            }
+        }
+        self.owners.entry(asset.owner).or_default().insert(asset.id);
+        self.assets.insert(asset.id, asset.clone());
+        Ok(())
+    }
+
+    fn assets_by_owner(&self, owner: &Pubkey) -> Result<Vec<Asset>> {
+        Ok(self
+            .owners
+            .get(owner)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.assets.get(id).cloned())
+            .collect())
+    }
+}
+
+#[cfg(feature = "sled-store")]
+pub use self::sled_store::SledStore;
+
+#[cfg(feature = "sled-store")]
+mod sled_store {
+    use super::*;
+    use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+
+    /// Keeps everything in a sled database, so replayed state survives restarts.
+    pub struct SledStore {
+        depths: sled::Tree,
+        nodes: sled::Tree,
+        assets: sled::Tree,
+        owners: sled::Tree,
+    }
+
+    fn node_key(tree: &Pubkey, level: u32, index: u64) -> Vec<u8> {
+        [
+            tree.as_ref(),
+            level.to_be_bytes().as_ref(),
+            index.to_be_bytes().as_ref(),
+        ]
+        .concat()
+    }
+
+    fn owner_key(owner: &Pubkey, id: &Pubkey) -> Vec<u8> {
+        [owner.as_ref(), id.as_ref()].concat()
+    }
+
+    fn to_node(bytes: &[u8]) -> Node {
+        let mut node = [0; 32];
+        node.copy_from_slice(bytes);
+        node
+    }
+
+    impl SledStore {
+        pub fn new(db: &sled::Db) -> Result<Self> {
+            Ok(SledStore {
+                depths: db.open_tree("depths")?,
+                nodes: db.open_tree("nodes")?,
+                assets: db.open_tree("assets")?,
+                owners: db.open_tree("owners")?,
+            })
+        }
+    }
+
+    impl Store for SledStore {
+        fn tree_depth(&self, tree: &Pubkey) -> Result<Option<u32>> {
+            Ok(self.depths.get(tree)?.map(|bytes| {
+                let mut depth = [0; 4];
+                depth.copy_from_slice(&bytes);
+                u32::from_be_bytes(depth)
+            }))
+        }
+
+        fn put_tree_depth(&mut self, tree: &Pubkey, depth: u32) -> Result<()> {
+            self.depths.insert(tree, &depth.to_be_bytes())?;
+            Ok(())
+        }
+
+        fn node(&self, tree: &Pubkey, level: u32, index: u64) -> Result<Option<Node>> {
+            Ok(self
+                .nodes
+                .get(node_key(tree, level, index))?
+                .map(|bytes| to_node(&bytes)))
+        }
+
+        fn put_node(&mut self, tree: &Pubkey, level: u32, index: u64, node: Node) -> Result<()> {
+            self.nodes.insert(node_key(tree, level, index), &node)?;
+            Ok(())
+        }
+
+        fn asset(&self, id: &Pubkey) -> Result<Option<Asset>> {
+            self.assets
+                .get(id)?
+                .map(|bytes| Asset::try_from_slice(&bytes).map_err(Into::into))
+                .transpose()
+        }
+
+        fn put_asset(&mut self, asset: &Asset) -> Result<()> {
+            if let Some(previous) = self.asset(&asset.id)? {
+                self.owners.remove(owner_key(&previous.owner, &asset.id))?;
+            }
+            self.owners
+                .insert(owner_key(&asset.owner, &asset.id), &[])?;
+            self.assets.insert(asset.id, asset.try_to_vec()?)?;
+            Ok(())
+        }
+
+        fn assets_by_owner(&self, owner: &Pubkey) -> Result<Vec<Asset>> {
+            self.owners
+                .scan_prefix(owner)
+                .map(|entry| {
+                    let (key, _) = entry?;
+                    let id = Pubkey::new(&key[32..]);
+                    self.asset(&id)?
+                        .ok_or(crate::error::ReplayError::AssetNotFound(id))
+                })
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,286 @@
+#[path = "../../program/tests/utils/mod.rs"]
+pub mod utils;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use mpl_bubblegum::utils::{get_asset_id, get_successor_tree_accounts};
+use mpl_bubblegum_replay::{AssetState, Replayer, Store};
+use solana_program_test::tokio;
+use solana_sdk::signature::{Keypair, Signer};
+
+use utils::{
+    context::{BubblegumTestContext, DEFAULT_LAMPORTS_FUND_AMOUNT},
+    LeafArgs, Tree, TxBuilder,
+};
+
+const MAX_DEPTH: usize = 14;
+const MAX_BUF_SIZE: usize = 64;
+
+// Runs the transaction and feeds it to the replayer, the same way an indexer would.
+async fn execute_and_ingest<T, U, S>(replayer: &mut Replayer<S>, tx: &TxBuilder<T, U>)
+where
+    T: ToAccountMetas,
+    U: InstructionData,
+    S: Store,
+{
+    let logs = tx.execute_with_logs().await.unwrap();
+    replayer
+        .ingest_transaction(&[tx.instruction()], &logs)
+        .unwrap();
+}
+
+async fn create_tree<S: Store>(
+    context: &BubblegumTestContext,
+    replayer: &mut Replayer<S>,
+) -> Tree<MAX_DEPTH, MAX_BUF_SIZE> {
+    let payer = context.payer();
+    let tree = Tree::<MAX_DEPTH, MAX_BUF_SIZE>::with_creator(&payer, context.client());
+    tree.alloc(&payer).await.unwrap();
+    execute_and_ingest(replayer, &tree.create_tree_tx(&payer)).await;
+    tree
+}
+
+async fn mint_and_ingest<S: Store>(
+    context: &BubblegumTestContext,
+    replayer: &mut Replayer<S>,
+    tree: &Tree<MAX_DEPTH, MAX_BUF_SIZE>,
+    count: u64,
+) -> Vec<LeafArgs> {
+    let mut leaves = Vec::new();
+    for nonce in 0..count {
+        let mut leaf = LeafArgs::new(
+            &context.payer(),
+            context.default_metadata_args(format!("test {}", nonce), "tst"),
+        );
+        leaf.nonce = nonce;
+        leaf.index = nonce as u32;
+
+        execute_and_ingest(replayer, &tree.mint_v1_tx(&tree.tree_delegate, &leaf)).await;
+        assert_eq!(
+            replayer.root(&tree.tree_pubkey()).unwrap(),
+            tree.decode_root().await.unwrap()
+        );
+        leaves.push(leaf);
+    }
+    leaves
+}
+
+#[tokio::test]
+async fn test_replay_matches_on_chain_tree() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut replayer = Replayer::new();
+    let tree = create_tree(&context, &mut replayer).await;
+    let tree_id = tree.tree_pubkey();
+
+    let leaves = mint_and_ingest(&context, &mut replayer, &tree, 4).await;
+
+    let asset = replayer
+        .get_asset(&get_asset_id(&tree_id, 0))
+        .unwrap()
+        .unwrap();
+    assert_eq!(asset.owner, context.payer().pubkey());
+    assert_eq!(asset.state, AssetState::Compressed);
+    assert!(asset.metadata.unwrap() == leaves[0].metadata);
+
+    // Proofs from the replayer are accepted on chain for leaves other than the latest one.
+    let new_owner = Keypair::new();
+    let proof = replayer.get_proof(&get_asset_id(&tree_id, 0)).unwrap();
+    assert_eq!(proof.root, tree.decode_root().await.unwrap());
+    let mut tx = tree
+        .transfer_tx(&leaves[0], new_owner.pubkey())
+        .await
+        .unwrap();
+    tx.set_additional_accounts(&proof.proof_pubkeys());
+    execute_and_ingest(&mut replayer, &tx).await;
+    assert_eq!(
+        replayer.root(&tree_id).unwrap(),
+        tree.decode_root().await.unwrap()
+    );
+
+    let proof = replayer.get_proof(&get_asset_id(&tree_id, 1)).unwrap();
+    let mut tx = tree.burn_tx(&leaves[1]).await.unwrap();
+    tx.set_additional_accounts(&proof.proof_pubkeys());
+    execute_and_ingest(&mut replayer, &tx).await;
+    assert_eq!(
+        replayer.root(&tree_id).unwrap(),
+        tree.decode_root().await.unwrap()
+    );
+
+    let burned = replayer
+        .get_asset(&get_asset_id(&tree_id, 1))
+        .unwrap()
+        .unwrap();
+    assert_eq!(burned.state, AssetState::Burned);
+
+    let owned = replayer
+        .get_assets_by_owner(&new_owner.pubkey())
+        .unwrap()
+        .into_iter()
+        .map(|asset| asset.nonce)
+        .collect::<Vec<_>>();
+    assert_eq!(owned, vec![0]);
+
+    let mut owned = replayer
+        .get_assets_by_owner(&context.payer().pubkey())
+        .unwrap()
+        .into_iter()
+        .map(|asset| asset.nonce)
+        .collect::<Vec<_>>();
+    owned.sort_unstable();
+    assert_eq!(owned, vec![2, 3]);
+}
+
+#[tokio::test]
+async fn test_replay_batch_mint_and_creator_verification() {
+    let mut context = BubblegumTestContext::new().await.unwrap();
+    let mut replayer = Replayer::new();
+    let tree = create_tree(&context, &mut replayer).await;
+    let tree_id = tree.tree_pubkey();
+
+    let owners = [Keypair::new(), Keypair::new()];
+    for owner in owners.iter() {
+        context
+            .fund_account(owner.pubkey(), DEFAULT_LAMPORTS_FUND_AMOUNT)
+            .await
+            .unwrap();
+    }
+
+    let mut leaves = owners
+        .iter()
+        .map(|owner| {
+            let mut metadata = context.default_metadata_args("a", "b");
+            metadata.uri = "c".to_owned();
+            metadata.creators.truncate(1);
+            metadata.creators[0].share = 100;
+            LeafArgs::new(owner, metadata)
+        })
+        .collect::<Vec<_>>();
+    for (nonce, leaf) in leaves.iter_mut().enumerate() {
+        leaf.nonce = nonce as u64;
+        leaf.index = nonce as u32;
+    }
+
+    execute_and_ingest(
+        &mut replayer,
+        &tree.mint_batch_v1_tx(&tree.tree_delegate, &leaves),
+    )
+    .await;
+    assert_eq!(
+        replayer.root(&tree_id).unwrap(),
+        tree.decode_root().await.unwrap()
+    );
+    for (leaf, owner) in leaves.iter().zip(owners.iter()) {
+        let assets = replayer.get_assets_by_owner(&owner.pubkey()).unwrap();
+        assert_eq!(assets.len(), 1);
+        assert_eq!(assets[0].nonce, leaf.nonce);
+    }
+
+    // The creator hash changes without the metadata being logged.
+    let creator = &context.default_creators[0];
+    let last = leaves.last().unwrap();
+    execute_and_ingest(
+        &mut replayer,
+        &tree.verify_creator_tx(last, creator).await.unwrap(),
+    )
+    .await;
+    assert_eq!(
+        replayer.root(&tree_id).unwrap(),
+        tree.decode_root().await.unwrap()
+    );
+
+    let asset = replayer
+        .get_asset(&get_asset_id(&tree_id, last.nonce))
+        .unwrap()
+        .unwrap();
+    let creators = asset.metadata.unwrap().creators;
+    assert_eq!(creators[0].address, creator.pubkey());
+    assert!(creators[0].verified);
+}
+
+#[tokio::test]
+async fn test_replay_mint_rollover() {
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut replayer = Replayer::new();
+    let payer = context.payer();
+
+    // Depth 3 leaves room for 8 leaves.
+    let mut trees = Vec::new();
+    for _ in 0..2 {
+        let tree = Tree::<3, 8>::with_creator(&payer, context.client());
+        tree.alloc(&payer).await.unwrap();
+        execute_and_ingest(&mut replayer, &tree.create_tree_tx(&payer)).await;
+        trees.push(tree);
+    }
+    let (tree, successor) = (&trees[0], &trees[1]);
+    execute_and_ingest(
+        &mut replayer,
+        &tree.set_successor_tree_tx(successor.tree_pubkey()),
+    )
+    .await;
+
+    let leaves = (0..9)
+        .map(|_| LeafArgs::new(&payer, context.small_metadata_args()))
+        .collect::<Vec<_>>();
+    execute_and_ingest(
+        &mut replayer,
+        &tree.mint_batch_v1_tx(&tree.tree_delegate, &leaves[..8]),
+    )
+    .await;
+
+    // The ninth mint goes into the successor, although the instruction names the first tree.
+    let chain = vec![
+        (tree.tree_pubkey(), tree.read_tree_config().await.unwrap()),
+        (
+            successor.tree_pubkey(),
+            successor.read_tree_config().await.unwrap(),
+        ),
+    ];
+    let mut tx = tree.mint_v1_tx(&tree.tree_delegate, &leaves[8]);
+    tx.set_additional_account_metas(&get_successor_tree_accounts(&chain, 1));
+    execute_and_ingest(&mut replayer, &tx).await;
+
+    for tree in trees.iter() {
+        assert_eq!(
+            replayer.root(&tree.tree_pubkey()).unwrap(),
+            tree.decode_root().await.unwrap()
+        );
+    }
+    let asset = replayer
+        .get_asset(&get_asset_id(&successor.tree_pubkey(), 0))
+        .unwrap()
+        .unwrap();
+    assert_eq!(asset.tree, successor.tree_pubkey());
+}
+
+#[cfg(feature = "sled-store")]
+#[tokio::test]
+async fn test_replay_into_sled() {
+    use mpl_bubblegum_replay::SledStore;
+
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    let context = BubblegumTestContext::new().await.unwrap();
+    let mut replayer = Replayer::with_store(SledStore::new(&db).unwrap());
+    let tree = create_tree(&context, &mut replayer).await;
+    let tree_id = tree.tree_pubkey();
+
+    let leaves = mint_and_ingest(&context, &mut replayer, &tree, 3).await;
+
+    // Moving the first leaf needs a full proof, which comes from the sled-backed tree.
+    let proof = replayer.get_proof(&get_asset_id(&tree_id, 0)).unwrap();
+    let mut tx = tree
+        .transfer_tx(&leaves[0], Keypair::new().pubkey())
+        .await
+        .unwrap();
+    tx.set_additional_accounts(&proof.proof_pubkeys());
+    execute_and_ingest(&mut replayer, &tx).await;
+    assert_eq!(
+        replayer.root(&tree_id).unwrap(),
+        tree.decode_root().await.unwrap()
+    );
+    assert_eq!(
+        replayer
+            .get_assets_by_owner(&context.payer().pubkey())
+            .unwrap()
+            .len(),
+        2
+    );
+}
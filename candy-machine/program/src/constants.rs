@@ -14,6 +14,15 @@ pub const MAX_FREEZE_TIME: i64 = 60 * 60 * 24 * 31; // 1 month
 pub const COLLECTIONS_FEATURE_INDEX: usize = 0;
 pub const FREEZE_FEATURE_INDEX: usize = 1;
 pub const FREEZE_LOCK_FEATURE_INDEX: usize = 2;
+pub const COMMIT_REVEAL_FEATURE_INDEX: usize = 3;
+pub const MINT_PHASES_FEATURE_INDEX: usize = 4;
+pub const MINT_LIMIT_FEATURE_INDEX: usize = 5;
+
+pub const MAX_MINT_PHASES: usize = 5;
+
+// Mint requests have to be fulfilled while the slot hash after them is still in the SlotHashes
+// sysvar, which holds the last 512 slots.
+pub const MINT_REQUEST_EXPIRY_SLOTS: u64 = 300;
 
 pub const COLLECTION_PDA_SIZE: usize = 8 + 32 + 32;
 
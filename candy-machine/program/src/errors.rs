@@ -108,4 +108,40 @@ pub enum CandyError {
     NoUnlockWithNFTsStillFrozen,
     #[msg("Setting a sized collection requires the collection metadata to be mutable.")]
     SizedCollectionMetadataMustBeMutable,
+    #[msg("Candy machine uses commit-reveal minting, mint through request_mint and fulfill_mint")]
+    MintRequestRequired,
+    #[msg("Commit-reveal minting is not enabled for this candy machine")]
+    CommitRevealNotEnabled,
+    #[msg("Commit-reveal minting doesn't support gatekeepers, whitelists, SPL token payments, freeze, mint phases or mint limits")]
+    CommitRevealUnsupportedSettings,
+    #[msg("Mint request can't be fulfilled until a later slot hash is available")]
+    MintRequestNotRevealable,
+    #[msg("Mint request has expired")]
+    MintRequestExpired,
+    #[msg("Mint request has not expired")]
+    MintRequestNotExpired,
+    #[msg("Mint phases must be sorted by start date, end after they start and not overlap")]
+    InvalidMintPhases,
+    #[msg("Mint phases replace the whitelist and SPL token settings and don't support freeze or commit-reveal")]
+    MintPhasesUnsupportedSettings,
+    #[msg("Mint phases PDA does not belong to this candy machine")]
+    MintPhasesPDAMismatch,
+    #[msg("The current mint phase has reached its supply cap")]
+    MintPhaseSoldOut,
+    #[msg("Payment destination does not match the mint phase")]
+    IncorrectPaymentDestination,
+    #[msg("Cannot withdraw while mint phases are set, remove them first")]
+    NoWithdrawWithMintPhases,
+    #[msg("Mint limit PDA does not belong to this candy machine")]
+    MintLimitPDAMismatch,
+    #[msg("Mint counter does not belong to this candy machine and payer")]
+    MintCounterMismatch,
+    #[msg("Payer has reached the mint limit")]
+    MintLimitReached,
+    #[msg("Cannot withdraw while a mint limit is set, remove it first")]
+    NoWithdrawWithMintLimit,
+    #[msg("Payer is not on the allowlist of the current mint phase")]
+    InvalidAllowlistProof,
+    #[msg("Token account must hold the NFT for the payer of the mint request")]
+    MintRequestTokenAccountMismatch,
 }
@@ -1,5 +1,6 @@
 pub mod constants;
 pub mod errors;
+pub mod merkle_proof;
 pub mod processor;
 pub mod state;
 pub mod utils;
@@ -56,7 +57,50 @@ pub mod candy_machine {
         ctx: Context<'_, '_, '_, 'info, MintNFT<'info>>,
         creator_bump: u8,
     ) -> Result<()> {
-        handle_mint_nft(ctx, creator_bump)
+        handle_mint_nft(ctx, creator_bump, None)
+    }
+
+    pub fn mint_nft_with_proof<'info>(
+        ctx: Context<'_, '_, '_, 'info, MintNFT<'info>>,
+        creator_bump: u8,
+        allowlist_proof: AllowlistProof,
+    ) -> Result<()> {
+        handle_mint_nft(ctx, creator_bump, Some(allowlist_proof))
+    }
+
+    pub fn set_mint_phases(ctx: Context<SetMintPhases>, phases: Vec<MintPhase>) -> Result<()> {
+        handle_set_mint_phases(ctx, phases)
+    }
+
+    pub fn remove_mint_phases(ctx: Context<RemoveMintPhases>) -> Result<()> {
+        handle_remove_mint_phases(ctx)
+    }
+
+    pub fn set_mint_limit(ctx: Context<SetMintLimit>, mint_limit: u64) -> Result<()> {
+        handle_set_mint_limit(ctx, mint_limit)
+    }
+
+    pub fn remove_mint_limit(ctx: Context<RemoveMintLimit>) -> Result<()> {
+        handle_remove_mint_limit(ctx)
+    }
+
+    pub fn set_commit_reveal(ctx: Context<SetCommitReveal>, enabled: bool) -> Result<()> {
+        handle_set_commit_reveal(ctx, enabled)
+    }
+
+    pub fn request_mint(ctx: Context<RequestMint>, commitment: [u8; 32]) -> Result<()> {
+        handle_request_mint(ctx, commitment)
+    }
+
+    pub fn fulfill_mint<'info>(
+        ctx: Context<'_, '_, '_, 'info, FulfillMint<'info>>,
+        creator_bump: u8,
+    ) -> Result<()> {
+        handle_fulfill_mint(ctx, creator_bump)
+    }
+
+    pub fn refund_mint_request(ctx: Context<RefundMintRequest>) -> Result<()> {
+        handle_refund_mint_request(ctx)
     }
 
     pub fn set_collection_during_mint(ctx: Context<SetCollectionDuringMint>) -> Result<()> {
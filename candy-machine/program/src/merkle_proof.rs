@@ -0,0 +1,68 @@
+//! Verification of merkle allowlists, hashed the same way as gumdrop's distributors so the same
+//! tree builders can be used.
+
+use anchor_lang::prelude::Pubkey;
+use solana_program::keccak;
+
+/// Leaf of `wallet` being allowed to mint `allowance` items.
+pub fn allowlist_leaf(wallet: &Pubkey, allowance: u64) -> [u8; 32] {
+    keccak::hashv(&[&[0x00], &wallet.to_bytes(), &allowance.to_le_bytes()]).0
+}
+
+/// Returns true if a `leaf` can be proved to be a part of a Merkle tree
+/// defined by `root`. For this, a `proof` must be provided, containing
+/// sibling hashes on the branch from the leaf to the root of the tree. Each
+/// pair of leaves and each pair of pre-images are assumed to be sorted.
+/// Same as gumdrop's `merkle_proof::verify`.
+pub fn verify(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let mut computed_hash = leaf;
+    for proof_element in proof {
+        if computed_hash <= *proof_element {
+            // Hash(current computed hash + current element of the proof)
+            computed_hash = keccak::hashv(&[&[0x01], &computed_hash, proof_element]).0;
+        } else {
+            // Hash(current element of the proof + current computed hash)
+            computed_hash = keccak::hashv(&[&[0x01], proof_element, &computed_hash]).0;
+        }
+    }
+    // Check if the computed hash (root) is equal to the provided root
+    computed_hash == root
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        let (first, second) = if a <= b { (a, b) } else { (b, a) };
+        keccak::hashv(&[&[0x01], &first, &second]).0
+    }
+
+    #[test]
+    fn verify_allowlist_proof() {
+        let wallets = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let leaves = [
+            allowlist_leaf(&wallets[0], 1),
+            allowlist_leaf(&wallets[1], 2),
+            allowlist_leaf(&wallets[2], 3),
+        ];
+        let left = hash_pair(leaves[0], leaves[1]);
+        let root = hash_pair(left, leaves[2]);
+
+        assert!(verify(&[leaves[1], leaves[2]], root, leaves[0]));
+        assert!(verify(&[leaves[0], leaves[2]], root, leaves[1]));
+        assert!(verify(&[left], root, leaves[2]));
+
+        // The allowance is part of the leaf.
+        assert!(!verify(
+            &[leaves[1], leaves[2]],
+            root,
+            allowlist_leaf(&wallets[0], 5)
+        ));
+        assert!(!verify(&[leaves[1]], root, leaves[0]));
+    }
+}
@@ -55,8 +55,13 @@ pub fn handle_set_collection_during_mint(ctx: Context<SetCollectionDuringMint>)
         return Ok(());
     }
 
+    // Either `mint_nft`, `mint_nft_with_proof` or `fulfill_mint`, which lay out their accounts
+    // the same way.
     let discriminator = &previous_instruction.data[0..8];
-    if discriminator != [211, 57, 6, 167, 15, 219, 35, 251] {
+    if discriminator != [211, 57, 6, 167, 15, 219, 35, 251]
+        && discriminator != [211, 254, 152, 217, 203, 186, 244, 252]
+        && discriminator != [57, 64, 56, 56, 44, 114, 224, 165]
+    {
         msg!("Transaction had ix with data {:?}", discriminator);
         return Ok(());
     }
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use arrayref::array_ref;
+use solana_program::{keccak, sysvar, sysvar::instructions::get_instruction_relative};
+
+use crate::{
+    cmp_pubkeys,
+    constants::{COLLECTIONS_FEATURE_INDEX, MINT_REQUEST_EXPIRY_SLOTS, PREFIX},
+    create_nft_from_config_line, get_config_line, get_slot_hash_after, is_feature_active,
+    CandyError, CandyMachine, MintRequest, NftAccounts,
+};
+
+/// Mints the NFT paid for by a mint request. The config line is picked from the hash of the
+/// first slot after the request combined with its commitment. Anyone can fulfill a request,
+/// e.g. the candy machine authority or a crank, and the NFT has to be held by the payer of the
+/// request, so that the payer can't pass on a draw it doesn't like. The accounts up to
+/// `master_edition` are laid out like those of `mint_nft`, which `set_collection_during_mint`
+/// relies on.
+#[derive(Accounts)]
+#[instruction(creator_bump: u8)]
+pub struct FulfillMint<'info> {
+    #[account(
+    mut,
+    has_one = wallet
+    )]
+    candy_machine: Box<Account<'info, CandyMachine>>,
+    /// CHECK: account constraints checked in account trait
+    #[account(seeds=[PREFIX.as_bytes(), candy_machine.key().as_ref()], bump=creator_bump)]
+    candy_machine_creator: UncheckedAccount<'info>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    /// CHECK: wallet can be any account and is not read
+    #[account(mut)]
+    wallet: UncheckedAccount<'info>,
+    /// CHECK: account checked in CPI
+    #[account(mut)]
+    metadata: UncheckedAccount<'info>,
+    /// CHECK: account checked in CPI
+    #[account(mut)]
+    mint: UncheckedAccount<'info>,
+    mint_authority: Signer<'info>,
+    update_authority: Signer<'info>,
+    /// CHECK: account checked in CPI
+    #[account(mut)]
+    master_edition: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = request_payer,
+        has_one = candy_machine,
+        seeds = [
+            MintRequest::PREFIX.as_bytes(),
+            candy_machine.key().as_ref(),
+            request_payer.key().as_ref()
+        ],
+        bump
+    )]
+    mint_request: Account<'info, MintRequest>,
+    /// CHECK: only receives the rent of the mint request, checked against it
+    #[account(mut, address = mint_request.payer)]
+    request_payer: UncheckedAccount<'info>,
+    #[account(
+        has_one = mint,
+        constraint = token_account.owner == mint_request.payer && token_account.amount == 1 @ CandyError::MintRequestTokenAccountMismatch
+    )]
+    token_account: Account<'info, TokenAccount>,
+    /// CHECK: account checked in CPI
+    #[account(address = mpl_token_metadata::id())]
+    token_metadata_program: UncheckedAccount<'info>,
+    token_program: Program<'info, Token>,
+    system_program: Program<'info, System>,
+    rent: Sysvar<'info, Rent>,
+    /// CHECK: account constraints checked in account trait
+    #[account(address = sysvar::slot_hashes::id())]
+    slot_hashes: UncheckedAccount<'info>,
+    /// CHECK: account constraints checked in account trait
+    #[account(address = sysvar::instructions::id())]
+    instruction_sysvar_account: UncheckedAccount<'info>,
+}
+
+pub fn handle_fulfill_mint<'info>(
+    ctx: Context<'_, '_, '_, 'info, FulfillMint<'info>>,
+    creator_bump: u8,
+) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    let mint_request = &ctx.accounts.mint_request;
+    let wallet = &ctx.accounts.wallet;
+    let clock = Clock::get()?;
+
+    if !ctx.accounts.metadata.data_is_empty() {
+        return err!(CandyError::MetadataAccountMustBeEmpty);
+    }
+    if clock.slot > mint_request.slot.saturating_add(MINT_REQUEST_EXPIRY_SLOTS) {
+        return err!(CandyError::MintRequestExpired);
+    }
+    if candy_machine.items_redeemed >= candy_machine.data.items_available {
+        return err!(CandyError::CandyMachineEmpty);
+    }
+
+    if is_feature_active(&candy_machine.data.uuid, COLLECTIONS_FEATURE_INDEX) {
+        let instruction_sysvar_account_info =
+            ctx.accounts.instruction_sysvar_account.to_account_info();
+        let sets_collection = match get_instruction_relative(1, &instruction_sysvar_account_info) {
+            Ok(ix) => {
+                cmp_pubkeys(&ix.program_id, &crate::id())
+                    && ix.data.len() >= 8
+                    && ix.data[0..8] == [103, 17, 200, 25, 118, 95, 125, 61]
+            }
+            Err(_) => false,
+        };
+        if !sets_collection {
+            return err!(CandyError::MissingSetCollectionDuringMint);
+        }
+    }
+
+    let slot_hash = get_slot_hash_after(&ctx.accounts.slot_hashes, mint_request.slot)
+        .ok_or(CandyError::MintRequestNotRevealable)?;
+    let random = keccak::hashv(&[&mint_request.commitment, &slot_hash]).to_bytes();
+    let index = u64::from_le_bytes(*array_ref![random, 0, 8]);
+    let modded: usize = index
+        .checked_rem(candy_machine.data.items_available)
+        .ok_or(CandyError::NumericalOverflowError)? as usize;
+
+    let config_line = get_config_line(candy_machine, modded, candy_machine.items_redeemed)?;
+
+    candy_machine.items_redeemed = candy_machine
+        .items_redeemed
+        .checked_add(1)
+        .ok_or(CandyError::NumericalOverflowError)?;
+
+    create_nft_from_config_line(
+        candy_machine,
+        creator_bump,
+        config_line,
+        NftAccounts {
+            candy_machine_creator: ctx.accounts.candy_machine_creator.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            update_authority: ctx.accounts.update_authority.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+    )?;
+
+    // The escrowed price goes to the wallet, and the rent back to the payer of the request when
+    // it's closed.
+    let mint_request_info = mint_request.to_account_info();
+    let wallet_info = wallet.to_account_info();
+    let request_lamports = mint_request_info
+        .lamports()
+        .checked_sub(mint_request.price)
+        .ok_or(CandyError::NumericalOverflowError)?;
+    let wallet_lamports = wallet_info
+        .lamports()
+        .checked_add(mint_request.price)
+        .ok_or(CandyError::NumericalOverflowError)?;
+    **mint_request_info.try_borrow_mut_lamports()? = request_lamports;
+    **wallet_info.try_borrow_mut_lamports()? = wallet_lamports;
+    Ok(())
+}
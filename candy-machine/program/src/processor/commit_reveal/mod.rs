@@ -0,0 +1,9 @@
+pub mod fulfill_mint;
+pub mod refund_mint_request;
+pub mod request_mint;
+pub mod set_commit_reveal;
+
+pub use fulfill_mint::*;
+pub use refund_mint_request::*;
+pub use request_mint::*;
+pub use set_commit_reveal::*;
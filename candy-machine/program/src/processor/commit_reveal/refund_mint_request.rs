@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{BOT_FEE, MINT_REQUEST_EXPIRY_SLOTS},
+    CandyError, CandyMachine, MintRequest,
+};
+
+/// Closes a mint request that can no longer be fulfilled, because it expired or the candy
+/// machine ran out, and refunds the price and rent to its payer. Anyone can refund. Expired
+/// requests of a candy machine that still has items left are charged the bot fee.
+#[derive(Accounts)]
+pub struct RefundMintRequest<'info> {
+    /// CHECK: may have been withdrawn, checked in the instruction
+    #[account(mut)]
+    candy_machine: UncheckedAccount<'info>,
+    /// CHECK: only receives the refund, checked against the mint request
+    #[account(mut)]
+    payer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        has_one = candy_machine,
+        seeds = [
+            MintRequest::PREFIX.as_bytes(),
+            candy_machine.key().as_ref(),
+            payer.key().as_ref()
+        ],
+        bump
+    )]
+    mint_request: Account<'info, MintRequest>,
+}
+
+pub fn handle_refund_mint_request(ctx: Context<RefundMintRequest>) -> Result<()> {
+    let mint_request = &ctx.accounts.mint_request;
+    let clock = Clock::get()?;
+
+    let expired = clock.slot > mint_request.slot.saturating_add(MINT_REQUEST_EXPIRY_SLOTS);
+    // A candy machine that was withdrawn won't mint anything anymore.
+    let candy_machine_info = ctx.accounts.candy_machine.to_account_info();
+    let sold_out = match Account::<CandyMachine>::try_from(&candy_machine_info) {
+        Ok(candy_machine) => candy_machine.items_redeemed >= candy_machine.data.items_available,
+        Err(_) => true,
+    };
+    if !sold_out {
+        if !expired {
+            return err!(CandyError::MintRequestNotExpired);
+        }
+        // Anyone can fulfill a request, but the payer can still hold off a draw it doesn't like
+        // until it expires and try again, which is taxed like botting.
+        let fee = BOT_FEE.min(mint_request.price);
+        msg!(
+            "Expired mint request, Candy Machine Botting is taxed at {:?} lamports",
+            fee
+        );
+        let mint_request_info = mint_request.to_account_info();
+        let request_lamports = mint_request_info
+            .lamports()
+            .checked_sub(fee)
+            .ok_or(CandyError::NumericalOverflowError)?;
+        let candy_machine_lamports = candy_machine_info
+            .lamports()
+            .checked_add(fee)
+            .ok_or(CandyError::NumericalOverflowError)?;
+        **mint_request_info.try_borrow_mut_lamports()? = request_lamports;
+        **candy_machine_info.try_borrow_mut_lamports()? = candy_machine_lamports;
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke, system_instruction};
+
+use crate::{
+    assert_valid_go_live, cmp_pubkeys, constants::COMMIT_REVEAL_FEATURE_INDEX, is_feature_active,
+    CandyError, CandyMachine, EndSettingType, MintRequest,
+};
+
+/// Pays for a mint and records `commitment` along with the current slot. The config line is
+/// picked by `fulfill_mint` from the hash of a later slot, which can't be known at this point.
+#[derive(Accounts)]
+pub struct RequestMint<'info> {
+    candy_machine: Box<Account<'info, CandyMachine>>,
+    #[account(mut)]
+    payer: Signer<'info>,
+    #[account(
+        init,
+        seeds = [
+            MintRequest::PREFIX.as_bytes(),
+            candy_machine.key().as_ref(),
+            payer.key().as_ref()
+        ],
+        bump,
+        space = MintRequest::SIZE,
+        payer = payer
+    )]
+    mint_request: Account<'info, MintRequest>,
+    system_program: Program<'info, System>,
+}
+
+pub fn handle_request_mint(ctx: Context<RequestMint>, commitment: [u8; 32]) -> Result<()> {
+    let candy_machine = &ctx.accounts.candy_machine;
+    let payer = &ctx.accounts.payer;
+    let clock = Clock::get()?;
+
+    if !is_feature_active(&candy_machine.data.uuid, COMMIT_REVEAL_FEATURE_INDEX) {
+        return err!(CandyError::CommitRevealNotEnabled);
+    }
+    candy_machine.assert_commit_reveal_supported()?;
+
+    if candy_machine.items_redeemed >= candy_machine.data.items_available {
+        return err!(CandyError::CandyMachineEmpty);
+    }
+    assert_valid_go_live(payer, &clock, candy_machine, None)?;
+    if let Some(es) = &candy_machine.data.end_settings {
+        match es.end_setting_type {
+            EndSettingType::Date => {
+                if clock.unix_timestamp > es.number as i64
+                    && !cmp_pubkeys(&payer.key(), &candy_machine.authority)
+                {
+                    return err!(CandyError::CandyMachineNotLive);
+                }
+            }
+            EndSettingType::Amount => {
+                if candy_machine.items_redeemed >= es.number {
+                    return err!(CandyError::CandyMachineEmpty);
+                }
+            }
+        }
+    }
+
+    // The price is held by the request until it's fulfilled or refunded.
+    let price = candy_machine.data.price;
+    if payer.lamports() < price {
+        return err!(CandyError::NotEnoughSOL);
+    }
+    invoke(
+        &system_instruction::transfer(&payer.key(), &ctx.accounts.mint_request.key(), price),
+        &[
+            payer.to_account_info(),
+            ctx.accounts.mint_request.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let mint_request = &mut ctx.accounts.mint_request;
+    mint_request.candy_machine = candy_machine.key();
+    mint_request.payer = payer.key();
+    mint_request.commitment = commitment;
+    mint_request.slot = clock.slot;
+    mint_request.price = price;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::COMMIT_REVEAL_FEATURE_INDEX, remove_feature_flag, set_feature_flag, CandyMachine,
+};
+
+/// Turns minting through `request_mint` and `fulfill_mint` on or off. `mint_nft` is disabled
+/// while it's on.
+#[derive(Accounts)]
+pub struct SetCommitReveal<'info> {
+    #[account(mut, has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    authority: Signer<'info>,
+}
+
+pub fn handle_set_commit_reveal(ctx: Context<SetCommitReveal>, enabled: bool) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    if enabled {
+        candy_machine.assert_commit_reveal_supported()?;
+        set_feature_flag(&mut candy_machine.data.uuid, COMMIT_REVEAL_FEATURE_INDEX);
+    } else {
+        remove_feature_flag(&mut candy_machine.data.uuid, COMMIT_REVEAL_FEATURE_INDEX);
+    }
+    Ok(())
+}
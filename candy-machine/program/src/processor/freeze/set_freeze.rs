@@ -2,8 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::{
     assert_is_ata,
-    constants::{FREEZE_FEATURE_INDEX, FREEZE_LOCK_FEATURE_INDEX, MAX_FREEZE_TIME},
-    set_feature_flag, CandyError, CandyMachine, FreezePDA,
+    constants::{
+        FREEZE_FEATURE_INDEX, FREEZE_LOCK_FEATURE_INDEX, MAX_FREEZE_TIME, MINT_PHASES_FEATURE_INDEX,
+    },
+    is_feature_active, set_feature_flag, CandyError, CandyMachine, FreezePDA,
 };
 
 /// Set the Freeze PDA for the candy machine
@@ -24,6 +26,9 @@ pub struct SetFreeze<'info> {
 pub fn handle_set_freeze(ctx: Context<SetFreeze>, freeze_time: i64) -> Result<()> {
     let candy_machine = &mut ctx.accounts.candy_machine;
     candy_machine.assert_not_minted(error!(CandyError::NoChangingFreezeDuringMint))?;
+    if is_feature_active(&candy_machine.data.uuid, MINT_PHASES_FEATURE_INDEX) {
+        return err!(CandyError::MintPhasesUnsupportedSettings);
+    }
     let freeze_pda = &mut ctx.accounts.freeze_pda;
     if freeze_time > MAX_FREEZE_TIME {
         return err!(CandyError::EnteredFreezeIsMoreThanMaxFreeze);
@@ -23,15 +23,20 @@ use solana_program::{
 };
 use spl_token::instruction::approve;
 
-use crate::constants::{COMPUTE_BUDGET, FREEZE_FEATURE_INDEX};
+use crate::constants::{
+    COMMIT_REVEAL_FEATURE_INDEX, COMPUTE_BUDGET, FREEZE_FEATURE_INDEX, MINT_LIMIT_FEATURE_INDEX,
+    MINT_PHASES_FEATURE_INDEX,
+};
 use crate::{
     constants::{
         A_TOKEN, BLOCK_HASHES, BOT_FEE, COLLECTIONS_FEATURE_INDEX, CONFIG_ARRAY_START,
         CONFIG_LINE_SIZE, CUPCAKE_ID, EXPIRE_OFFSET, GUMDROP_ID, PREFIX,
     },
+    merkle_proof::{self, allowlist_leaf},
     utils::*,
-    CandyError, CandyMachine, CandyMachineData, ConfigLine, EndSettingType, FreezePDA,
-    WhitelistMintMode, WhitelistMintSettings,
+    AllowlistProof, CandyError, CandyMachine, CandyMachineData, ConfigLine, EndSettingType,
+    FreezePDA, MintCounter, MintLimitPDA, MintPhasesPDA, PhaseGate, WhitelistMintMode,
+    WhitelistMintSettings,
 };
 
 /// Mint a new NFT pseudo-randomly from the config array.
@@ -82,6 +87,22 @@ pub struct MintNFT<'info> {
     // > Only needed if candy machine has a gatekeeper and it has expire_on_use set to true:
     // gateway program
     // network_expire_feature
+    // > Only needed if candy machine has mint phases
+    // mint_phases_pda (writable)
+    // > Only needed if candy machine has a mint limit
+    // mint_limit_pda
+    // > Only needed if candy machine has a mint limit, or one of its mint phases has a mint limit
+    // or a merkle allowlist
+    // mint_counter (writable)
+    // > Only needed if the active phase has a whitelist token gate
+    // whitelist_token_account
+    // > Only needed if the active phase has a whitelist token gate with mode BurnEveryTime
+    // whitelist_token_mint
+    // whitelist_burn_authority
+    // > Only needed if the active phase is paid in an SPL token
+    // token_account_info
+    // transfer_authority_info
+    // payment_destination
     // > Only needed if candy machine has whitelist_mint_settings
     // whitelist_token_account
     // > Only needed if candy machine has whitelist_mint_settings and mode is BurnEveryTime
@@ -99,6 +120,7 @@ pub struct MintNFT<'info> {
 pub fn handle_mint_nft<'info>(
     ctx: Context<'_, '_, '_, 'info, MintNFT<'info>>,
     creator_bump: u8,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<()> {
     let candy_machine = &mut ctx.accounts.candy_machine;
     let candy_machine_creator = &ctx.accounts.candy_machine_creator;
@@ -117,6 +139,9 @@ pub fn handle_mint_nft<'info>(
     if !ctx.accounts.metadata.data_is_empty() {
         return err!(CandyError::MetadataAccountMustBeEmpty);
     }
+    if is_feature_active(&candy_machine.data.uuid, COMMIT_REVEAL_FEATURE_INDEX) {
+        return err!(CandyError::MintRequestRequired);
+    }
 
     if get_expected_remaining_accounts_count(candy_machine) > ctx.remaining_accounts.len() {
         punish_bots(
@@ -326,6 +351,70 @@ pub fn handle_mint_nft<'info>(
         }
     }
 
+    let mut mint_phase: Option<(Account<MintPhasesPDA>, usize)> = None;
+    if is_feature_active(&candy_machine.data.uuid, MINT_PHASES_FEATURE_INDEX) {
+        let mint_phases_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+        let mint_phases: Account<MintPhasesPDA> = Account::try_from(mint_phases_info)?;
+        mint_phases.assert_from_candy(&candy_machine.key())?;
+        let index = mint_phases.resolve_phase(clock.unix_timestamp);
+        mint_phase = Some((mint_phases, index));
+    }
+    let active_phase = mint_phase
+        .as_ref()
+        .map(|(mint_phases, index)| &mint_phases.phases[*index]);
+
+    let mut mint_limit = None;
+    if is_feature_active(&candy_machine.data.uuid, MINT_LIMIT_FEATURE_INDEX) {
+        let mint_limit_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+        let mint_limit_pda: Account<MintLimitPDA> = Account::try_from(mint_limit_info)?;
+        mint_limit_pda.assert_from_candy(&candy_machine.key())?;
+        mint_limit = Some(mint_limit_pda.mint_limit);
+    }
+    let phases_count_mints = mint_phase.as_ref().map_or(false, |(mint_phases, _)| {
+        mint_phases.phases.iter().any(|phase| phase.counts_mints())
+    });
+    let mut mint_counter = None;
+    if mint_limit.is_some() || phases_count_mints {
+        // Without a mint limit on the candy machine the counter isn't part of the expected count.
+        if remaining_accounts_counter >= ctx.remaining_accounts.len() {
+            punish_bots(
+                CandyError::IncorrectRemainingAccountsLen,
+                payer.to_account_info(),
+                ctx.accounts.candy_machine.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                BOT_FEE,
+            )?;
+            return Ok(());
+        }
+        let mint_counter_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+        let counter = get_or_create_mint_counter(
+            mint_counter_info,
+            &candy_machine.key(),
+            &payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        let limit_reached = mint_limit.map_or(false, |limit| counter.count >= limit)
+            || active_phase.map_or(false, |phase| {
+                phase
+                    .mint_limit
+                    .map_or(false, |limit| counter.count_in_phase(phase) >= limit)
+            });
+        if limit_reached {
+            punish_bots(
+                CandyError::MintLimitReached,
+                payer.to_account_info(),
+                ctx.accounts.candy_machine.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                BOT_FEE,
+            )?;
+            return Ok(());
+        }
+        mint_counter = Some(counter);
+    }
+
     if let Some(ws) = &candy_machine.data.whitelist_mint_settings {
         let whitelist_token_account = &ctx.remaining_accounts[remaining_accounts_counter];
         remaining_accounts_counter += 1;
@@ -417,7 +506,7 @@ pub fn handle_mint_nft<'info>(
                         )?;
                         return Ok(());
                     }
-                    let go_live = assert_valid_go_live(payer, &clock, candy_machine);
+                    let go_live = assert_valid_go_live(payer, &clock, candy_machine, active_phase);
                     if go_live.is_err() {
                         punish_bots(
                             CandyError::CandyMachineNotLive,
@@ -450,7 +539,7 @@ pub fn handle_mint_nft<'info>(
                 if ws.mode == WhitelistMintMode::BurnEveryTime {
                     remaining_accounts_counter += 2;
                 }
-                let go_live = assert_valid_go_live(payer, &clock, candy_machine);
+                let go_live = assert_valid_go_live(payer, &clock, candy_machine, active_phase);
                 if go_live.is_err() {
                     punish_bots(
                         CandyError::CandyMachineNotLive,
@@ -465,7 +554,7 @@ pub fn handle_mint_nft<'info>(
         }
     } else {
         // no whitelist means normal datecheck
-        let go_live = assert_valid_go_live(payer, &clock, candy_machine);
+        let go_live = assert_valid_go_live(payer, &clock, candy_machine, active_phase);
         if go_live.is_err() {
             punish_bots(
                 CandyError::CandyMachineNotLive,
@@ -478,6 +567,129 @@ pub fn handle_mint_nft<'info>(
         }
     }
 
+    let mut token_payment = None;
+    if let Some(phase) = active_phase {
+        if let Some(supply_cap) = phase.supply_cap {
+            if phase.items_redeemed >= supply_cap {
+                punish_bots(
+                    CandyError::MintPhaseSoldOut,
+                    payer.to_account_info(),
+                    ctx.accounts.candy_machine.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    BOT_FEE,
+                )?;
+                return Ok(());
+            }
+        }
+
+        // The accounts of the phase depend on which one is active, so they're only counted now.
+        let mut phase_accounts_count = 0;
+        if let Some(PhaseGate::WhitelistToken { mode, .. }) = &phase.gate {
+            phase_accounts_count += if *mode == WhitelistMintMode::BurnEveryTime {
+                3
+            } else {
+                1
+            };
+        }
+        if phase.token_payment.is_some() {
+            phase_accounts_count += 3;
+        }
+        if remaining_accounts_counter + phase_accounts_count > ctx.remaining_accounts.len() {
+            punish_bots(
+                CandyError::IncorrectRemainingAccountsLen,
+                payer.to_account_info(),
+                ctx.accounts.candy_machine.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+                BOT_FEE,
+            )?;
+            return Ok(());
+        }
+
+        if let Some(PhaseGate::WhitelistToken { mint, mode }) = &phase.gate {
+            let whitelist_token_account = &ctx.remaining_accounts[remaining_accounts_counter];
+            remaining_accounts_counter += 1;
+            let has_token = match assert_is_ata(whitelist_token_account, &payer.key(), mint) {
+                Ok(wta) => wta.amount > 0,
+                Err(_) => false,
+            };
+            if !has_token {
+                punish_bots(
+                    CandyError::NoWhitelistToken,
+                    payer.to_account_info(),
+                    ctx.accounts.candy_machine.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    BOT_FEE,
+                )?;
+                return Ok(());
+            }
+
+            if *mode == WhitelistMintMode::BurnEveryTime {
+                let whitelist_token_mint = &ctx.remaining_accounts[remaining_accounts_counter];
+                remaining_accounts_counter += 1;
+                let whitelist_burn_authority = &ctx.remaining_accounts[remaining_accounts_counter];
+                remaining_accounts_counter += 1;
+
+                if assert_keys_equal(&whitelist_token_mint.key(), mint).is_err() {
+                    punish_bots(
+                        CandyError::IncorrectOwner,
+                        payer.to_account_info(),
+                        ctx.accounts.candy_machine.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                        BOT_FEE,
+                    )?;
+                    return Ok(());
+                }
+
+                spl_token_burn(TokenBurnParams {
+                    mint: whitelist_token_mint.clone(),
+                    source: whitelist_token_account.clone(),
+                    amount: 1,
+                    authority: whitelist_burn_authority.clone(),
+                    authority_signer_seeds: None,
+                    token_program: token_program.to_account_info(),
+                })?;
+            }
+        }
+
+        if let Some(PhaseGate::MerkleAllowlist { root }) = &phase.gate {
+            let allowance = allowlist_proof.as_ref().and_then(|allowlist_proof| {
+                let leaf = allowlist_leaf(&payer.key(), allowlist_proof.allowance);
+                merkle_proof::verify(&allowlist_proof.proof, *root, leaf)
+                    .then(|| allowlist_proof.allowance)
+            });
+            let allowance = match allowance {
+                Some(allowance) => allowance,
+                None => {
+                    punish_bots(
+                        CandyError::InvalidAllowlistProof,
+                        payer.to_account_info(),
+                        ctx.accounts.candy_machine.to_account_info(),
+                        ctx.accounts.system_program.to_account_info(),
+                        BOT_FEE,
+                    )?;
+                    return Ok(());
+                }
+            };
+            // The mint counter is loaded for every phase with a merkle allowlist.
+            let minted = mint_counter
+                .as_ref()
+                .map_or(0, |counter| counter.count_in_phase(phase));
+            if minted >= allowance {
+                punish_bots(
+                    CandyError::MintLimitReached,
+                    payer.to_account_info(),
+                    ctx.accounts.candy_machine.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                    BOT_FEE,
+                )?;
+                return Ok(());
+            }
+        }
+
+        price = phase.price;
+        token_payment = phase.token_payment.clone();
+    }
+
     let (wallet_to_use, freeze_pda): (&AccountInfo, Option<Account<FreezePDA>>) =
         if is_feature_active(&candy_machine.data.uuid, FREEZE_FEATURE_INDEX) {
             if let Some(mint) = candy_machine.token_mint {
@@ -503,7 +715,32 @@ pub fn handle_mint_nft<'info>(
             (wallet, None)
         };
 
-    if let Some(mint) = candy_machine.token_mint {
+    if let Some(token_payment) = token_payment {
+        let token_account_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+        let transfer_authority_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+        let destination_info = &ctx.remaining_accounts[remaining_accounts_counter];
+        remaining_accounts_counter += 1;
+
+        if !cmp_pubkeys(destination_info.key, &token_payment.destination) {
+            return err!(CandyError::IncorrectPaymentDestination);
+        }
+        let token_account = assert_is_ata(token_account_info, &payer.key(), &token_payment.mint)?;
+
+        if token_account.amount < price {
+            return err!(CandyError::NotEnoughTokens);
+        }
+
+        spl_token_transfer(TokenTransferParams {
+            source: token_account_info.clone(),
+            destination: destination_info.clone(),
+            authority: transfer_authority_info.clone(),
+            authority_signer_seeds: &[],
+            token_program: token_program.to_account_info(),
+            amount: price,
+        })?;
+    } else if let Some(mint) = candy_machine.token_mint {
         let token_account_info = &ctx.remaining_accounts[remaining_accounts_counter];
         remaining_accounts_counter += 1;
         let transfer_authority_info = &ctx.remaining_accounts[remaining_accounts_counter];
@@ -552,110 +789,37 @@ pub fn handle_mint_nft<'info>(
         .checked_add(1)
         .ok_or(CandyError::NumericalOverflowError)?;
 
-    let cm_key = candy_machine.key();
-    let authority_seeds = [PREFIX.as_bytes(), cm_key.as_ref(), &[creator_bump]];
-
-    let mut creators: Vec<mpl_token_metadata::state::Creator> =
-        vec![mpl_token_metadata::state::Creator {
-            address: candy_machine_creator.key(),
-            verified: true,
-            share: 0,
-        }];
-
-    for c in &candy_machine.data.creators {
-        creators.push(mpl_token_metadata::state::Creator {
-            address: c.address,
-            verified: false,
-            share: c.share,
-        });
+    if let Some(mut mint_counter) = mint_counter {
+        mint_counter.increment(active_phase)?;
+        mint_counter.exit(&crate::id())?;
     }
 
-    let metadata_infos = vec![
-        ctx.accounts.metadata.to_account_info(),
-        ctx.accounts.mint.to_account_info(),
-        ctx.accounts.mint_authority.to_account_info(),
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.token_metadata_program.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.rent.to_account_info(),
-        candy_machine_creator.to_account_info(),
-    ];
-
-    let master_edition_infos = vec![
-        ctx.accounts.master_edition.to_account_info(),
-        ctx.accounts.mint.to_account_info(),
-        ctx.accounts.mint_authority.to_account_info(),
-        ctx.accounts.payer.to_account_info(),
-        ctx.accounts.metadata.to_account_info(),
-        ctx.accounts.token_metadata_program.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.system_program.to_account_info(),
-        ctx.accounts.rent.to_account_info(),
-        candy_machine_creator.to_account_info(),
-    ];
-
-    invoke_signed(
-        &create_metadata_accounts_v2(
-            ctx.accounts.token_metadata_program.key(),
-            ctx.accounts.metadata.key(),
-            ctx.accounts.mint.key(),
-            ctx.accounts.mint_authority.key(),
-            ctx.accounts.payer.key(),
-            candy_machine_creator.key(),
-            config_line.name,
-            candy_machine.data.symbol.clone(),
-            config_line.uri,
-            Some(creators),
-            candy_machine.data.seller_fee_basis_points,
-            true,
-            candy_machine.data.is_mutable,
-            None,
-            None,
-        ),
-        metadata_infos.as_slice(),
-        &[&authority_seeds],
-    )?;
-    invoke_signed(
-        &create_master_edition_v3(
-            ctx.accounts.token_metadata_program.key(),
-            ctx.accounts.master_edition.key(),
-            ctx.accounts.mint.key(),
-            candy_machine_creator.key(),
-            ctx.accounts.mint_authority.key(),
-            ctx.accounts.metadata.key(),
-            ctx.accounts.payer.key(),
-            Some(candy_machine.data.max_supply),
-        ),
-        master_edition_infos.as_slice(),
-        &[&authority_seeds],
-    )?;
-
-    let mut new_update_authority = Some(candy_machine.authority);
-
-    if !candy_machine.data.retain_authority {
-        new_update_authority = Some(ctx.accounts.update_authority.key());
+    if let Some((mut mint_phases, index)) = mint_phase {
+        let phase = &mut mint_phases.phases[index];
+        phase.items_redeemed = phase
+            .items_redeemed
+            .checked_add(1)
+            .ok_or(CandyError::NumericalOverflowError)?;
+        mint_phases.exit(&crate::id())?;
     }
-    invoke_signed(
-        &update_metadata_accounts_v2(
-            ctx.accounts.token_metadata_program.key(),
-            ctx.accounts.metadata.key(),
-            candy_machine_creator.key(),
-            new_update_authority,
-            None,
-            Some(true),
-            if !candy_machine.data.is_mutable {
-                Some(false)
-            } else {
-                None
-            },
-        ),
-        &[
-            ctx.accounts.token_metadata_program.to_account_info(),
-            ctx.accounts.metadata.to_account_info(),
-            candy_machine_creator.to_account_info(),
-        ],
-        &[&authority_seeds],
+
+    create_nft_from_config_line(
+        candy_machine,
+        creator_bump,
+        config_line,
+        NftAccounts {
+            candy_machine_creator: candy_machine_creator.to_account_info(),
+            payer: ctx.accounts.payer.to_account_info(),
+            metadata: ctx.accounts.metadata.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            mint_authority: ctx.accounts.mint_authority.to_account_info(),
+            update_authority: ctx.accounts.update_authority.to_account_info(),
+            master_edition: ctx.accounts.master_edition.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
     )?;
 
     if let Some(mut freeze_pda) = freeze_pda {
@@ -742,6 +906,176 @@ pub fn handle_mint_nft<'info>(
     Ok(())
 }
 
+/// Loads the mint counter of `payer`, creating it on their first mint.
+fn get_or_create_mint_counter<'info>(
+    mint_counter_info: &AccountInfo<'info>,
+    candy_machine: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<Account<'info, MintCounter>> {
+    let seeds = [
+        MintCounter::PREFIX.as_bytes(),
+        candy_machine.as_ref(),
+        payer.key.as_ref(),
+    ];
+    let (expected_key, bump) = Pubkey::find_program_address(&seeds, &crate::id());
+    if !cmp_pubkeys(mint_counter_info.key, &expected_key) {
+        return err!(CandyError::MintCounterMismatch);
+    }
+    if !mint_counter_info.data_is_empty() {
+        return Account::try_from(mint_counter_info);
+    }
+
+    create_pda_account(
+        payer,
+        mint_counter_info,
+        system_program,
+        MintCounter::SIZE,
+        &[
+            MintCounter::PREFIX.as_bytes(),
+            candy_machine.as_ref(),
+            payer.key.as_ref(),
+            &[bump],
+        ],
+    )?;
+    let mut mint_counter: Account<MintCounter> = Account::try_from_unchecked(mint_counter_info)?;
+    mint_counter.candy_machine = *candy_machine;
+    mint_counter.payer = payer.key();
+    Ok(mint_counter)
+}
+
+/// Accounts that go into creating the metadata and master edition of a minted NFT.
+pub struct NftAccounts<'info> {
+    pub candy_machine_creator: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub mint_authority: AccountInfo<'info>,
+    pub update_authority: AccountInfo<'info>,
+    pub master_edition: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+/// Creates the metadata and master edition of an NFT minted from `config_line`, signed by the
+/// candy machine creator PDA.
+pub fn create_nft_from_config_line<'info>(
+    candy_machine: &Account<'info, CandyMachine>,
+    creator_bump: u8,
+    config_line: ConfigLine,
+    accounts: NftAccounts<'info>,
+) -> Result<()> {
+    let cm_key = candy_machine.key();
+    let authority_seeds = [PREFIX.as_bytes(), cm_key.as_ref(), &[creator_bump]];
+
+    let mut creators: Vec<mpl_token_metadata::state::Creator> =
+        vec![mpl_token_metadata::state::Creator {
+            address: accounts.candy_machine_creator.key(),
+            verified: true,
+            share: 0,
+        }];
+
+    for c in &candy_machine.data.creators {
+        creators.push(mpl_token_metadata::state::Creator {
+            address: c.address,
+            verified: false,
+            share: c.share,
+        });
+    }
+
+    let metadata_infos = vec![
+        accounts.metadata.clone(),
+        accounts.mint.clone(),
+        accounts.mint_authority.clone(),
+        accounts.payer.clone(),
+        accounts.token_metadata_program.clone(),
+        accounts.token_program.clone(),
+        accounts.system_program.clone(),
+        accounts.rent.clone(),
+        accounts.candy_machine_creator.clone(),
+    ];
+
+    let master_edition_infos = vec![
+        accounts.master_edition.clone(),
+        accounts.mint.clone(),
+        accounts.mint_authority.clone(),
+        accounts.payer.clone(),
+        accounts.metadata.clone(),
+        accounts.token_metadata_program.clone(),
+        accounts.token_program.clone(),
+        accounts.system_program.clone(),
+        accounts.rent.clone(),
+        accounts.candy_machine_creator.clone(),
+    ];
+
+    invoke_signed(
+        &create_metadata_accounts_v2(
+            accounts.token_metadata_program.key(),
+            accounts.metadata.key(),
+            accounts.mint.key(),
+            accounts.mint_authority.key(),
+            accounts.payer.key(),
+            accounts.candy_machine_creator.key(),
+            config_line.name,
+            candy_machine.data.symbol.clone(),
+            config_line.uri,
+            Some(creators),
+            candy_machine.data.seller_fee_basis_points,
+            true,
+            candy_machine.data.is_mutable,
+            None,
+            None,
+        ),
+        metadata_infos.as_slice(),
+        &[&authority_seeds],
+    )?;
+    invoke_signed(
+        &create_master_edition_v3(
+            accounts.token_metadata_program.key(),
+            accounts.master_edition.key(),
+            accounts.mint.key(),
+            accounts.candy_machine_creator.key(),
+            accounts.mint_authority.key(),
+            accounts.metadata.key(),
+            accounts.payer.key(),
+            Some(candy_machine.data.max_supply),
+        ),
+        master_edition_infos.as_slice(),
+        &[&authority_seeds],
+    )?;
+
+    let mut new_update_authority = Some(candy_machine.authority);
+
+    if !candy_machine.data.retain_authority {
+        new_update_authority = Some(accounts.update_authority.key());
+    }
+    invoke_signed(
+        &update_metadata_accounts_v2(
+            accounts.token_metadata_program.key(),
+            accounts.metadata.key(),
+            accounts.candy_machine_creator.key(),
+            new_update_authority,
+            None,
+            Some(true),
+            if !candy_machine.data.is_mutable {
+                Some(false)
+            } else {
+                None
+            },
+        ),
+        &[
+            accounts.token_metadata_program.clone(),
+            accounts.metadata.clone(),
+            accounts.candy_machine_creator.clone(),
+        ],
+        &[&authority_seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn get_good_index(
     arr: &mut RefMut<&mut [u8]>,
     items_available: usize,
@@ -887,6 +1221,13 @@ pub fn get_expected_remaining_accounts_count(candy: &CandyMachine) -> usize {
             expected_count += 2;
         }
     }
+    if is_feature_active(&candy.data.uuid, MINT_PHASES_FEATURE_INDEX) {
+        // The accounts of the active phase are counted once it's resolved.
+        expected_count += 1;
+    }
+    if is_feature_active(&candy.data.uuid, MINT_LIMIT_FEATURE_INDEX) {
+        expected_count += 2;
+    }
     if let Some(whitelist) = &candy.data.whitelist_mint_settings {
         expected_count += 1;
         if whitelist.mode == WhitelistMintMode::BurnEveryTime {
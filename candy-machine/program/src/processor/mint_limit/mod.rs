@@ -0,0 +1,5 @@
+pub mod remove_mint_limit;
+pub mod set_mint_limit;
+
+pub use remove_mint_limit::*;
+pub use set_mint_limit::*;
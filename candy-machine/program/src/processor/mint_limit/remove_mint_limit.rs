@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MINT_LIMIT_FEATURE_INDEX, remove_feature_flag, CandyMachine, MintLimitPDA};
+
+/// Removes the mint limit from the candy machine and closes the mint limit pda
+#[derive(Accounts)]
+pub struct RemoveMintLimit<'info> {
+    #[account(mut, has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, seeds = [MintLimitPDA::PREFIX.as_bytes(), candy_machine.key().as_ref()], bump, close = authority)]
+    mint_limit_pda: Account<'info, MintLimitPDA>,
+}
+
+pub fn handle_remove_mint_limit(ctx: Context<RemoveMintLimit>) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    remove_feature_flag(&mut candy_machine.data.uuid, MINT_LIMIT_FEATURE_INDEX);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{COMMIT_REVEAL_FEATURE_INDEX, MINT_LIMIT_FEATURE_INDEX},
+    is_feature_active, set_feature_flag, CandyError, CandyMachine, MintLimitPDA,
+};
+
+/// Limit the number of items each payer can mint. To change the limit, remove and set it again.
+#[derive(Accounts)]
+pub struct SetMintLimit<'info> {
+    #[account(mut, has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(init, seeds = [MintLimitPDA::PREFIX.as_bytes(), candy_machine.key().as_ref()], bump, space = MintLimitPDA::SIZE, payer = authority)]
+    mint_limit_pda: Account<'info, MintLimitPDA>,
+    system_program: Program<'info, System>,
+}
+
+pub fn handle_set_mint_limit(ctx: Context<SetMintLimit>, mint_limit: u64) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    // Mint requests don't take the mint counter.
+    if is_feature_active(&candy_machine.data.uuid, COMMIT_REVEAL_FEATURE_INDEX) {
+        return err!(CandyError::CommitRevealUnsupportedSettings);
+    }
+
+    let mint_limit_pda = &mut ctx.accounts.mint_limit_pda;
+    mint_limit_pda.candy_machine = candy_machine.key();
+    mint_limit_pda.mint_limit = mint_limit;
+    set_feature_flag(&mut candy_machine.data.uuid, MINT_LIMIT_FEATURE_INDEX);
+    Ok(())
+}
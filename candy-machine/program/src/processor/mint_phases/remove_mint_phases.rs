@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MINT_PHASES_FEATURE_INDEX, remove_feature_flag, CandyMachine, MintPhasesPDA,
+};
+
+/// Removes the mint phases from the candy machine and closes the mint phases pda
+#[derive(Accounts)]
+pub struct RemoveMintPhases<'info> {
+    #[account(mut, has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(mut, seeds = [MintPhasesPDA::PREFIX.as_bytes(), candy_machine.key().as_ref()], bump, close = authority)]
+    mint_phases_pda: Account<'info, MintPhasesPDA>,
+}
+
+pub fn handle_remove_mint_phases(ctx: Context<RemoveMintPhases>) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    remove_feature_flag(&mut candy_machine.data.uuid, MINT_PHASES_FEATURE_INDEX);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    assert_initialized, assert_owned_by, cmp_pubkeys, constants::MINT_PHASES_FEATURE_INDEX,
+    set_feature_flag, CandyError, CandyMachine, MintPhase, MintPhasesPDA,
+};
+
+/// Set the mint phases of the candy machine. To change them, remove and set them again.
+#[derive(Accounts)]
+pub struct SetMintPhases<'info> {
+    #[account(mut, has_one = authority)]
+    candy_machine: Account<'info, CandyMachine>,
+    #[account(mut)]
+    authority: Signer<'info>,
+    #[account(init, seeds = [MintPhasesPDA::PREFIX.as_bytes(), candy_machine.key().as_ref()], bump, space = MintPhasesPDA::SIZE, payer = authority)]
+    mint_phases_pda: Account<'info, MintPhasesPDA>,
+    system_program: Program<'info, System>,
+    // > Only needed for phases paid in an SPL token, in the order of the phases
+    // payment destination token account
+}
+
+pub fn handle_set_mint_phases(
+    ctx: Context<SetMintPhases>,
+    mut phases: Vec<MintPhase>,
+) -> Result<()> {
+    let candy_machine = &mut ctx.accounts.candy_machine;
+    candy_machine.assert_mint_phases_supported()?;
+    MintPhasesPDA::assert_valid_phases(&phases)?;
+
+    let mut destinations = ctx.remaining_accounts.iter();
+    for phase in phases.iter_mut() {
+        phase.items_redeemed = 0;
+        if let Some(token_payment) = &phase.token_payment {
+            let destination = destinations
+                .next()
+                .ok_or(CandyError::IncorrectPaymentDestination)?;
+            if !cmp_pubkeys(destination.key, &token_payment.destination) {
+                return err!(CandyError::IncorrectPaymentDestination);
+            }
+            assert_owned_by(destination, &spl_token::id())?;
+            let token_account: spl_token::state::Account = assert_initialized(destination)?;
+            if !cmp_pubkeys(&token_account.mint, &token_payment.mint) {
+                return err!(CandyError::MintMismatch);
+            }
+        }
+    }
+
+    let mint_phases_pda = &mut ctx.accounts.mint_phases_pda;
+    mint_phases_pda.candy_machine = candy_machine.key();
+    mint_phases_pda.phases = phases;
+    set_feature_flag(&mut candy_machine.data.uuid, MINT_PHASES_FEATURE_INDEX);
+    Ok(())
+}
@@ -1,15 +1,21 @@
 pub mod add_config_lines;
 pub mod collection;
+pub mod commit_reveal;
 pub mod freeze;
 pub mod initialize;
 pub mod mint;
+pub mod mint_limit;
+pub mod mint_phases;
 pub mod update;
 pub mod withdraw;
 
 pub use add_config_lines::*;
 pub use collection::*;
+pub use commit_reveal::*;
 pub use freeze::*;
 pub use initialize::*;
 pub use mint::*;
+pub use mint_limit::*;
+pub use mint_phases::*;
 pub use update::*;
 pub use withdraw::*;
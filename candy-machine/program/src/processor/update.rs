@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::FREEZE_FEATURE_INDEX;
+use crate::constants::{FREEZE_FEATURE_INDEX, MINT_PHASES_FEATURE_INDEX};
 use crate::{
     constants::COLLECTIONS_FEATURE_INDEX, is_feature_active, CandyError, CandyMachine,
     CandyMachineData,
@@ -69,6 +69,11 @@ pub fn handle_update_candy_machine(
     if is_feature_active(&old_uuid, COLLECTIONS_FEATURE_INDEX) && !data.retain_authority {
         return err!(CandyError::CandyCollectionRequiresRetainAuthority);
     }
+    if is_feature_active(&old_uuid, MINT_PHASES_FEATURE_INDEX)
+        && (token_mint.is_some() || data.whitelist_mint_settings.is_some())
+    {
+        return err!(CandyError::MintPhasesUnsupportedSettings);
+    }
 
     candy_machine.wallet = ctx.accounts.wallet.key();
     candy_machine.data = data;
@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::AccountsClose;
 
-use crate::constants::{FREEZE_FEATURE_INDEX, FREEZE_LOCK_FEATURE_INDEX};
+use crate::constants::{
+    FREEZE_FEATURE_INDEX, FREEZE_LOCK_FEATURE_INDEX, MINT_LIMIT_FEATURE_INDEX,
+    MINT_PHASES_FEATURE_INDEX,
+};
 use crate::{cmp_pubkeys, is_feature_active, CandyError, CandyMachine, CollectionPDA};
 
 /// Withdraw SOL from candy machine account.
@@ -26,6 +29,12 @@ pub fn handle_withdraw_funds<'info>(
     if is_feature_active(&candy_machine.data.uuid, FREEZE_LOCK_FEATURE_INDEX) {
         return err!(CandyError::NoWithdrawWithFrozenFunds);
     }
+    if is_feature_active(&candy_machine.data.uuid, MINT_PHASES_FEATURE_INDEX) {
+        return err!(CandyError::NoWithdrawWithMintPhases);
+    }
+    if is_feature_active(&candy_machine.data.uuid, MINT_LIMIT_FEATURE_INDEX) {
+        return err!(CandyError::NoWithdrawWithMintLimit);
+    }
 
     if !ctx.remaining_accounts.is_empty() {
         let candy_key = candy_machine.key();
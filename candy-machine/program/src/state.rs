@@ -1,5 +1,8 @@
-use crate::constants::FREEZE_FEE;
-use crate::CandyError;
+use crate::constants::{
+    COMMIT_REVEAL_FEATURE_INDEX, FREEZE_FEATURE_INDEX, FREEZE_FEE, MAX_MINT_PHASES,
+    MINT_LIMIT_FEATURE_INDEX, MINT_PHASES_FEATURE_INDEX,
+};
+use crate::{is_feature_active, CandyError};
 use anchor_lang::prelude::*;
 
 /// Candy machine state and config data.
@@ -75,6 +78,209 @@ impl FreezePDA {
     }
 }
 
+/// A mint paid for by `request_mint` and waiting for `fulfill_mint` to pick its config line.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintRequest {
+    pub candy_machine: Pubkey, // 32
+    pub payer: Pubkey,         // 32
+    pub commitment: [u8; 32],  // 32
+    pub slot: u64,             // 8
+    pub price: u64,            // 8
+}
+
+impl MintRequest {
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 8;
+
+    pub const PREFIX: &'static str = "mint_request";
+}
+
+/// Mint limit PDA account. While it's set, every payer can mint at most `mint_limit` items.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintLimitPDA {
+    pub candy_machine: Pubkey, // 32
+    pub mint_limit: u64,       // 8
+}
+
+impl MintLimitPDA {
+    pub const SIZE: usize = 8 + 32 + 8;
+
+    pub const PREFIX: &'static str = "mint_limit";
+
+    pub fn assert_from_candy(&self, candy_machine: &Pubkey) -> Result<()> {
+        if &self.candy_machine != candy_machine {
+            return err!(CandyError::MintLimitPDAMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Number of items a payer minted from a candy machine, created on their first mint.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintCounter {
+    pub candy_machine: Pubkey, // 32
+    pub payer: Pubkey,         // 32
+    pub count: u64,            // 8
+    // Phases don't overlap, so their start date tells them apart.
+    pub phase_start_date: i64, // 8
+    pub phase_count: u64,      // 8
+}
+
+impl MintCounter {
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8;
+
+    pub const PREFIX: &'static str = "mint_counter";
+
+    /// Number of items minted in `phase`.
+    pub fn count_in_phase(&self, phase: &MintPhase) -> u64 {
+        if self.phase_start_date == phase.start_date {
+            self.phase_count
+        } else {
+            0
+        }
+    }
+
+    pub fn increment(&mut self, phase: Option<&MintPhase>) -> Result<()> {
+        self.count = self
+            .count
+            .checked_add(1)
+            .ok_or(CandyError::NumericalOverflowError)?;
+        if let Some(phase) = phase {
+            self.phase_count = self
+                .count_in_phase(phase)
+                .checked_add(1)
+                .ok_or(CandyError::NumericalOverflowError)?;
+            self.phase_start_date = phase.start_date;
+        }
+        Ok(())
+    }
+}
+
+/// Mint phases PDA account. While it's set, the active phase takes the place of the go live date,
+/// price, whitelist and token mint of the candy machine.
+#[account]
+#[derive(Default, Debug)]
+pub struct MintPhasesPDA {
+    pub candy_machine: Pubkey,  // 32
+    pub phases: Vec<MintPhase>, // 4 + MAX_MINT_PHASES * MintPhase::SIZE
+}
+
+impl MintPhasesPDA {
+    pub const SIZE: usize = 8 + 32 + 4 + MAX_MINT_PHASES * MintPhase::SIZE;
+
+    pub const PREFIX: &'static str = "mint_phases";
+
+    pub fn assert_from_candy(&self, candy_machine: &Pubkey) -> Result<()> {
+        if &self.candy_machine != candy_machine {
+            return err!(CandyError::MintPhasesPDAMismatch);
+        }
+        Ok(())
+    }
+
+    pub fn assert_valid_phases(phases: &[MintPhase]) -> Result<()> {
+        if phases.is_empty() || phases.len() > MAX_MINT_PHASES {
+            return err!(CandyError::InvalidMintPhases);
+        }
+        for (i, phase) in phases.iter().enumerate() {
+            if let Some(end_date) = phase.end_date {
+                if end_date <= phase.start_date {
+                    return err!(CandyError::InvalidMintPhases);
+                }
+            }
+            if let Some(next) = phases.get(i + 1) {
+                if next.start_date <= phase.start_date
+                    || phase.end_date.map_or(false, |end| end > next.start_date)
+                {
+                    return err!(CandyError::InvalidMintPhases);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Index of the phase that started last, or of the first one if none started yet. Whether
+    /// it's live is up to [`crate::assert_valid_go_live`].
+    pub fn resolve_phase(&self, timestamp: i64) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| phase.start_date <= timestamp)
+            .unwrap_or(0)
+    }
+}
+
+/// A window of the mint with its own price, payment and gating.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct MintPhase {
+    pub start_date: i64,
+    /// If [`None`] the phase runs until the next one starts
+    pub end_date: Option<i64>,
+    pub price: u64,
+    /// If [`Some`] the price is paid in an SPL token instead of SOL
+    pub token_payment: Option<TokenPayment>,
+    /// If [`Some`] only minters passing the gate can mint in this phase
+    pub gate: Option<PhaseGate>,
+    /// If [`Some`] caps the number of items minted in this phase
+    pub supply_cap: Option<u64>,
+    /// If [`Some`] caps the number of items each payer can mint in this phase
+    pub mint_limit: Option<u64>,
+    /// Set by the program
+    pub items_redeemed: u64,
+}
+
+impl MintPhase {
+    pub const SIZE: usize = 8 + // start date
+        9 + // end date
+        8 + // price
+        1 + 32 + 32 + // token payment
+        1 + PhaseGate::SIZE + // gate
+        9 + // supply cap
+        9 + // mint limit
+        8; // items redeemed
+
+    pub fn is_live(&self, timestamp: i64) -> bool {
+        self.start_date <= timestamp && self.end_date.map_or(true, |end| timestamp < end)
+    }
+
+    /// Whether mints in this phase are counted per payer.
+    pub fn counts_mints(&self) -> bool {
+        self.mint_limit.is_some() || matches!(self.gate, Some(PhaseGate::MerkleAllowlist { .. }))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TokenPayment {
+    pub mint: Pubkey,
+    /// Token account of `mint` receiving the payments
+    pub destination: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum PhaseGate {
+    /// Holders of a whitelist token, which is burnt on mint with `BurnEveryTime`
+    WhitelistToken {
+        mint: Pubkey,
+        mode: WhitelistMintMode,
+    },
+    /// Wallets in the merkle tree with `root`, each up to the allowance of its leaf. Leaves are
+    /// built with [`crate::merkle_proof::allowlist_leaf`].
+    MerkleAllowlist { root: [u8; 32] },
+}
+
+impl PhaseGate {
+    // Variant index and the largest variant.
+    pub const SIZE: usize = 1 + 32 + 1;
+}
+
+/// Proof that the payer is on the merkle allowlist of the active mint phase.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    /// Number of items the payer can mint in the phase
+    pub allowance: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
 /// Candy machine settings data.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
 pub struct CandyMachineData {
@@ -105,6 +311,36 @@ impl CandyMachine {
             Ok(())
         }
     }
+
+    // Mint requests escrow the price in lamports and don't take remaining accounts, so the
+    // settings that need either aren't available with commit-reveal minting.
+    pub fn assert_commit_reveal_supported(&self) -> Result<()> {
+        if self.token_mint.is_some()
+            || self.data.gatekeeper.is_some()
+            || self.data.whitelist_mint_settings.is_some()
+            || is_feature_active(&self.data.uuid, FREEZE_FEATURE_INDEX)
+            || is_feature_active(&self.data.uuid, MINT_PHASES_FEATURE_INDEX)
+            || is_feature_active(&self.data.uuid, MINT_LIMIT_FEATURE_INDEX)
+        {
+            err!(CandyError::CommitRevealUnsupportedSettings)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Phases bring their own whitelist and token payment, and don't lay out the remaining
+    // accounts the way freeze and commit-reveal minting expect.
+    pub fn assert_mint_phases_supported(&self) -> Result<()> {
+        if self.token_mint.is_some()
+            || self.data.whitelist_mint_settings.is_some()
+            || is_feature_active(&self.data.uuid, FREEZE_FEATURE_INDEX)
+            || is_feature_active(&self.data.uuid, COMMIT_REVEAL_FEATURE_INDEX)
+        {
+            err!(CandyError::MintPhasesUnsupportedSettings)
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// Individual config line for storing NFT data pre-mint.
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use arrayref::array_ref;
 use solana_program::{
     account_info::AccountInfo,
     clock::Clock,
@@ -10,7 +11,7 @@ use solana_program::{
 };
 use spl_associated_token_account::get_associated_token_address;
 
-use crate::{constants::*, CandyError, CandyMachine};
+use crate::{constants::*, CandyError, CandyMachine, MintPhase};
 
 pub fn assert_initialized<T: Pack + IsInitialized>(account_info: &AccountInfo) -> Result<T> {
     let account: T = T::unpack_unchecked(&account_info.data.borrow())?;
@@ -25,11 +26,22 @@ pub fn cmp_pubkeys(a: &Pubkey, b: &Pubkey) -> bool {
     sol_memcmp(a.as_ref(), b.as_ref(), PUBKEY_BYTES) == 0
 }
 
+/// Checks that the candy machine is live, or the active mint phase if it has phases. The
+/// authority can always mint.
 pub fn assert_valid_go_live<'info>(
     payer: &Signer<'info>,
     clock: &Clock,
     candy_machine: &Account<'info, CandyMachine>,
+    phase: Option<&MintPhase>,
 ) -> Result<()> {
+    if let Some(phase) = phase {
+        if !phase.is_live(clock.unix_timestamp) && !cmp_pubkeys(payer.key, &candy_machine.authority)
+        {
+            return Err(CandyError::CandyMachineNotLive.into());
+        }
+        return Ok(());
+    }
+
     match candy_machine.data.go_live_date {
         None => {
             if !cmp_pubkeys(payer.key, &candy_machine.authority) {
@@ -46,6 +58,31 @@ pub fn assert_valid_go_live<'info>(
     Ok(())
 }
 
+// Size of a (slot, hash) entry of the SlotHashes sysvar.
+const SLOT_HASH_ENTRY_SIZE: usize = 8 + 32;
+
+/// Hash of the first slot after `slot` in the SlotHashes sysvar, which lists the most recent
+/// slots first. `None` if no later slot was produced yet, or if `slot` is older than the sysvar
+/// goes back.
+pub fn get_slot_hash_after(slot_hashes: &AccountInfo, slot: u64) -> Option<[u8; 32]> {
+    let data = slot_hashes.data.borrow();
+    if data.len() < 8 {
+        return None;
+    }
+    let len = (u64::from_le_bytes(*array_ref![data, 0, 8]) as usize)
+        .min((data.len() - 8) / SLOT_HASH_ENTRY_SIZE);
+
+    let mut later = None;
+    for i in 0..len {
+        let entry = 8 + i * SLOT_HASH_ENTRY_SIZE;
+        if u64::from_le_bytes(*array_ref![data, entry, 8]) <= slot {
+            return later;
+        }
+        later = Some(*array_ref![data, entry + 8, 32]);
+    }
+    None
+}
+
 pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> Result<()> {
     if !cmp_pubkeys(account.owner, owner) {
         Err(CandyError::IncorrectOwner.into())
@@ -213,6 +250,37 @@ pub fn remove_feature_flag(uuid: &mut str, feature_index: usize) {
     }
 }
 
+/// Creates `new_account` owned by this program, funded by `payer`. Works even if the address
+/// already holds lamports, which `create_account` refuses.
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    new_account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let required_lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(new_account.lamports());
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, new_account.key, required_lamports),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(new_account.key, &crate::id()),
+        &[new_account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
+
 pub fn punish_bots<'a>(
     error: CandyError,
     bot_account: AccountInfo<'a>,
@@ -285,6 +353,27 @@ pub mod tests {
         assert!(is_feature_active(&uuid, COLLECTIONS_FEATURE_INDEX));
     }
 
+    #[test]
+    fn slot_hash_after() {
+        let entries: [(u64, u8); 3] = [(12, 3), (11, 2), (9, 1)];
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[hash; 32]);
+        }
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let slot_hashes =
+            AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+
+        // Skipped slots aren't listed, so the next listed slot is used.
+        assert_eq!(get_slot_hash_after(&slot_hashes, 9), Some([2; 32]));
+        assert_eq!(get_slot_hash_after(&slot_hashes, 10), Some([2; 32]));
+        assert_eq!(get_slot_hash_after(&slot_hashes, 11), Some([3; 32]));
+        assert_eq!(get_slot_hash_after(&slot_hashes, 12), None);
+        assert_eq!(get_slot_hash_after(&slot_hashes, 5), None);
+    }
+
     #[test]
     fn check_keys_equal() {
         let key1 = Pubkey::new_unique();
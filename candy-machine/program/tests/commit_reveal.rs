@@ -0,0 +1,232 @@
+#![cfg(feature = "test-bpf")]
+#![allow(dead_code)]
+
+use solana_program::rent::Rent;
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+
+use mpl_candy_machine::{
+    constants::{BOT_FEE, COMMIT_REVEAL_FEATURE_INDEX, MINT_REQUEST_EXPIRY_SLOTS},
+    is_feature_active, MintRequest,
+    WhitelistMintMode::BurnEveryTime,
+};
+
+use crate::core::helpers::{
+    assert_account_empty, clone_keypair, get_balance, get_token_balance, new_funded_keypair,
+    prepare_nft,
+};
+use crate::utils::helpers::{
+    assert_candy_error, find_candy_creator, find_mint_request, test_start,
+};
+use crate::{
+    core::helpers::airdrop,
+    utils::{
+        auto_config, candy_machine_program_test, fulfill_mint, helpers::sol, CandyManager,
+        WhitelistConfig,
+    },
+};
+
+pub mod core;
+pub mod utils;
+
+async fn commit_reveal_candy_machine(context: &mut ProgramTestContext) -> CandyManager {
+    let mut candy_manager = CandyManager::init(context, Some(false), false, None, None, None).await;
+    airdrop(context, &candy_manager.minter.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+
+    let candy_data = auto_config(&candy_manager, Some(0), true, true, None, None);
+    candy_manager.create(context, candy_data).await.unwrap();
+    candy_manager.fill_config_lines(context).await.unwrap();
+    candy_manager.set_collection(context).await.unwrap();
+    candy_manager
+        .set_commit_reveal(context, true)
+        .await
+        .unwrap();
+    candy_manager
+}
+
+#[tokio::test]
+async fn commit_reveal_flow() {
+    test_start("Test Commit-Reveal Mint");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = commit_reveal_candy_machine(context).await;
+    let candy_machine = candy_manager.get_candy(context).await;
+    assert!(is_feature_active(
+        &candy_machine.data.uuid,
+        COMMIT_REVEAL_FEATURE_INDEX
+    ));
+
+    // Minting in one go isn't possible anymore.
+    let result = candy_manager.mint_nft(context).await;
+    assert_candy_error(result, 6050);
+
+    let mint_request_key = find_mint_request(
+        &candy_manager.candy_machine.pubkey(),
+        &candy_manager.minter.pubkey(),
+    );
+    let wallet_balance = get_balance(context, &candy_manager.wallet).await;
+    candy_manager.request_mint(context, [7; 32]).await.unwrap();
+    let mint_request = candy_manager.get_mint_request(context).await;
+    assert_eq!(mint_request.payer, candy_manager.minter.pubkey());
+    assert_eq!(mint_request.commitment, [7; 32]);
+    assert_eq!(mint_request.price, candy_machine.data.price);
+    let rent = Rent::default().minimum_balance(MintRequest::SIZE);
+    assert_eq!(
+        get_balance(context, &mint_request_key).await,
+        rent + candy_machine.data.price
+    );
+
+    // Only one request per payer can be pending.
+    candy_manager
+        .request_mint(context, [8; 32])
+        .await
+        .unwrap_err();
+
+    // Anyone can fulfill the request, and the NFT and the rent of the request go to its payer.
+    let crank = new_funded_keypair(context, sol(1.0)).await;
+    let minter_balance = get_balance(context, &candy_manager.minter.pubkey()).await;
+    let new_nft = candy_manager.fulfill_mint(context, &crank).await.unwrap();
+    assert_account_empty(context, &mint_request_key).await;
+    assert_eq!(
+        get_balance(context, &candy_manager.minter.pubkey()).await - minter_balance,
+        rent
+    );
+    assert_eq!(get_token_balance(context, &new_nft.token_account).await, 1);
+    assert_eq!(new_nft.owner.pubkey(), candy_manager.minter.pubkey());
+    assert_eq!(
+        get_balance(context, &candy_manager.wallet).await - wallet_balance,
+        candy_machine.data.price
+    );
+    assert_eq!(candy_manager.get_candy(context).await.items_redeemed, 1);
+
+    let metadata = new_nft.get_metadata(context).await;
+    let collection = metadata.collection.unwrap();
+    assert_eq!(collection.key, candy_manager.collection_info.mint.pubkey());
+    assert!(collection.verified);
+}
+
+#[tokio::test]
+async fn fulfill_mint_to_request_payer_only() {
+    test_start("Test Fulfill Mint To Request Payer Only");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = commit_reveal_candy_machine(context).await;
+    candy_manager.request_mint(context, [1; 32]).await.unwrap();
+
+    // A crank can't keep the NFT for itself.
+    let crank = new_funded_keypair(context, sol(1.0)).await;
+    let nft_info = prepare_nft(context, &crank).await;
+    let (candy_machine_creator, creator_bump) =
+        find_candy_creator(&candy_manager.candy_machine.pubkey());
+    let result = fulfill_mint(
+        context,
+        &candy_manager.candy_machine.pubkey(),
+        &candy_machine_creator,
+        creator_bump,
+        &candy_manager.wallet,
+        &candy_manager.authority.pubkey(),
+        &crank,
+        &candy_manager.minter.pubkey(),
+        &nft_info,
+        candy_manager.collection_info.clone(),
+    )
+    .await;
+    assert_candy_error(result, 6067);
+
+    candy_manager.fulfill_mint(context, &crank).await.unwrap();
+}
+
+#[tokio::test]
+async fn refund_expired_mint_request() {
+    test_start("Test Refund Expired Mint Request");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = commit_reveal_candy_machine(context).await;
+    let price = candy_manager.get_candy(context).await.data.price;
+    let anyone = new_funded_keypair(context, sol(1.0)).await;
+
+    candy_manager.request_mint(context, [1; 32]).await.unwrap();
+    let result = candy_manager.refund_mint_request(context, &anyone).await;
+    assert_candy_error(result, 6055);
+
+    let current_slot = context.banks_client.get_root_slot().await.unwrap();
+    context
+        .warp_to_slot(current_slot + MINT_REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+    let minter = clone_keypair(&candy_manager.minter);
+    let result = candy_manager.fulfill_mint(context, &minter).await;
+    assert_candy_error(result, 6054);
+
+    // Letting a request expire costs the bot fee, which goes to the candy machine.
+    let minter_balance = get_balance(context, &candy_manager.minter.pubkey()).await;
+    let candy_balance = get_balance(context, &candy_manager.candy_machine.pubkey()).await;
+    candy_manager
+        .refund_mint_request(context, &anyone)
+        .await
+        .unwrap();
+    let rent = Rent::default().minimum_balance(MintRequest::SIZE);
+    assert_eq!(
+        get_balance(context, &candy_manager.minter.pubkey()).await - minter_balance,
+        rent + price - BOT_FEE
+    );
+    assert_eq!(
+        get_balance(context, &candy_manager.candy_machine.pubkey()).await - candy_balance,
+        BOT_FEE
+    );
+    assert_eq!(candy_manager.get_candy(context).await.items_redeemed, 0);
+}
+
+#[tokio::test]
+async fn refund_withdrawn_candy_machine() {
+    test_start("Test Refund Mint Request Of Withdrawn Candy Machine");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = commit_reveal_candy_machine(context).await;
+    let price = candy_manager.get_candy(context).await.data.price;
+
+    candy_manager.request_mint(context, [1; 32]).await.unwrap();
+    candy_manager.withdraw(context).await.unwrap();
+
+    // The request can't be fulfilled anymore, so it's refunded in full.
+    let minter_balance = get_balance(context, &candy_manager.minter.pubkey()).await;
+    let anyone = new_funded_keypair(context, sol(1.0)).await;
+    candy_manager
+        .refund_mint_request(context, &anyone)
+        .await
+        .unwrap();
+    let rent = Rent::default().minimum_balance(MintRequest::SIZE);
+    assert_eq!(
+        get_balance(context, &candy_manager.minter.pubkey()).await - minter_balance,
+        rent + price
+    );
+}
+
+#[tokio::test]
+async fn commit_reveal_unsupported_settings() {
+    test_start("Test Commit-Reveal Unsupported Settings");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = CandyManager::init(
+        context,
+        None,
+        false,
+        None,
+        Some(WhitelistConfig::new(BurnEveryTime, false, None)),
+        None,
+    )
+    .await;
+    airdrop(context, &candy_manager.minter.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+
+    let candy_data = auto_config(&candy_manager, Some(0), true, true, None, None);
+    candy_manager.create(context, candy_data).await.unwrap();
+    candy_manager.fill_config_lines(context).await.unwrap();
+
+    let result = candy_manager.set_commit_reveal(context, true).await;
+    assert_candy_error(result, 6052);
+    let result = candy_manager.request_mint(context, [1; 32]).await;
+    assert_candy_error(result, 6051);
+}
@@ -306,9 +306,19 @@ pub async fn transfer(
 pub async fn prepare_nft(
     context: &mut ProgramTestContext,
     minter: &Keypair,
+) -> MasterEditionManager {
+    prepare_nft_for(context, minter, minter).await
+}
+
+/// Like `prepare_nft`, with the token minted by `minter` to `owner`.
+pub async fn prepare_nft_for(
+    context: &mut ProgramTestContext,
+    minter: &Keypair,
+    owner: &Keypair,
 ) -> MasterEditionManager {
     update_blockhash(context).await.expect("warp slot failed!");
-    let nft_info = metadata_manager::MetadataManager::new(minter);
+    let mut nft_info = metadata_manager::MetadataManager::new(minter);
+    nft_info.owner = clone_keypair(owner);
     create_mint(
         context,
         &minter.pubkey(),
@@ -322,7 +332,7 @@ pub async fn prepare_nft(
         context,
         &nft_info.mint.pubkey(),
         minter,
-        vec![(minter.pubkey(), 1)],
+        vec![(owner.pubkey(), 1)],
     )
     .await
     .unwrap();
@@ -69,6 +69,8 @@ async fn fail_metadata_not_blank() {
         candy_manager.collection_info.clone(),
         candy_manager.gateway_info.clone(),
         candy_manager.freeze_info.clone(),
+        candy_manager.mint_phases_info.clone(),
+        candy_manager.mint_limit_info.clone(),
     )
     .await
     .unwrap_err()
@@ -139,6 +141,8 @@ async fn metadata_check_before_bot_tax() {
         candy_manager.collection_info.clone(),
         candy_manager.gateway_info.clone(),
         candy_manager.freeze_info.clone(),
+        candy_manager.mint_phases_info.clone(),
+        candy_manager.mint_limit_info.clone(),
     );
 
     ix[0].accounts.pop();
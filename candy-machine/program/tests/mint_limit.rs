@@ -0,0 +1,129 @@
+#![cfg(feature = "test-bpf")]
+#![allow(dead_code)]
+
+use solana_program::clock::Clock;
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+
+use mpl_candy_machine::{constants::MINT_LIMIT_FEATURE_INDEX, is_feature_active, MintPhase};
+
+use crate::utils::helpers::{assert_candy_error, test_start};
+use crate::{
+    core::helpers::airdrop,
+    utils::{auto_config, candy_machine_program_test, helpers::sol, CandyManager},
+};
+
+pub mod core;
+pub mod utils;
+
+async fn init_candy_machine(context: &mut ProgramTestContext) -> CandyManager {
+    let mut candy_manager = CandyManager::init(context, None, false, None, None, None).await;
+    airdrop(context, &candy_manager.minter.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+
+    let candy_data = auto_config(&candy_manager, Some(0), true, true, None, None);
+    candy_manager.create(context, candy_data).await.unwrap();
+    candy_manager.fill_config_lines(context).await.unwrap();
+    candy_manager
+}
+
+#[tokio::test]
+async fn mint_limit_flow() {
+    test_start("Test Mint Limit");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context).await;
+
+    candy_manager.set_mint_limit(context, 2).await.unwrap();
+    let candy_machine = candy_manager.get_candy(context).await;
+    assert!(is_feature_active(
+        &candy_machine.data.uuid,
+        MINT_LIMIT_FEATURE_INDEX
+    ));
+    assert_eq!(
+        candy_manager.get_mint_limit_pda(context).await.mint_limit,
+        2
+    );
+
+    // The mint counter is created on the first mint.
+    for _ in 0..2 {
+        candy_manager
+            .mint_and_assert_successful(context, Some(candy_machine.data.price), false)
+            .await
+            .unwrap();
+    }
+    let mint_counter = candy_manager.get_mint_counter(context).await;
+    assert_eq!(
+        mint_counter.candy_machine,
+        candy_manager.candy_machine.pubkey()
+    );
+    assert_eq!(mint_counter.payer, candy_manager.minter.pubkey());
+    assert_eq!(mint_counter.count, 2);
+
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+
+    let result = candy_manager.withdraw(context).await;
+    assert_candy_error(result, 6065);
+    let result = candy_manager.set_commit_reveal(context, true).await;
+    assert_candy_error(result, 6052);
+
+    candy_manager.remove_mint_limit(context).await.unwrap();
+    candy_manager
+        .mint_and_assert_successful(context, Some(candy_machine.data.price), false)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mint_phase_mint_limit() {
+    test_start("Test Mint Phase Mint Limit");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context).await;
+
+    let now = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp;
+    let phase = MintPhase {
+        start_date: now - 100,
+        end_date: None,
+        price: sol(0.5),
+        token_payment: None,
+        gate: None,
+        supply_cap: None,
+        mint_limit: Some(1),
+        items_redeemed: 0,
+    };
+    candy_manager
+        .set_mint_phases(context, vec![phase])
+        .await
+        .unwrap();
+
+    // The mint counter is required once a phase has a mint limit.
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+
+    candy_manager.mint_limit_info.phase_limits = true;
+    candy_manager
+        .mint_and_assert_successful(context, Some(sol(0.5)), false)
+        .await
+        .unwrap();
+    let mint_counter = candy_manager.get_mint_counter(context).await;
+    assert_eq!(mint_counter.count, 1);
+    assert_eq!(mint_counter.phase_start_date, now - 100);
+    assert_eq!(mint_counter.phase_count, 1);
+
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+}
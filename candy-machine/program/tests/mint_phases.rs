@@ -0,0 +1,265 @@
+#![cfg(feature = "test-bpf")]
+#![allow(dead_code)]
+
+use solana_program::{clock::Clock, instruction::AccountMeta, keccak, pubkey::Pubkey};
+use solana_program_test::*;
+use solana_sdk::signer::Signer;
+
+use mpl_candy_machine::{
+    constants::MINT_PHASES_FEATURE_INDEX, is_feature_active, merkle_proof::allowlist_leaf,
+    AllowlistProof, MintPhase, PhaseGate, WhitelistMintMode::BurnEveryTime,
+};
+
+use crate::core::helpers::get_token_balance;
+use crate::utils::helpers::{assert_candy_error, test_start};
+use crate::{
+    core::helpers::airdrop,
+    utils::{auto_config, candy_machine_program_test, helpers::sol, CandyManager, WhitelistConfig},
+};
+
+pub mod core;
+pub mod utils;
+
+async fn now(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+fn phase(start_date: i64, end_date: Option<i64>, price: u64) -> MintPhase {
+    MintPhase {
+        start_date,
+        end_date,
+        price,
+        token_payment: None,
+        gate: None,
+        supply_cap: None,
+        mint_limit: None,
+        items_redeemed: 0,
+    }
+}
+
+async fn init_candy_machine(
+    context: &mut ProgramTestContext,
+    whitelist: Option<WhitelistConfig>,
+) -> CandyManager {
+    let mut candy_manager = CandyManager::init(context, None, false, None, whitelist, None).await;
+    airdrop(context, &candy_manager.minter.pubkey(), sol(10.0))
+        .await
+        .unwrap();
+    // The whitelist token is only used to gate phases.
+    candy_manager.whitelist_info.set = false;
+
+    let candy_data = auto_config(&candy_manager, Some(0), true, true, None, None);
+    candy_manager.create(context, candy_data).await.unwrap();
+    candy_manager.fill_config_lines(context).await.unwrap();
+    candy_manager
+}
+
+#[tokio::test]
+async fn mint_phases_flow() {
+    test_start("Test Mint Phases");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context, None).await;
+
+    let now = now(context).await;
+    let mut public = phase(now - 100, None, sol(0.5));
+    public.supply_cap = Some(1);
+    candy_manager
+        .set_mint_phases(
+            context,
+            vec![phase(now - 2000, Some(now - 1000), sol(0.1)), public],
+        )
+        .await
+        .unwrap();
+    let candy_machine = candy_manager.get_candy(context).await;
+    assert!(is_feature_active(
+        &candy_machine.data.uuid,
+        MINT_PHASES_FEATURE_INDEX
+    ));
+
+    // The public phase is the active one, so its price is paid.
+    candy_manager
+        .mint_and_assert_successful(context, Some(sol(0.5)), false)
+        .await
+        .unwrap();
+    let mint_phases = candy_manager.get_mint_phases_pda(context).await;
+    assert_eq!(mint_phases.phases[0].items_redeemed, 0);
+    assert_eq!(mint_phases.phases[1].items_redeemed, 1);
+
+    // Its supply cap is reached.
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+
+    let result = candy_manager.withdraw(context).await;
+    assert_candy_error(result, 6061);
+
+    // Without phases the settings of the candy machine apply again.
+    candy_manager.remove_mint_phases(context).await.unwrap();
+    candy_manager
+        .mint_and_assert_successful(context, Some(candy_machine.data.price), false)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mint_phase_not_live() {
+    test_start("Test Mint Phase Not Live");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context, None).await;
+
+    // Between two phases nothing is live.
+    let now = now(context).await;
+    candy_manager
+        .set_mint_phases(
+            context,
+            vec![
+                phase(now - 2000, Some(now - 1000), sol(0.1)),
+                phase(now + 10000, None, sol(0.5)),
+            ],
+        )
+        .await
+        .unwrap();
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn whitelist_gated_mint_phase() {
+    test_start("Test Whitelist Gated Mint Phase");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(
+        context,
+        Some(WhitelistConfig::new(BurnEveryTime, false, None)),
+    )
+    .await;
+
+    let now = now(context).await;
+    let whitelist_mint = candy_manager.whitelist_info.mint;
+    let whitelist_account = candy_manager.whitelist_info.minter_account;
+    let mut allowlist = phase(now - 100, None, sol(0.2));
+    allowlist.gate = Some(PhaseGate::WhitelistToken {
+        mint: whitelist_mint,
+        mode: BurnEveryTime,
+    });
+    candy_manager
+        .set_mint_phases(context, vec![allowlist])
+        .await
+        .unwrap();
+    candy_manager.mint_phases_info.phase_accounts = vec![
+        AccountMeta::new(whitelist_account, false),
+        AccountMeta::new(whitelist_mint, false),
+        AccountMeta::new_readonly(candy_manager.minter.pubkey(), true),
+    ];
+
+    candy_manager
+        .mint_and_assert_successful(context, Some(sol(0.2)), false)
+        .await
+        .unwrap();
+    assert_eq!(get_token_balance(context, &whitelist_account).await, 0);
+
+    // The only whitelist token was burnt.
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_mint_phases() {
+    test_start("Test Invalid Mint Phases");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context, None).await;
+
+    let now = now(context).await;
+    let result = candy_manager.set_mint_phases(context, vec![]).await;
+    assert_candy_error(result, 6056);
+    let result = candy_manager
+        .set_mint_phases(
+            context,
+            vec![
+                phase(now, Some(now + 1000), sol(0.1)),
+                phase(now + 500, None, sol(0.5)),
+            ],
+        )
+        .await;
+    assert_candy_error(result, 6056);
+    let result = candy_manager
+        .set_mint_phases(
+            context,
+            vec![phase(now + 500, None, sol(0.1)), phase(now, None, sol(0.5))],
+        )
+        .await;
+    assert_candy_error(result, 6056);
+}
+
+#[tokio::test]
+async fn merkle_allowlist_mint_phase() {
+    test_start("Test Merkle Allowlist Mint Phase");
+    let mut context = candy_machine_program_test().start_with_context().await;
+    let context = &mut context;
+    let mut candy_manager = init_candy_machine(context, None).await;
+
+    // Two leaf tree, hashed like gumdrop's.
+    let minter_leaf = allowlist_leaf(&candy_manager.minter.pubkey(), 2);
+    let other_leaf = allowlist_leaf(&Pubkey::new_unique(), 1);
+    let root = if minter_leaf <= other_leaf {
+        keccak::hashv(&[&[0x01], &minter_leaf, &other_leaf]).0
+    } else {
+        keccak::hashv(&[&[0x01], &other_leaf, &minter_leaf]).0
+    };
+
+    let now = now(context).await;
+    let mut allowlist = phase(now - 100, None, sol(0.2));
+    allowlist.gate = Some(PhaseGate::MerkleAllowlist { root });
+    candy_manager
+        .set_mint_phases(context, vec![allowlist])
+        .await
+        .unwrap();
+    candy_manager.mint_limit_info.phase_limits = true;
+    candy_manager.mint_phases_info.allowlist_proof = Some(AllowlistProof {
+        allowance: 2,
+        proof: vec![other_leaf],
+    });
+
+    for _ in 0..2 {
+        candy_manager
+            .mint_and_assert_successful(context, Some(sol(0.2)), false)
+            .await
+            .unwrap();
+    }
+    assert_eq!(candy_manager.get_mint_counter(context).await.phase_count, 2);
+
+    // The allowance is used up.
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+
+    // Claiming a larger allowance doesn't match the leaf.
+    candy_manager.mint_phases_info.allowlist_proof = Some(AllowlistProof {
+        allowance: 5,
+        proof: vec![other_leaf],
+    });
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+
+    candy_manager.mint_phases_info.allowlist_proof = None;
+    candy_manager
+        .mint_and_assert_bot_tax(context)
+        .await
+        .unwrap();
+}
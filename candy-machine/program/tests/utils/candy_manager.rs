@@ -5,8 +5,10 @@ use mpl_token_metadata::pda::find_collection_authority_account;
 use mpl_token_metadata::state::Metadata;
 use solana_gateway::state::{get_expire_address_with_seed, get_gateway_token_address_with_seed};
 use solana_program::clock::Clock;
+use solana_program::instruction::AccountMeta;
 use solana_program::program_option::COption;
 use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
     signature::{Keypair, Signer},
@@ -18,26 +20,31 @@ use spl_token::state::AccountState;
 use mpl_candy_machine::constants::{FREEZE_FEATURE_INDEX, FREEZE_LOCK_FEATURE_INDEX};
 use mpl_candy_machine::{
     constants::BOT_FEE,
-    is_feature_active, CandyMachine, CandyMachineData, CollectionPDA, FreezePDA, WhitelistMintMode,
+    is_feature_active, AllowlistProof, CandyMachine, CandyMachineData, CollectionPDA, FreezePDA,
+    MintCounter, MintLimitPDA, MintPhase, MintPhasesPDA, MintRequest, WhitelistMintMode,
     WhitelistMintMode::{BurnEveryTime, NeverBurn},
 };
 
 use crate::core::helpers::create_associated_token_account;
 use crate::utils::helpers::CandyTestLogger;
 use crate::utils::{
-    remove_freeze, set_freeze, thaw_nft, unlock_funds, update_authority, withdraw_funds,
+    fulfill_mint, refund_mint_request, remove_freeze, remove_mint_limit, remove_mint_phases,
+    request_mint, set_commit_reveal, set_freeze, set_mint_limit, set_mint_phases, thaw_nft,
+    unlock_funds, update_authority, withdraw_funds,
 };
 use crate::{
     core::{
         helpers::{
             airdrop, assert_account_empty, clone_keypair, create_mint, get_account, get_balance,
-            get_token_account, get_token_balance, mint_to_wallets, prepare_nft,
+            get_token_account, get_token_balance, mint_to_wallets, prepare_nft, prepare_nft_for,
         },
         MasterEditionManager, MetadataManager,
     },
     utils::{
         add_all_config_lines,
-        helpers::{find_candy_creator, find_collection_pda, sol},
+        helpers::{
+            find_candy_creator, find_collection_pda, find_mint_counter, find_mint_request, sol,
+        },
         initialize_candy_machine, mint_nft, remove_collection, set_collection,
         update_candy_machine,
     },
@@ -54,6 +61,8 @@ pub struct CandyManager {
     pub whitelist_info: WhitelistInfo,
     pub gateway_info: GatekeeperInfo,
     pub freeze_info: FreezeInfo,
+    pub mint_phases_info: MintPhasesInfo,
+    pub mint_limit_info: MintLimitInfo,
 }
 
 impl Clone for CandyManager {
@@ -68,6 +77,8 @@ impl Clone for CandyManager {
             whitelist_info: self.whitelist_info.clone(),
             gateway_info: self.gateway_info.clone(),
             freeze_info: self.freeze_info.clone(),
+            mint_phases_info: self.mint_phases_info.clone(),
+            mint_limit_info: self.mint_limit_info.clone(),
         }
     }
 }
@@ -375,6 +386,51 @@ impl FreezeInfo {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct MintPhasesInfo {
+    pub set: bool,
+    pub pda: Pubkey,
+    /// Accounts of the phase expected to be active when minting
+    pub phase_accounts: Vec<AccountMeta>,
+    /// If [`Some`] mints through `mint_nft_with_proof`
+    pub allowlist_proof: Option<AllowlistProof>,
+}
+
+impl MintPhasesInfo {
+    pub fn new(set: bool, candy_machine: &Pubkey) -> Self {
+        let seeds: &[&[u8]] = &[MintPhasesPDA::PREFIX.as_bytes(), candy_machine.as_ref()];
+        let pda = Pubkey::find_program_address(seeds, &mpl_candy_machine::ID).0;
+        MintPhasesInfo {
+            set,
+            pda,
+            phase_accounts: Vec::new(),
+            allowlist_proof: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MintLimitInfo {
+    pub set: bool,
+    pub pda: Pubkey,
+    /// Whether the mint phases have mint limits, which need the mint counter as well
+    pub phase_limits: bool,
+    pub mint_counter: Pubkey,
+}
+
+impl MintLimitInfo {
+    pub fn new(set: bool, candy_machine: &Pubkey, minter: &Pubkey) -> Self {
+        let seeds: &[&[u8]] = &[MintLimitPDA::PREFIX.as_bytes(), candy_machine.as_ref()];
+        let pda = Pubkey::find_program_address(seeds, &mpl_candy_machine::ID).0;
+        MintLimitInfo {
+            set,
+            pda,
+            phase_limits: false,
+            mint_counter: find_mint_counter(candy_machine, minter),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct FreezeConfig {
     pub set: bool,
@@ -501,6 +557,8 @@ impl CandyManager {
         whitelist_info: WhitelistInfo,
         gateway_info: GatekeeperInfo,
         freeze_info: FreezeInfo,
+        mint_phases_info: MintPhasesInfo,
+        mint_limit_info: MintLimitInfo,
     ) -> Self {
         CandyManager {
             candy_machine,
@@ -512,6 +570,8 @@ impl CandyManager {
             whitelist_info,
             gateway_info,
             freeze_info,
+            mint_phases_info,
+            mint_limit_info,
         }
     }
 
@@ -620,6 +680,9 @@ impl CandyManager {
             }
         };
 
+        let mint_phases_info = MintPhasesInfo::new(false, &candy_machine.pubkey());
+        let mint_limit_info = MintLimitInfo::new(false, &candy_machine.pubkey(), &minter.pubkey());
+
         let wallet = match &token_info.set {
             true => token_info.auth_account,
             false => authority.pubkey(),
@@ -635,6 +698,8 @@ impl CandyManager {
             whitelist_info,
             gateway_info,
             freeze_info,
+            mint_phases_info,
+            mint_limit_info,
         )
     }
 
@@ -653,6 +718,30 @@ impl CandyManager {
         FreezePDA::try_deserialize(&mut account.data.as_ref()).unwrap()
     }
 
+    pub async fn get_mint_phases_pda(&self, context: &mut ProgramTestContext) -> MintPhasesPDA {
+        let account = get_account(context, &self.mint_phases_info.pda).await;
+        MintPhasesPDA::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    pub async fn get_mint_limit_pda(&self, context: &mut ProgramTestContext) -> MintLimitPDA {
+        let account = get_account(context, &self.mint_limit_info.pda).await;
+        MintLimitPDA::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    pub async fn get_mint_counter(&self, context: &mut ProgramTestContext) -> MintCounter {
+        let account = get_account(context, &self.mint_limit_info.mint_counter).await;
+        MintCounter::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
+    pub async fn get_mint_request(&self, context: &mut ProgramTestContext) -> MintRequest {
+        let account = get_account(
+            context,
+            &find_mint_request(&self.candy_machine.pubkey(), &self.minter.pubkey()),
+        )
+        .await;
+        MintRequest::try_deserialize(&mut account.data.as_ref()).unwrap()
+    }
+
     pub async fn assert_freeze_set(
         &self,
         context: &mut ProgramTestContext,
@@ -934,12 +1023,165 @@ impl CandyManager {
             self.collection_info.clone(),
             self.gateway_info.clone(),
             self.freeze_info.clone(),
+            self.mint_phases_info.clone(),
+            self.mint_limit_info.clone(),
         )
         .await?;
         logger.end();
         Ok(nft_info)
     }
 
+    pub async fn set_mint_phases(
+        &mut self,
+        context: &mut ProgramTestContext,
+        phases: Vec<MintPhase>,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Set mint phases");
+        set_mint_phases(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.authority,
+            &self.mint_phases_info,
+            phases,
+        )
+        .await?;
+        self.mint_phases_info.set = true;
+        logger.end();
+        Ok(())
+    }
+
+    pub async fn remove_mint_phases(
+        &mut self,
+        context: &mut ProgramTestContext,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Remove mint phases");
+        remove_mint_phases(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.authority,
+            &self.mint_phases_info,
+        )
+        .await?;
+        self.mint_phases_info.set = false;
+        logger.end();
+        Ok(())
+    }
+
+    pub async fn set_mint_limit(
+        &mut self,
+        context: &mut ProgramTestContext,
+        mint_limit: u64,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Set mint limit");
+        set_mint_limit(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.authority,
+            &self.mint_limit_info,
+            mint_limit,
+        )
+        .await?;
+        self.mint_limit_info.set = true;
+        logger.end();
+        Ok(())
+    }
+
+    pub async fn remove_mint_limit(
+        &mut self,
+        context: &mut ProgramTestContext,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Remove mint limit");
+        remove_mint_limit(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.authority,
+            &self.mint_limit_info,
+        )
+        .await?;
+        self.mint_limit_info.set = false;
+        logger.end();
+        Ok(())
+    }
+
+    pub async fn set_commit_reveal(
+        &mut self,
+        context: &mut ProgramTestContext,
+        enabled: bool,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Set commit-reveal");
+        set_commit_reveal(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.authority,
+            enabled,
+        )
+        .await?;
+        logger.end();
+        Ok(())
+    }
+
+    pub async fn request_mint(
+        &mut self,
+        context: &mut ProgramTestContext,
+        commitment: [u8; 32],
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Request Mint");
+        request_mint(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.minter,
+            commitment,
+        )
+        .await?;
+        logger.end();
+        Ok(())
+    }
+
+    // Fulfills the mint request of `minter` on behalf of `fulfiller`, which mints the NFT to
+    // `minter`.
+    pub async fn fulfill_mint(
+        &mut self,
+        context: &mut ProgramTestContext,
+        fulfiller: &Keypair,
+    ) -> transport::Result<MasterEditionManager> {
+        let logger = CandyTestLogger::new_start("Fulfill Mint");
+        let nft_info = prepare_nft_for(context, fulfiller, &self.minter).await;
+        let (candy_machine_creator, creator_bump) =
+            find_candy_creator(&self.candy_machine.pubkey());
+        fulfill_mint(
+            context,
+            &self.candy_machine.pubkey(),
+            &candy_machine_creator,
+            creator_bump,
+            &self.wallet,
+            &self.authority.pubkey(),
+            fulfiller,
+            &self.minter.pubkey(),
+            &nft_info,
+            self.collection_info.clone(),
+        )
+        .await?;
+        logger.end();
+        Ok(nft_info)
+    }
+
+    pub async fn refund_mint_request(
+        &mut self,
+        context: &mut ProgramTestContext,
+        signer: &Keypair,
+    ) -> transport::Result<()> {
+        let logger = CandyTestLogger::new_start("Refund Mint Request");
+        refund_mint_request(
+            context,
+            &self.candy_machine.pubkey(),
+            &self.minter.pubkey(),
+            signer,
+        )
+        .await?;
+        logger.end();
+        Ok(())
+    }
+
     pub async fn withdraw(
         &mut self,
         context: &mut ProgramTestContext,
@@ -990,6 +1232,12 @@ impl CandyManager {
         let start_token_balance = get_token_balance(context, &self.token_info.minter_account).await;
         let start_whitelist_balance =
             get_token_balance(context, &self.whitelist_info.minter_account).await;
+        let creates_mint_counter = (self.mint_limit_info.set || self.mint_limit_info.phase_limits)
+            && context
+                .banks_client
+                .get_account(self.mint_limit_info.mint_counter)
+                .await?
+                .is_none();
         let mut new_nft = self.mint_nft(context).await.unwrap();
         let candy_end = self.get_candy(context).await;
         let end_balance = get_balance(context, &self.minter.pubkey()).await;
@@ -1042,6 +1290,9 @@ impl CandyManager {
                 let freeze_pda = self.get_freeze_pda(context).await;
                 fees += freeze_pda.freeze_fee;
             };
+            if creates_mint_counter {
+                fees += Rent::default().minimum_balance(MintCounter::SIZE);
+            }
             fees
         };
         if let Some(change) = balance_change {
@@ -10,15 +10,17 @@ use solana_sdk::{signature::Keypair, transaction::Transaction, transport};
 
 use mpl_candy_machine::{
     constants::{CONFIG_ARRAY_START, CONFIG_LINE_SIZE},
-    CandyMachine, CandyMachineData, ConfigLine,
+    CandyMachine, CandyMachineData, ConfigLine, MintPhase,
     WhitelistMintMode::BurnEveryTime,
 };
 
 use crate::{
     core::{helpers::update_blockhash, MasterEditionManager},
     utils::{
-        candy_manager::{CollectionInfo, GatekeeperInfo, TokenInfo, WhitelistInfo},
-        helpers::make_config_lines,
+        candy_manager::{
+            CollectionInfo, GatekeeperInfo, MintLimitInfo, MintPhasesInfo, TokenInfo, WhitelistInfo,
+        },
+        helpers::{find_mint_request, make_config_lines},
         FreezeInfo,
     },
 };
@@ -343,6 +345,75 @@ pub async fn set_freeze(
     context.banks_client.process_transaction(tx).await
 }
 
+pub async fn set_mint_phases(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    authority: &Keypair,
+    mint_phases_info: &MintPhasesInfo,
+    phases: Vec<MintPhase>,
+) -> transport::Result<()> {
+    let mut accounts = mpl_candy_machine::accounts::SetMintPhases {
+        candy_machine: *candy_machine,
+        authority: authority.pubkey(),
+        mint_phases_pda: mint_phases_info.pda,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    for phase in &phases {
+        if let Some(token_payment) = &phase.token_payment {
+            accounts.push(AccountMeta::new_readonly(token_payment.destination, false));
+        }
+    }
+
+    let data = mpl_candy_machine::instruction::SetMintPhases { phases }.data();
+    let set_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn remove_mint_phases(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    authority: &Keypair,
+    mint_phases_info: &MintPhasesInfo,
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::RemoveMintPhases {
+        candy_machine: *candy_machine,
+        authority: authority.pubkey(),
+        mint_phases_pda: mint_phases_info.pda,
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::RemoveMintPhases {}.data();
+    let remove_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[remove_ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
 pub async fn remove_freeze(
     context: &mut ProgramTestContext,
     candy_machine: &Pubkey,
@@ -499,6 +570,8 @@ pub fn mint_nft_ix(
     collection_info: CollectionInfo,
     gateway_info: GatekeeperInfo,
     freeze_info: FreezeInfo,
+    mint_phases_info: MintPhasesInfo,
+    mint_limit_info: MintLimitInfo,
 ) -> Vec<Instruction> {
     let metadata = new_nft.metadata_pubkey;
     let master_edition = new_nft.edition_pubkey;
@@ -535,6 +608,19 @@ pub fn mint_nft_ix(
         }
     }
 
+    if mint_phases_info.set {
+        accounts.push(AccountMeta::new(mint_phases_info.pda, false));
+    }
+    if mint_limit_info.set {
+        accounts.push(AccountMeta::new_readonly(mint_limit_info.pda, false));
+    }
+    if mint_limit_info.set || mint_limit_info.phase_limits {
+        accounts.push(AccountMeta::new(mint_limit_info.mint_counter, false));
+    }
+    if mint_phases_info.set {
+        accounts.extend(mint_phases_info.phase_accounts);
+    }
+
     if whitelist_info.set {
         accounts.push(AccountMeta::new(whitelist_info.minter_account, false));
         if whitelist_info.whitelist_config.burn == BurnEveryTime {
@@ -559,7 +645,14 @@ pub fn mint_nft_ix(
         }
     }
 
-    let data = mpl_candy_machine::instruction::MintNft { creator_bump }.data();
+    let data = match mint_phases_info.allowlist_proof {
+        Some(allowlist_proof) => mpl_candy_machine::instruction::MintNftWithProof {
+            creator_bump,
+            allowlist_proof,
+        }
+        .data(),
+        None => mpl_candy_machine::instruction::MintNft { creator_bump }.data(),
+    };
 
     let mut instructions = Vec::new();
 
@@ -571,38 +664,53 @@ pub fn mint_nft_ix(
     instructions.push(mint_ix);
 
     if collection_info.set {
-        let mut accounts = mpl_candy_machine::accounts::SetCollectionDuringMint {
-            candy_machine: *candy_machine,
-            metadata,
-            payer: payer.pubkey(),
-            collection_pda: collection_info.pda,
-            token_metadata_program: mpl_token_metadata::id(),
-            instructions: sysvar::instructions::id(),
-            collection_mint: collection_info.mint.pubkey(),
-            collection_metadata: collection_info.metadata,
-            collection_master_edition: collection_info.master_edition,
-            authority: *authority,
-            collection_authority_record: collection_info.authority_record,
-        }
-        .to_account_metas(None);
-        if collection_info.sized {
-            accounts
-                .iter_mut()
-                .find(|m| m.pubkey == collection_info.metadata)
-                .unwrap()
-                .is_writable = true;
-        }
-        let data = mpl_candy_machine::instruction::SetCollectionDuringMint {}.data();
-        let set_ix = Instruction {
-            program_id: mpl_candy_machine::id(),
-            data,
-            accounts,
-        };
-        instructions.push(set_ix)
+        instructions.push(set_collection_during_mint_ix(
+            candy_machine,
+            &metadata,
+            payer,
+            authority,
+            &collection_info,
+        ));
     }
     instructions
 }
 
+pub fn set_collection_during_mint_ix(
+    candy_machine: &Pubkey,
+    metadata: &Pubkey,
+    payer: &Keypair,
+    authority: &Pubkey,
+    collection_info: &CollectionInfo,
+) -> Instruction {
+    let mut accounts = mpl_candy_machine::accounts::SetCollectionDuringMint {
+        candy_machine: *candy_machine,
+        metadata: *metadata,
+        payer: payer.pubkey(),
+        collection_pda: collection_info.pda,
+        token_metadata_program: mpl_token_metadata::id(),
+        instructions: sysvar::instructions::id(),
+        collection_mint: collection_info.mint.pubkey(),
+        collection_metadata: collection_info.metadata,
+        collection_master_edition: collection_info.master_edition,
+        authority: *authority,
+        collection_authority_record: collection_info.authority_record,
+    }
+    .to_account_metas(None);
+    if collection_info.sized {
+        accounts
+            .iter_mut()
+            .find(|m| m.pubkey == collection_info.metadata)
+            .unwrap()
+            .is_writable = true;
+    }
+    let data = mpl_candy_machine::instruction::SetCollectionDuringMint {}.data();
+    Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn mint_nft(
     context: &mut ProgramTestContext,
@@ -618,6 +726,8 @@ pub async fn mint_nft(
     collection_info: CollectionInfo,
     gateway_info: GatekeeperInfo,
     freeze_info: FreezeInfo,
+    mint_phases_info: MintPhasesInfo,
+    mint_limit_info: MintLimitInfo,
 ) -> transport::Result<()> {
     let ins = mint_nft_ix(
         candy_machine,
@@ -632,6 +742,8 @@ pub async fn mint_nft(
         collection_info,
         gateway_info,
         freeze_info,
+        mint_phases_info,
+        mint_limit_info,
     );
     let signers = vec![payer];
     update_blockhash(context).await?;
@@ -644,3 +756,246 @@ pub async fn mint_nft(
 
     context.banks_client.process_transaction(tx).await
 }
+
+pub async fn set_mint_limit(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    authority: &Keypair,
+    mint_limit_info: &MintLimitInfo,
+    mint_limit: u64,
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::SetMintLimit {
+        candy_machine: *candy_machine,
+        authority: authority.pubkey(),
+        mint_limit_pda: mint_limit_info.pda,
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::SetMintLimit { mint_limit }.data();
+    let set_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn remove_mint_limit(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    authority: &Keypair,
+    mint_limit_info: &MintLimitInfo,
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::RemoveMintLimit {
+        candy_machine: *candy_machine,
+        authority: authority.pubkey(),
+        mint_limit_pda: mint_limit_info.pda,
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::RemoveMintLimit {}.data();
+    let remove_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[remove_ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn set_commit_reveal(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    authority: &Keypair,
+    enabled: bool,
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::SetCommitReveal {
+        candy_machine: *candy_machine,
+        authority: authority.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::SetCommitReveal { enabled }.data();
+    let set_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[set_ix],
+        Some(&authority.pubkey()),
+        &[authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn request_mint(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    payer: &Keypair,
+    commitment: [u8; 32],
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::RequestMint {
+        candy_machine: *candy_machine,
+        payer: payer.pubkey(),
+        mint_request: find_mint_request(candy_machine, &payer.pubkey()),
+        system_program: system_program::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::RequestMint { commitment }.data();
+    let request_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[request_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fulfill_mint_ix(
+    candy_machine: &Pubkey,
+    candy_creator_pda: &Pubkey,
+    creator_bump: u8,
+    wallet: &Pubkey,
+    authority: &Pubkey,
+    payer: &Keypair,
+    request_payer: &Pubkey,
+    new_nft: &MasterEditionManager,
+    collection_info: CollectionInfo,
+) -> Vec<Instruction> {
+    let metadata = new_nft.metadata_pubkey;
+
+    let accounts = mpl_candy_machine::accounts::FulfillMint {
+        candy_machine: *candy_machine,
+        candy_machine_creator: *candy_creator_pda,
+        payer: payer.pubkey(),
+        wallet: *wallet,
+        metadata,
+        mint: new_nft.mint.pubkey(),
+        mint_authority: payer.pubkey(),
+        update_authority: payer.pubkey(),
+        master_edition: new_nft.edition_pubkey,
+        mint_request: find_mint_request(candy_machine, request_payer),
+        request_payer: *request_payer,
+        token_account: new_nft.token_account,
+        token_metadata_program: mpl_token_metadata::id(),
+        token_program: spl_token::id(),
+        system_program: system_program::id(),
+        rent: sysvar::rent::id(),
+        slot_hashes: sysvar::slot_hashes::id(),
+        instruction_sysvar_account: sysvar::instructions::id(),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::FulfillMint { creator_bump }.data();
+    let mut instructions = vec![Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    }];
+
+    if collection_info.set {
+        instructions.push(set_collection_during_mint_ix(
+            candy_machine,
+            &metadata,
+            payer,
+            authority,
+            &collection_info,
+        ));
+    }
+    instructions
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn fulfill_mint(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    candy_creator_pda: &Pubkey,
+    creator_bump: u8,
+    wallet: &Pubkey,
+    authority: &Pubkey,
+    payer: &Keypair,
+    request_payer: &Pubkey,
+    new_nft: &MasterEditionManager,
+    collection_info: CollectionInfo,
+) -> transport::Result<()> {
+    let ins = fulfill_mint_ix(
+        candy_machine,
+        candy_creator_pda,
+        creator_bump,
+        wallet,
+        authority,
+        payer,
+        request_payer,
+        new_nft,
+        collection_info,
+    );
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &ins,
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+pub async fn refund_mint_request(
+    context: &mut ProgramTestContext,
+    candy_machine: &Pubkey,
+    payer: &Pubkey,
+    signer: &Keypair,
+) -> transport::Result<()> {
+    let accounts = mpl_candy_machine::accounts::RefundMintRequest {
+        candy_machine: *candy_machine,
+        payer: *payer,
+        mint_request: find_mint_request(candy_machine, payer),
+    }
+    .to_account_metas(None);
+
+    let data = mpl_candy_machine::instruction::RefundMintRequest {}.data();
+    let refund_ix = Instruction {
+        program_id: mpl_candy_machine::id(),
+        data,
+        accounts,
+    };
+    update_blockhash(context).await?;
+    let tx = Transaction::new_signed_with_payer(
+        &[refund_ix],
+        Some(&signer.pubkey()),
+        &[signer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
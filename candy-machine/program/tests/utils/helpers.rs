@@ -1,9 +1,19 @@
+use std::fmt::Debug;
+
 use console::style;
-use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use solana_program::{
+    instruction::InstructionError, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+};
+use solana_sdk::{
+    transaction::TransactionError,
+    transport::{self, TransportError},
+};
 use spl_associated_token_account::get_associated_token_address;
 
 use crate::utils::{FreezeInfo, TokenInfo};
-use mpl_candy_machine::{constants::PREFIX as CANDY_PREFIX, CollectionPDA, ConfigLine};
+use mpl_candy_machine::{
+    constants::PREFIX as CANDY_PREFIX, CollectionPDA, ConfigLine, MintCounter, MintRequest,
+};
 
 pub fn make_config_lines(start_index: u32, total: u8) -> Vec<ConfigLine> {
     let mut config_lines = Vec::with_capacity(total as usize);
@@ -28,10 +38,45 @@ pub fn find_collection_pda(candy_machine_key: &Pubkey) -> (Pubkey, u8) {
     )
 }
 
+pub fn find_mint_request(candy_machine_key: &Pubkey, payer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            MintRequest::PREFIX.as_bytes(),
+            candy_machine_key.as_ref(),
+            payer.as_ref(),
+        ],
+        &mpl_candy_machine::id(),
+    )
+    .0
+}
+
+pub fn find_mint_counter(candy_machine_key: &Pubkey, payer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            MintCounter::PREFIX.as_bytes(),
+            candy_machine_key.as_ref(),
+            payer.as_ref(),
+        ],
+        &mpl_candy_machine::id(),
+    )
+    .0
+}
+
 pub fn find_freeze_ata(freeze_info: &FreezeInfo, token_info: &TokenInfo) -> Pubkey {
     get_associated_token_address(&freeze_info.pda, &token_info.mint)
 }
 
+pub fn assert_candy_error<T: Debug>(result: transport::Result<T>, expected: u32) {
+    let err = match result.unwrap_err() {
+        TransportError::TransactionError(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(err_num),
+        )) => err_num,
+        _ => 0,
+    };
+    assert_eq!(err, expected);
+}
+
 pub fn sol(amount: f64) -> u64 {
     (amount * LAMPORTS_PER_SOL as f64) as u64
 }
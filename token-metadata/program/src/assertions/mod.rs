@@ -1,2 +1,3 @@
 pub mod collection;
+pub mod print_delegate;
 pub mod uses;
@@ -0,0 +1,44 @@
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    error::MetadataError,
+    state::{PrintDelegateRecord, PREFIX, PRINT_DELEGATE},
+    utils::assert_derivation,
+};
+
+pub fn assert_print_delegate_derivation(
+    program_id: &Pubkey,
+    print_delegate_record_info: &AccountInfo,
+    delegate_info: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let print_delegate_seeds = [
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint.as_ref(),
+        PRINT_DELEGATE.as_bytes(),
+        delegate_info.key.as_ref(),
+    ];
+    assert_derivation(
+        program_id,
+        print_delegate_record_info,
+        &print_delegate_seeds,
+    )
+    .map_err(|_| MetadataError::InvalidPrintDelegateRecord.into())
+}
+
+pub fn assert_valid_print_delegate(
+    canonical_bump: u8,
+    record: &PrintDelegateRecord,
+    master_token_owner: &Pubkey,
+) -> Result<(), ProgramError> {
+    if canonical_bump != record.bump || record.owner != *master_token_owner {
+        return Err(MetadataError::InvalidPrintDelegateRecord.into());
+    }
+
+    if record.allowed_prints == 0 {
+        return Err(MetadataError::NotEnoughPrints.into());
+    }
+
+    Ok(())
+}
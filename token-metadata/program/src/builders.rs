@@ -0,0 +1,1631 @@
+//! Typed builders for Token Metadata instructions.
+//!
+//! The functions in [`crate::instruction`] take every account as a positional `Pubkey`, which
+//! makes it easy to swap two keys of the same type. The builders here take only the keys that
+//! cannot be derived, fill in the Metadata, Master Edition, Edition Marker and authority record
+//! PDAs from [`crate::pda`], and fail on `build()` if a required account was never set.
+//!
+//! ```ignore
+//! let ix = VerifySizedCollectionItemBuilder::new(mint, collection_mint)
+//!     .collection_authority(authority)
+//!     .payer(payer)
+//!     .build()?;
+//! ```
+
+use crate::{
+    instruction,
+    pda::{
+        find_collection_authority_account, find_edition_marker_account,
+        find_master_edition_account, find_metadata_account, find_print_delegate_account,
+        find_program_as_burner_account, find_use_authority_account,
+    },
+    state::{CollectionDetails, DataV2},
+};
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use thiserror::Error;
+
+/// Errors returned when building an instruction.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum BuilderError {
+    /// A required account was not provided to the builder.
+    #[error("Missing required account: {0}")]
+    MissingAccount(&'static str),
+}
+
+fn required(key: Option<Pubkey>, name: &'static str) -> Result<Pubkey, BuilderError> {
+    key.ok_or(BuilderError::MissingAccount(name))
+}
+
+/// Builds a `CreateMetadataAccountV3` instruction, deriving the metadata PDA from the mint.
+pub struct CreateMetadataAccountV3Builder {
+    mint: Pubkey,
+    data: DataV2,
+    mint_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    update_authority: Option<Pubkey>,
+    update_authority_is_signer: bool,
+    is_mutable: bool,
+    collection_details: Option<CollectionDetails>,
+}
+
+impl CreateMetadataAccountV3Builder {
+    pub fn new(mint: Pubkey, data: DataV2) -> Self {
+        Self {
+            mint,
+            data,
+            mint_authority: None,
+            payer: None,
+            update_authority: None,
+            update_authority_is_signer: false,
+            is_mutable: true,
+            collection_details: None,
+        }
+    }
+
+    pub fn mint_authority(mut self, mint_authority: Pubkey) -> Self {
+        self.mint_authority = Some(mint_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey, is_signer: bool) -> Self {
+        self.update_authority = Some(update_authority);
+        self.update_authority_is_signer = is_signer;
+        self
+    }
+
+    pub fn is_mutable(mut self, is_mutable: bool) -> Self {
+        self.is_mutable = is_mutable;
+        self
+    }
+
+    pub fn collection_details(mut self, collection_details: CollectionDetails) -> Self {
+        self.collection_details = Some(collection_details);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::create_metadata_accounts_v3(
+            crate::id(),
+            metadata,
+            self.mint,
+            required(self.mint_authority, "mint_authority")?,
+            required(self.payer, "payer")?,
+            required(self.update_authority, "update_authority")?,
+            self.data.name,
+            self.data.symbol,
+            self.data.uri,
+            self.data.creators,
+            self.data.seller_fee_basis_points,
+            self.update_authority_is_signer,
+            self.is_mutable,
+            self.data.collection,
+            self.data.uses,
+            self.collection_details,
+        ))
+    }
+}
+
+/// Builds a `CreateMasterEditionV3` instruction, deriving the metadata and edition PDAs.
+pub struct CreateMasterEditionV3Builder {
+    mint: Pubkey,
+    update_authority: Option<Pubkey>,
+    mint_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    max_supply: Option<u64>,
+}
+
+impl CreateMasterEditionV3Builder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            update_authority: None,
+            mint_authority: None,
+            payer: None,
+            max_supply: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn mint_authority(mut self, mint_authority: Pubkey) -> Self {
+        self.mint_authority = Some(mint_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn max_supply(mut self, max_supply: u64) -> Self {
+        self.max_supply = Some(max_supply);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::create_master_edition_v3(
+            crate::id(),
+            edition,
+            self.mint,
+            required(self.update_authority, "update_authority")?,
+            required(self.mint_authority, "mint_authority")?,
+            metadata,
+            required(self.payer, "payer")?,
+            self.max_supply,
+        ))
+    }
+}
+
+/// Builds a `MintNewEditionFromMasterEditionViaToken` instruction, deriving the new metadata,
+/// new edition, master metadata, master edition and edition marker PDAs.
+pub struct MintNewEditionFromMasterEditionViaTokenBuilder {
+    new_mint: Pubkey,
+    master_mint: Pubkey,
+    edition: u64,
+    new_mint_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    token_account_owner: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+    new_metadata_update_authority: Option<Pubkey>,
+}
+
+impl MintNewEditionFromMasterEditionViaTokenBuilder {
+    pub fn new(new_mint: Pubkey, master_mint: Pubkey, edition: u64) -> Self {
+        Self {
+            new_mint,
+            master_mint,
+            edition,
+            new_mint_authority: None,
+            payer: None,
+            token_account_owner: None,
+            token_account: None,
+            new_metadata_update_authority: None,
+        }
+    }
+
+    pub fn new_mint_authority(mut self, new_mint_authority: Pubkey) -> Self {
+        self.new_mint_authority = Some(new_mint_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Owner of `token_account`, which must hold the master edition token.
+    pub fn token_account_owner(mut self, token_account_owner: Pubkey) -> Self {
+        self.token_account_owner = Some(token_account_owner);
+        self
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn new_metadata_update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.new_metadata_update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (new_metadata, _) = find_metadata_account(&self.new_mint);
+        let (new_edition, _) = find_master_edition_account(&self.new_mint);
+        let (metadata, _) = find_metadata_account(&self.master_mint);
+        let (master_edition, _) = find_master_edition_account(&self.master_mint);
+        Ok(instruction::mint_new_edition_from_master_edition_via_token(
+            crate::id(),
+            new_metadata,
+            new_edition,
+            master_edition,
+            self.new_mint,
+            required(self.new_mint_authority, "new_mint_authority")?,
+            required(self.payer, "payer")?,
+            required(self.token_account_owner, "token_account_owner")?,
+            required(self.token_account, "token_account")?,
+            required(
+                self.new_metadata_update_authority,
+                "new_metadata_update_authority",
+            )?,
+            metadata,
+            self.master_mint,
+            self.edition,
+        ))
+    }
+}
+
+/// Builds an `UpdateMetadataAccountV2` instruction, deriving the metadata PDA from the mint.
+pub struct UpdateMetadataAccountV2Builder {
+    mint: Pubkey,
+    update_authority: Option<Pubkey>,
+    new_update_authority: Option<Pubkey>,
+    data: Option<DataV2>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+impl UpdateMetadataAccountV2Builder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            update_authority: None,
+            new_update_authority: None,
+            data: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn new_update_authority(mut self, new_update_authority: Pubkey) -> Self {
+        self.new_update_authority = Some(new_update_authority);
+        self
+    }
+
+    pub fn data(mut self, data: DataV2) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    pub fn primary_sale_happened(mut self, primary_sale_happened: bool) -> Self {
+        self.primary_sale_happened = Some(primary_sale_happened);
+        self
+    }
+
+    pub fn is_mutable(mut self, is_mutable: bool) -> Self {
+        self.is_mutable = Some(is_mutable);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::update_metadata_accounts_v2(
+            crate::id(),
+            metadata,
+            required(self.update_authority, "update_authority")?,
+            self.new_update_authority,
+            self.data,
+            self.primary_sale_happened,
+            self.is_mutable,
+        ))
+    }
+}
+
+/// Builds a `SignMetadata` instruction, deriving the metadata PDA from the mint.
+pub struct SignMetadataBuilder {
+    mint: Pubkey,
+    creator: Pubkey,
+}
+
+impl SignMetadataBuilder {
+    pub fn new(mint: Pubkey, creator: Pubkey) -> Self {
+        Self { mint, creator }
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::sign_metadata(
+            crate::id(),
+            metadata,
+            self.creator,
+        ))
+    }
+}
+
+/// Builds a `RemoveCreatorVerification` instruction, deriving the metadata PDA from the mint.
+pub struct RemoveCreatorVerificationBuilder {
+    mint: Pubkey,
+    creator: Pubkey,
+}
+
+impl RemoveCreatorVerificationBuilder {
+    pub fn new(mint: Pubkey, creator: Pubkey) -> Self {
+        Self { mint, creator }
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::remove_creator_verification(
+            crate::id(),
+            metadata,
+            self.creator,
+        ))
+    }
+}
+
+/// Accounts shared by every collection verification instruction. The collection authority
+/// record is derived from the collection mint and authority when `delegated` is set.
+struct CollectionAccounts {
+    mint: Pubkey,
+    collection_mint: Pubkey,
+    collection_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    delegated: bool,
+}
+
+struct DerivedCollectionAccounts {
+    metadata: Pubkey,
+    collection_authority: Pubkey,
+    collection: Pubkey,
+    collection_master_edition_account: Pubkey,
+    collection_authority_record: Option<Pubkey>,
+}
+
+impl CollectionAccounts {
+    fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            mint,
+            collection_mint,
+            collection_authority: None,
+            payer: None,
+            delegated: false,
+        }
+    }
+
+    fn derive(&self) -> Result<DerivedCollectionAccounts, BuilderError> {
+        let collection_authority = required(self.collection_authority, "collection_authority")?;
+        let collection_authority_record = if self.delegated {
+            Some(find_collection_authority_account(&self.collection_mint, &collection_authority).0)
+        } else {
+            None
+        };
+
+        Ok(DerivedCollectionAccounts {
+            metadata: find_metadata_account(&self.mint).0,
+            collection_authority,
+            collection: find_metadata_account(&self.collection_mint).0,
+            collection_master_edition_account: find_master_edition_account(&self.collection_mint).0,
+            collection_authority_record,
+        })
+    }
+}
+
+/// Builds a `VerifyCollection` instruction.
+pub struct VerifyCollectionBuilder {
+    accounts: CollectionAccounts,
+}
+
+impl VerifyCollectionBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.accounts.payer = Some(payer);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::verify_collection(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            required(self.accounts.payer, "payer")?,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds an `UnverifyCollection` instruction.
+pub struct UnverifyCollectionBuilder {
+    accounts: CollectionAccounts,
+}
+
+impl UnverifyCollectionBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::unverify_collection(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds a `SetAndVerifyCollection` instruction.
+pub struct SetAndVerifyCollectionBuilder {
+    accounts: CollectionAccounts,
+    update_authority: Option<Pubkey>,
+}
+
+impl SetAndVerifyCollectionBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+            update_authority: None,
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.accounts.payer = Some(payer);
+        self
+    }
+
+    /// Update authority shared by the item and the collection.
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::set_and_verify_collection(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            required(self.accounts.payer, "payer")?,
+            required(self.update_authority, "update_authority")?,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds a `VerifySizedCollectionItem` instruction.
+pub struct VerifySizedCollectionItemBuilder {
+    accounts: CollectionAccounts,
+}
+
+impl VerifySizedCollectionItemBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.accounts.payer = Some(payer);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::verify_sized_collection_item(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            required(self.accounts.payer, "payer")?,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds an `UnverifySizedCollectionItem` instruction.
+pub struct UnverifySizedCollectionItemBuilder {
+    accounts: CollectionAccounts,
+}
+
+impl UnverifySizedCollectionItemBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.accounts.payer = Some(payer);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::unverify_sized_collection_item(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            required(self.accounts.payer, "payer")?,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds a `SetAndVerifySizedCollectionItem` instruction.
+pub struct SetAndVerifySizedCollectionItemBuilder {
+    accounts: CollectionAccounts,
+    update_authority: Option<Pubkey>,
+}
+
+impl SetAndVerifySizedCollectionItemBuilder {
+    pub fn new(mint: Pubkey, collection_mint: Pubkey) -> Self {
+        Self {
+            accounts: CollectionAccounts::new(mint, collection_mint),
+            update_authority: None,
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.accounts.collection_authority = Some(collection_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.accounts.payer = Some(payer);
+        self
+    }
+
+    /// Update authority shared by the item and the collection.
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.accounts.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let derived = self.accounts.derive()?;
+        Ok(instruction::set_and_verify_sized_collection_item(
+            crate::id(),
+            derived.metadata,
+            derived.collection_authority,
+            required(self.accounts.payer, "payer")?,
+            required(self.update_authority, "update_authority")?,
+            self.accounts.collection_mint,
+            derived.collection,
+            derived.collection_master_edition_account,
+            derived.collection_authority_record,
+        ))
+    }
+}
+
+/// Builds an `ApproveCollectionAuthority` instruction, deriving the record for the new authority.
+pub struct ApproveCollectionAuthorityBuilder {
+    collection_mint: Pubkey,
+    new_collection_authority: Pubkey,
+    update_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+}
+
+impl ApproveCollectionAuthorityBuilder {
+    pub fn new(collection_mint: Pubkey, new_collection_authority: Pubkey) -> Self {
+        Self {
+            collection_mint,
+            new_collection_authority,
+            update_authority: None,
+            payer: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) = find_collection_authority_account(
+            &self.collection_mint,
+            &self.new_collection_authority,
+        );
+        let (metadata, _) = find_metadata_account(&self.collection_mint);
+        Ok(instruction::approve_collection_authority(
+            crate::id(),
+            record,
+            self.new_collection_authority,
+            required(self.update_authority, "update_authority")?,
+            required(self.payer, "payer")?,
+            metadata,
+            self.collection_mint,
+        ))
+    }
+}
+
+/// Builds a `RevokeCollectionAuthority` instruction, deriving the record of the delegate.
+pub struct RevokeCollectionAuthorityBuilder {
+    collection_mint: Pubkey,
+    delegate_authority: Pubkey,
+    revoke_authority: Option<Pubkey>,
+}
+
+impl RevokeCollectionAuthorityBuilder {
+    pub fn new(collection_mint: Pubkey, delegate_authority: Pubkey) -> Self {
+        Self {
+            collection_mint,
+            delegate_authority,
+            revoke_authority: None,
+        }
+    }
+
+    /// Update authority of the collection, or the delegate itself.
+    pub fn revoke_authority(mut self, revoke_authority: Pubkey) -> Self {
+        self.revoke_authority = Some(revoke_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) =
+            find_collection_authority_account(&self.collection_mint, &self.delegate_authority);
+        let (metadata, _) = find_metadata_account(&self.collection_mint);
+        Ok(instruction::revoke_collection_authority(
+            crate::id(),
+            record,
+            self.delegate_authority,
+            required(self.revoke_authority, "revoke_authority")?,
+            metadata,
+            self.collection_mint,
+        ))
+    }
+}
+
+/// Builds an `ApproveUseAuthority` instruction, deriving the use authority record and burner.
+pub struct ApproveUseAuthorityBuilder {
+    mint: Pubkey,
+    user: Pubkey,
+    number_of_uses: u64,
+    owner: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    owner_token_account: Option<Pubkey>,
+}
+
+impl ApproveUseAuthorityBuilder {
+    pub fn new(mint: Pubkey, user: Pubkey, number_of_uses: u64) -> Self {
+        Self {
+            mint,
+            user,
+            number_of_uses,
+            owner: None,
+            payer: None,
+            owner_token_account: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn owner_token_account(mut self, owner_token_account: Pubkey) -> Self {
+        self.owner_token_account = Some(owner_token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) = find_use_authority_account(&self.mint, &self.user);
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (burner, _) = find_program_as_burner_account();
+        Ok(instruction::approve_use_authority(
+            crate::id(),
+            record,
+            self.user,
+            required(self.owner, "owner")?,
+            required(self.payer, "payer")?,
+            required(self.owner_token_account, "owner_token_account")?,
+            metadata,
+            self.mint,
+            burner,
+            self.number_of_uses,
+        ))
+    }
+}
+
+/// Builds a `RevokeUseAuthority` instruction, deriving the use authority record.
+pub struct RevokeUseAuthorityBuilder {
+    mint: Pubkey,
+    user: Pubkey,
+    owner: Option<Pubkey>,
+    owner_token_account: Option<Pubkey>,
+}
+
+impl RevokeUseAuthorityBuilder {
+    pub fn new(mint: Pubkey, user: Pubkey) -> Self {
+        Self {
+            mint,
+            user,
+            owner: None,
+            owner_token_account: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn owner_token_account(mut self, owner_token_account: Pubkey) -> Self {
+        self.owner_token_account = Some(owner_token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) = find_use_authority_account(&self.mint, &self.user);
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::revoke_use_authority(
+            crate::id(),
+            record,
+            self.user,
+            required(self.owner, "owner")?,
+            required(self.owner_token_account, "owner_token_account")?,
+            metadata,
+            self.mint,
+        ))
+    }
+}
+
+/// Builds a `Utilize` instruction. When the use authority is a delegate, the use authority
+/// record and burner are derived and appended.
+pub struct UtilizeBuilder {
+    mint: Pubkey,
+    number_of_uses: u64,
+    token_account: Option<Pubkey>,
+    use_authority: Option<Pubkey>,
+    owner: Option<Pubkey>,
+    delegated: bool,
+}
+
+impl UtilizeBuilder {
+    pub fn new(mint: Pubkey, number_of_uses: u64) -> Self {
+        Self {
+            mint,
+            number_of_uses,
+            token_account: None,
+            use_authority: None,
+            owner: None,
+            delegated: false,
+        }
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn use_authority(mut self, use_authority: Pubkey) -> Self {
+        self.use_authority = Some(use_authority);
+        self
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// The use authority is a delegate approved through `ApproveUseAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let use_authority = required(self.use_authority, "use_authority")?;
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (record, burner) = if self.delegated {
+            (
+                Some(find_use_authority_account(&self.mint, &use_authority).0),
+                Some(find_program_as_burner_account().0),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(instruction::utilize(
+            crate::id(),
+            metadata,
+            required(self.token_account, "token_account")?,
+            self.mint,
+            record,
+            use_authority,
+            required(self.owner, "owner")?,
+            burner,
+            self.number_of_uses,
+        ))
+    }
+}
+
+/// Builds a `FreezeDelegatedAccount` instruction, deriving the edition PDA from the mint.
+pub struct FreezeDelegatedAccountBuilder {
+    mint: Pubkey,
+    delegate: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+}
+
+impl FreezeDelegatedAccountBuilder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            delegate: None,
+            token_account: None,
+        }
+    }
+
+    pub fn delegate(mut self, delegate: Pubkey) -> Self {
+        self.delegate = Some(delegate);
+        self
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::freeze_delegated_account(
+            crate::id(),
+            required(self.delegate, "delegate")?,
+            required(self.token_account, "token_account")?,
+            edition,
+            self.mint,
+        ))
+    }
+}
+
+/// Builds a `ThawDelegatedAccount` instruction, deriving the edition PDA from the mint.
+pub struct ThawDelegatedAccountBuilder {
+    mint: Pubkey,
+    delegate: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+}
+
+impl ThawDelegatedAccountBuilder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            delegate: None,
+            token_account: None,
+        }
+    }
+
+    pub fn delegate(mut self, delegate: Pubkey) -> Self {
+        self.delegate = Some(delegate);
+        self
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::thaw_delegated_account(
+            crate::id(),
+            required(self.delegate, "delegate")?,
+            required(self.token_account, "token_account")?,
+            edition,
+            self.mint,
+        ))
+    }
+}
+
+/// Builds a `BurnNft` instruction, deriving the metadata and edition PDAs. The collection
+/// metadata is derived from the collection mint when one is given.
+pub struct BurnNftBuilder {
+    mint: Pubkey,
+    owner: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+    collection_mint: Option<Pubkey>,
+}
+
+impl BurnNftBuilder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            owner: None,
+            token_account: None,
+            collection_mint: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    /// Mint of the verified sized collection the NFT belongs to.
+    pub fn collection_mint(mut self, collection_mint: Pubkey) -> Self {
+        self.collection_mint = Some(collection_mint);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::burn_nft(
+            crate::id(),
+            metadata,
+            required(self.owner, "owner")?,
+            self.mint,
+            required(self.token_account, "token_account")?,
+            edition,
+            spl_token::id(),
+            self.collection_mint.map(|m| find_metadata_account(&m).0),
+        ))
+    }
+}
+
+/// Builds a `BurnEditionNft` instruction, deriving the print metadata, print edition, master
+/// edition and edition marker PDAs.
+pub struct BurnEditionNftBuilder {
+    print_edition_mint: Pubkey,
+    master_edition_mint: Pubkey,
+    edition: u64,
+    owner: Option<Pubkey>,
+    print_edition_token: Option<Pubkey>,
+    master_edition_token: Option<Pubkey>,
+}
+
+impl BurnEditionNftBuilder {
+    pub fn new(print_edition_mint: Pubkey, master_edition_mint: Pubkey, edition: u64) -> Self {
+        Self {
+            print_edition_mint,
+            master_edition_mint,
+            edition,
+            owner: None,
+            print_edition_token: None,
+            master_edition_token: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn print_edition_token(mut self, print_edition_token: Pubkey) -> Self {
+        self.print_edition_token = Some(print_edition_token);
+        self
+    }
+
+    pub fn master_edition_token(mut self, master_edition_token: Pubkey) -> Self {
+        self.master_edition_token = Some(master_edition_token);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.print_edition_mint);
+        let (print_edition, _) = find_master_edition_account(&self.print_edition_mint);
+        let (master_edition, _) = find_master_edition_account(&self.master_edition_mint);
+        let (edition_marker, _) =
+            find_edition_marker_account(&self.master_edition_mint, self.edition);
+        Ok(instruction::burn_edition_nft(
+            crate::id(),
+            metadata,
+            required(self.owner, "owner")?,
+            self.print_edition_mint,
+            self.master_edition_mint,
+            required(self.print_edition_token, "print_edition_token")?,
+            required(self.master_edition_token, "master_edition_token")?,
+            master_edition,
+            print_edition,
+            edition_marker,
+            spl_token::id(),
+        ))
+    }
+}
+
+/// Builds a `SetCollectionSize` instruction, deriving the collection metadata PDA.
+pub struct SetCollectionSizeBuilder {
+    collection_mint: Pubkey,
+    size: u64,
+    collection_authority: Option<Pubkey>,
+    delegated: bool,
+}
+
+impl SetCollectionSizeBuilder {
+    pub fn new(collection_mint: Pubkey, size: u64) -> Self {
+        Self {
+            collection_mint,
+            size,
+            collection_authority: None,
+            delegated: false,
+        }
+    }
+
+    pub fn collection_authority(mut self, collection_authority: Pubkey) -> Self {
+        self.collection_authority = Some(collection_authority);
+        self
+    }
+
+    /// The collection authority is a delegate approved through `ApproveCollectionAuthority`.
+    pub fn delegated(mut self) -> Self {
+        self.delegated = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let collection_authority = required(self.collection_authority, "collection_authority")?;
+        let (metadata, _) = find_metadata_account(&self.collection_mint);
+        let record = if self.delegated {
+            Some(find_collection_authority_account(&self.collection_mint, &collection_authority).0)
+        } else {
+            None
+        };
+
+        Ok(instruction::set_collection_size(
+            crate::id(),
+            metadata,
+            collection_authority,
+            self.collection_mint,
+            record,
+            self.size,
+        ))
+    }
+}
+
+/// Builds a `SetTokenStandard` instruction, deriving the metadata PDA and, when the mint has
+/// one, the edition PDA.
+pub struct SetTokenStandardBuilder {
+    mint: Pubkey,
+    update_authority: Option<Pubkey>,
+    has_edition: bool,
+}
+
+impl SetTokenStandardBuilder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            update_authority: None,
+            has_edition: false,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    /// The mint has a master edition or print edition account.
+    pub fn with_edition(mut self) -> Self {
+        self.has_edition = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let edition = if self.has_edition {
+            Some(find_master_edition_account(&self.mint).0)
+        } else {
+            None
+        };
+
+        Ok(instruction::set_token_standard(
+            crate::id(),
+            metadata,
+            required(self.update_authority, "update_authority")?,
+            self.mint,
+            edition,
+        ))
+    }
+}
+
+/// Builds a `SetPrintWindow` instruction, deriving the metadata and master edition PDAs.
+pub struct SetPrintWindowBuilder {
+    mint: Pubkey,
+    start: i64,
+    end: i64,
+    update_authority: Option<Pubkey>,
+}
+
+impl SetPrintWindowBuilder {
+    pub fn new(mint: Pubkey, start: i64, end: i64) -> Self {
+        Self {
+            mint,
+            start,
+            end,
+            update_authority: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (master_edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::set_print_window(
+            crate::id(),
+            master_edition,
+            required(self.update_authority, "update_authority")?,
+            metadata,
+            self.start,
+            self.end,
+        ))
+    }
+}
+
+/// Builds a `ClosePrintWindow` instruction, deriving the metadata and master edition PDAs.
+pub struct ClosePrintWindowBuilder {
+    mint: Pubkey,
+    update_authority: Option<Pubkey>,
+}
+
+impl ClosePrintWindowBuilder {
+    pub fn new(mint: Pubkey) -> Self {
+        Self {
+            mint,
+            update_authority: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (master_edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::close_print_window(
+            crate::id(),
+            master_edition,
+            required(self.update_authority, "update_authority")?,
+            metadata,
+        ))
+    }
+}
+
+/// Builds an `ApprovePrintDelegate` instruction, deriving the print delegate record.
+pub struct ApprovePrintDelegateBuilder {
+    mint: Pubkey,
+    delegate: Pubkey,
+    number_of_prints: u64,
+    owner: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    owner_token_account: Option<Pubkey>,
+}
+
+impl ApprovePrintDelegateBuilder {
+    pub fn new(mint: Pubkey, delegate: Pubkey, number_of_prints: u64) -> Self {
+        Self {
+            mint,
+            delegate,
+            number_of_prints,
+            owner: None,
+            payer: None,
+            owner_token_account: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    pub fn owner_token_account(mut self, owner_token_account: Pubkey) -> Self {
+        self.owner_token_account = Some(owner_token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) = find_print_delegate_account(&self.mint, &self.delegate);
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::approve_print_delegate(
+            crate::id(),
+            record,
+            self.delegate,
+            required(self.owner, "owner")?,
+            required(self.payer, "payer")?,
+            required(self.owner_token_account, "owner_token_account")?,
+            metadata,
+            self.mint,
+            self.number_of_prints,
+        ))
+    }
+}
+
+/// Builds a `RevokePrintDelegate` instruction, deriving the print delegate record.
+pub struct RevokePrintDelegateBuilder {
+    mint: Pubkey,
+    delegate: Pubkey,
+    owner: Option<Pubkey>,
+    owner_token_account: Option<Pubkey>,
+}
+
+impl RevokePrintDelegateBuilder {
+    pub fn new(mint: Pubkey, delegate: Pubkey) -> Self {
+        Self {
+            mint,
+            delegate,
+            owner: None,
+            owner_token_account: None,
+        }
+    }
+
+    pub fn owner(mut self, owner: Pubkey) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub fn owner_token_account(mut self, owner_token_account: Pubkey) -> Self {
+        self.owner_token_account = Some(owner_token_account);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (record, _) = find_print_delegate_account(&self.mint, &self.delegate);
+        let (metadata, _) = find_metadata_account(&self.mint);
+        Ok(instruction::revoke_print_delegate(
+            crate::id(),
+            record,
+            self.delegate,
+            required(self.owner, "owner")?,
+            required(self.owner_token_account, "owner_token_account")?,
+            metadata,
+            self.mint,
+        ))
+    }
+}
+
+/// Builds a `MintNewEditionFromMasterEditionViaPrintDelegate` instruction, deriving the
+/// new edition PDAs and the print delegate record.
+pub struct MintNewEditionFromMasterEditionViaPrintDelegateBuilder {
+    new_mint: Pubkey,
+    master_mint: Pubkey,
+    print_delegate: Pubkey,
+    edition: u64,
+    new_mint_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+    new_metadata_update_authority: Option<Pubkey>,
+}
+
+impl MintNewEditionFromMasterEditionViaPrintDelegateBuilder {
+    pub fn new(
+        new_mint: Pubkey,
+        master_mint: Pubkey,
+        print_delegate: Pubkey,
+        edition: u64,
+    ) -> Self {
+        Self {
+            new_mint,
+            master_mint,
+            print_delegate,
+            edition,
+            new_mint_authority: None,
+            payer: None,
+            token_account: None,
+            new_metadata_update_authority: None,
+        }
+    }
+
+    pub fn new_mint_authority(mut self, new_mint_authority: Pubkey) -> Self {
+        self.new_mint_authority = Some(new_mint_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Token account holding the master edition token, owned by whoever approved the delegate.
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn new_metadata_update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.new_metadata_update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (new_metadata, _) = find_metadata_account(&self.new_mint);
+        let (new_edition, _) = find_master_edition_account(&self.new_mint);
+        let (metadata, _) = find_metadata_account(&self.master_mint);
+        let (master_edition, _) = find_master_edition_account(&self.master_mint);
+        let (record, _) = find_print_delegate_account(&self.master_mint, &self.print_delegate);
+        Ok(
+            instruction::mint_new_edition_from_master_edition_via_print_delegate(
+                crate::id(),
+                new_metadata,
+                new_edition,
+                master_edition,
+                self.new_mint,
+                required(self.new_mint_authority, "new_mint_authority")?,
+                required(self.payer, "payer")?,
+                self.print_delegate,
+                record,
+                required(self.token_account, "token_account")?,
+                required(
+                    self.new_metadata_update_authority,
+                    "new_metadata_update_authority",
+                )?,
+                metadata,
+                self.master_mint,
+                self.edition,
+            ),
+        )
+    }
+}
+
+/// Builds a `SetReprintBurnedEditions` instruction, deriving the metadata and master edition PDAs.
+pub struct SetReprintBurnedEditionsBuilder {
+    mint: Pubkey,
+    enabled: bool,
+    update_authority: Option<Pubkey>,
+}
+
+impl SetReprintBurnedEditionsBuilder {
+    pub fn new(mint: Pubkey, enabled: bool) -> Self {
+        Self {
+            mint,
+            enabled,
+            update_authority: None,
+        }
+    }
+
+    pub fn update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (metadata, _) = find_metadata_account(&self.mint);
+        let (master_edition, _) = find_master_edition_account(&self.mint);
+        Ok(instruction::set_reprint_burned_editions(
+            crate::id(),
+            master_edition,
+            required(self.update_authority, "update_authority")?,
+            metadata,
+            self.enabled,
+        ))
+    }
+}
+
+/// Builds a `MintFreedEditionFromMasterEditionViaToken` instruction, deriving the new and
+/// master PDAs.
+pub struct MintFreedEditionFromMasterEditionViaTokenBuilder {
+    new_mint: Pubkey,
+    master_mint: Pubkey,
+    edition: u64,
+    new_mint_authority: Option<Pubkey>,
+    payer: Option<Pubkey>,
+    token_account_owner: Option<Pubkey>,
+    token_account: Option<Pubkey>,
+    new_metadata_update_authority: Option<Pubkey>,
+}
+
+impl MintFreedEditionFromMasterEditionViaTokenBuilder {
+    pub fn new(new_mint: Pubkey, master_mint: Pubkey, edition: u64) -> Self {
+        Self {
+            new_mint,
+            master_mint,
+            edition,
+            new_mint_authority: None,
+            payer: None,
+            token_account_owner: None,
+            token_account: None,
+            new_metadata_update_authority: None,
+        }
+    }
+
+    pub fn new_mint_authority(mut self, new_mint_authority: Pubkey) -> Self {
+        self.new_mint_authority = Some(new_mint_authority);
+        self
+    }
+
+    pub fn payer(mut self, payer: Pubkey) -> Self {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Owner of `token_account`, which must hold the master edition token.
+    pub fn token_account_owner(mut self, token_account_owner: Pubkey) -> Self {
+        self.token_account_owner = Some(token_account_owner);
+        self
+    }
+
+    pub fn token_account(mut self, token_account: Pubkey) -> Self {
+        self.token_account = Some(token_account);
+        self
+    }
+
+    pub fn new_metadata_update_authority(mut self, update_authority: Pubkey) -> Self {
+        self.new_metadata_update_authority = Some(update_authority);
+        self
+    }
+
+    pub fn build(self) -> Result<Instruction, BuilderError> {
+        let (new_metadata, _) = find_metadata_account(&self.new_mint);
+        let (new_edition, _) = find_master_edition_account(&self.new_mint);
+        let (metadata, _) = find_metadata_account(&self.master_mint);
+        let (master_edition, _) = find_master_edition_account(&self.master_mint);
+        Ok(
+            instruction::mint_freed_edition_from_master_edition_via_token(
+                crate::id(),
+                new_metadata,
+                new_edition,
+                master_edition,
+                self.new_mint,
+                required(self.new_mint_authority, "new_mint_authority")?,
+                required(self.payer, "payer")?,
+                required(self.token_account_owner, "token_account_owner")?,
+                required(self.token_account, "token_account")?,
+                required(
+                    self.new_metadata_update_authority,
+                    "new_metadata_update_authority",
+                )?,
+                metadata,
+                self.master_mint,
+                self.edition,
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda::find_edition_account;
+
+    #[test]
+    fn verify_sized_collection_item_matches_positional_fn() {
+        let mint = Pubkey::new_unique();
+        let collection_mint = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let built = VerifySizedCollectionItemBuilder::new(mint, collection_mint)
+            .collection_authority(authority)
+            .payer(payer)
+            .delegated()
+            .build()
+            .unwrap();
+
+        let expected = instruction::verify_sized_collection_item(
+            crate::id(),
+            find_metadata_account(&mint).0,
+            authority,
+            payer,
+            collection_mint,
+            find_metadata_account(&collection_mint).0,
+            find_master_edition_account(&collection_mint).0,
+            Some(find_collection_authority_account(&collection_mint, &authority).0),
+        );
+
+        assert_eq!(built, expected);
+    }
+
+    #[test]
+    fn mint_new_edition_derives_edition_marker() {
+        let new_mint = Pubkey::new_unique();
+        let master_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+
+        for edition in [1, 247, 248, 249, 496] {
+            let built =
+                MintNewEditionFromMasterEditionViaTokenBuilder::new(new_mint, master_mint, edition)
+                    .new_mint_authority(owner)
+                    .payer(owner)
+                    .token_account_owner(owner)
+                    .token_account(token_account)
+                    .new_metadata_update_authority(owner)
+                    .build()
+                    .unwrap();
+
+            let (marker, _) = find_edition_account(&master_mint, (edition / 248).to_string());
+            assert_eq!(built.accounts[4].pubkey, marker);
+        }
+    }
+
+    #[test]
+    fn missing_required_account_is_reported() {
+        let err = VerifySizedCollectionItemBuilder::new(Pubkey::new_unique(), Pubkey::new_unique())
+            .collection_authority(Pubkey::new_unique())
+            .build()
+            .unwrap_err();
+
+        assert_eq!(err, BuilderError::MissingAccount("payer"));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::state::{Collection, CollectionDetails, Data, Key, Metadata, TokenStandard, Uses};
+use crate::state::{
+    Collection, CollectionDetails, Data, Key, Metadata, TokenStandard, Uses,
+    CURRENT_METADATA_VERSION,
+};
 use borsh::{maybestd::io::Error as BorshError, BorshDeserialize, BorshSerialize};
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
 
@@ -29,6 +32,15 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
     let collection_details_res: Result<Option<CollectionDetails>, BorshError> =
         BorshDeserialize::deserialize(buf);
 
+    // Versioned
+    let version_res: Result<Option<u8>, BorshError> = BorshDeserialize::deserialize(buf);
+
+    // The version byte is only meaningful if every field before it was read from the right offset.
+    let tails_valid = token_standard_res.is_ok()
+        && collection_res.is_ok()
+        && uses_res.is_ok()
+        && collection_details_res.is_ok();
+
     /* We can have accidentally valid, but corrupted data, particularly on the Collection struct,
     so to increase probability of catching errors If any of these deserializations fail, set all values to None.
     */
@@ -45,6 +57,14 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         Err(_) => None,
     };
 
+    // Unknown versions can only come from stray bytes, so treat them as unversioned.
+    let version = match version_res {
+        Ok(Some(version)) if tails_valid && (1..=CURRENT_METADATA_VERSION).contains(&version) => {
+            Some(version)
+        }
+        _ => None,
+    };
+
     let metadata = Metadata {
         key,
         update_authority,
@@ -57,11 +77,19 @@ pub fn meta_deser_unchecked(buf: &mut &[u8]) -> Result<Metadata, BorshError> {
         collection,
         uses,
         collection_details,
+        version,
     };
 
     Ok(metadata)
 }
 
+/// Bring a Metadata read from any historical layout up to the current version.
+/// Corrupted tails have already been dropped by `meta_deser_unchecked`, so this only
+/// needs to stamp the version before the account is rewritten with `clean_write_metadata`.
+pub fn migrate_metadata(metadata: &mut Metadata) {
+    metadata.version = Some(CURRENT_METADATA_VERSION);
+}
+
 pub fn clean_write_metadata(
     metadata: &mut Metadata,
     metadata_account_info: &AccountInfo,
@@ -158,6 +186,7 @@ pub mod tests {
             collection: None,
             uses: None,
             collection_details: None,
+            version: None,
         };
 
         puff_out_data_fields(&mut metadata);
@@ -480,6 +480,42 @@ pub enum MetadataError {
     /// 121 - Print Edition doesn't match Master Edition
     #[error("Print Edition does not match Master Edition")]
     PrintEditionDoesNotMatchMasterEdition,
+
+    /// 122 - Print window has not opened yet
+    #[error("Print window has not opened yet")]
+    PrintWindowNotOpen,
+
+    /// 123 - Print window is closed
+    #[error("Print window is closed")]
+    PrintWindowClosed,
+
+    /// 124 - Invalid print window
+    #[error("Print window start must be before its end")]
+    InvalidPrintWindow,
+
+    /// 125 - Master Edition has no print window
+    #[error("This Master Edition has no print window")]
+    MissingPrintWindow,
+
+    /// 126 - Print delegate record already exists
+    #[error("The Print Delegate Record already exists, to modify it Revoke, then Approve")]
+    PrintDelegateRecordAlreadyExists,
+
+    /// 127 - Invalid print delegate record
+    #[error("The Print Delegate Record is invalid")]
+    InvalidPrintDelegateRecord,
+
+    /// 128 - Print delegate has no prints remaining
+    #[error("This Print Delegate has no prints remaining")]
+    NotEnoughPrints,
+
+    /// 129 - Reprinting burned editions is not enabled
+    #[error("This Master Edition does not allow reprinting burned editions")]
+    ReprintingNotEnabled,
+
+    /// 130 - Edition number was never printed
+    #[error("Edition number is greater than the highest edition ever printed")]
+    EditionNeverPrinted,
 }
 
 impl PrintProgramError for MetadataError {
@@ -118,6 +118,30 @@ pub struct SetCollectionSizeArgs {
     pub size: u64,
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ApprovePrintDelegateArgs {
+    pub number_of_prints: u64,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetReprintBurnedEditionsArgs {
+    pub enabled: bool,
+}
+
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetPrintWindowArgs {
+    /// Unix timestamp at which prints may first be minted.
+    pub start: i64,
+    /// Unix timestamp at which printing closes.
+    pub end: i64,
+}
+
 /// Instructions supported by the Metadata program.
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, Clone, ShankInstruction)]
@@ -540,6 +564,85 @@ pub enum MetadataInstruction {
     #[account(8, writable, name="edition_marker_account", desc="Edition Marker PDA of the NFT")]
     #[account(9, name="spl token program", desc="SPL Token Program")]
     BurnEditionNft,
+
+    /// Set the window during which prints can be minted from a Master Edition.
+    /// The window is kept in the unused end of the Master Edition V2 account.
+    #[account(0, writable, name="master_edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(1, signer, name="update_authority", desc="Metadata update authority")]
+    #[account(2, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    SetPrintWindow(SetPrintWindowArgs),
+
+    /// Close the print window of a Master Edition early, capping max supply at the current supply.
+    #[account(0, writable, name="master_edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(1, signer, name="update_authority", desc="Metadata update authority")]
+    #[account(2, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    ClosePrintWindow,
+
+    /// Approve another account to print editions from this Master Edition with [mint_new_edition_from_master_edition_via_print_delegate].
+    #[account(0, writable, name="print_delegate_record", desc="Print Delegate Record PDA (pda of ['metadata', program id, mint id, 'print_delegate', delegate])")]
+    #[account(1, signer, writable, name="owner", desc="Owner of the Master Edition token")]
+    #[account(2, signer, writable, name="payer", desc="Payer")]
+    #[account(3, name="delegate", desc="A Print Delegate")]
+    #[account(4, name="owner_token_account", desc="Token account holding the Master Edition token")]
+    #[account(5, name="metadata", desc="Metadata account")]
+    #[account(6, name="mint", desc="Mint of Metadata")]
+    #[account(7, name="system_program", desc="System program")]
+    ApprovePrintDelegate(ApprovePrintDelegateArgs),
+
+    /// Revoke a Print Delegate's right to print editions from this Master Edition.
+    #[account(0, writable, name="print_delegate_record", desc="Print Delegate Record PDA")]
+    #[account(1, signer, writable, name="owner", desc="Owner of the Master Edition token")]
+    #[account(2, name="delegate", desc="A Print Delegate")]
+    #[account(3, name="owner_token_account", desc="Token account holding the Master Edition token")]
+    #[account(4, name="metadata", desc="Metadata account")]
+    #[account(5, name="mint", desc="Mint of Metadata")]
+    RevokePrintDelegate,
+
+    /// Print a new edition as an approved Print Delegate, without holding the Master Edition token.
+    #[account(0, writable, name="new_metadata", desc="New Metadata key (pda of ['metadata', program id, mint id])")]
+    #[account(1, writable, name="new_edition", desc="New Edition (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, writable, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
+    #[account(3, writable, name="new_mint", desc="Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY")]
+    #[account(4, writable, name="edition_mark_pda", desc="Edition pda to mark creation - will be checked for pre-existence. (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number]) where edition_number is NOT the edition number you pass in args but actually edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).")]
+    #[account(5, signer, name="new_mint_authority", desc="Mint authority of new mint")]
+    #[account(6, signer, writable, name="payer", desc="payer")]
+    #[account(7, signer, name="print_delegate", desc="Approved Print Delegate")]
+    #[account(8, writable, name="print_delegate_record", desc="Print Delegate Record PDA")]
+    #[account(9, name="token_account", desc="token account containing token from master metadata mint")]
+    #[account(10, name="new_metadata_update_authority", desc="Update authority info for new metadata")]
+    #[account(11, name="metadata", desc="Master record metadata account")]
+    #[account(12, name="token_program", desc="Token program")]
+    #[account(13, name="system_program", desc="System program")]
+    MintNewEditionFromMasterEditionViaPrintDelegate(MintNewEditionFromMasterEditionViaTokenArgs),
+
+    /// Opt a Master Edition in or out of freeing edition numbers when prints are burned.
+    /// The setting is kept in the unused end of the Master Edition V2 account.
+    #[account(0, writable, name="master_edition", desc="Master Edition V2 (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(1, signer, name="update_authority", desc="Metadata update authority")]
+    #[account(2, name="metadata", desc="Metadata (pda of ['metadata', program id, mint id])")]
+    SetReprintBurnedEditions(SetReprintBurnedEditionsArgs),
+
+    /// Reprint a specific edition number that was freed by a burn. The Master Edition must have
+    /// reprinting enabled and the edition number must be at most the highest edition ever printed.
+    #[account(0, writable, name="new_metadata", desc="New Metadata key (pda of ['metadata', program id, mint id])")]
+    #[account(1, writable, name="new_edition", desc="New Edition (pda of ['metadata', program id, mint id, 'edition'])")]
+    #[account(2, writable, name="master_edition", desc="Master Record Edition V2 (pda of ['metadata', program id, master metadata mint id, 'edition'])")]
+    #[account(3, writable, name="new_mint", desc="Mint of new token - THIS WILL TRANSFER AUTHORITY AWAY FROM THIS KEY")]
+    #[account(4, writable, name="edition_mark_pda", desc="Edition pda to mark creation - will be checked for pre-existence. (pda of ['metadata', program id, master metadata mint id, 'edition', edition_number]) where edition_number is NOT the edition number you pass in args but actually edition_number = floor(edition/EDITION_MARKER_BIT_SIZE).")]
+    #[account(5, signer, name="new_mint_authority", desc="Mint authority of new mint")]
+    #[account(6, signer, writable, name="payer", desc="payer")]
+    #[account(7, signer, name="token_account_owner", desc="owner of token account containing master token (#8)")]
+    #[account(8, name="token_account", desc="token account containing token from master metadata mint")]
+    #[account(9, name="new_metadata_update_authority", desc="Update authority info for new metadata")]
+    #[account(10, name="metadata", desc="Master record metadata account")]
+    #[account(11, name="token_program", desc="Token program")]
+    #[account(12, name="system_program", desc="System program")]
+    MintFreedEditionFromMasterEditionViaToken(MintNewEditionFromMasterEditionViaTokenArgs),
+
+    /// Migrate a Metadata - rewrite any historical (or corrupted) layout cleanly as the current
+    /// layout version, dropping unreadable collection/uses bytes and any trailing data.
+    #[account(0, writable, name="metadata", desc="Metadata account")]
+    MigrateMetadata,
 }
 
 /// Creates an CreateMetadataAccounts instruction
@@ -1773,3 +1876,276 @@ pub fn set_token_standard(
         data,
     }
 }
+
+///# Set Print Window
+///
+///Set the window during which prints can be minted from a Master Edition
+///
+///### Accounts:
+///   0. `[writable]` Master Edition V2
+///   1. `[signer]` Metadata update authority
+///   2. `[]` Metadata
+pub fn set_print_window(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+    start: i64,
+    end: i64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(metadata, false),
+        ],
+        data: MetadataInstruction::SetPrintWindow(SetPrintWindowArgs { start, end })
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+///# Close Print Window
+///
+///Close the print window of a Master Edition early and cap its max supply at the current supply
+///
+///### Accounts:
+///   0. `[writable]` Master Edition V2
+///   1. `[signer]` Metadata update authority
+///   2. `[]` Metadata
+pub fn close_print_window(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(metadata, false),
+        ],
+        data: MetadataInstruction::ClosePrintWindow.try_to_vec().unwrap(),
+    }
+}
+
+///# Approve Print Delegate
+///
+///Approve another account to print editions from this Master Edition
+///
+///### Accounts:
+///   0. `[writable]` Print Delegate Record PDA
+///   1. `[signer]` Owner of the Master Edition token
+///   2. `[signer]` Payer
+///   3. `[]` A Print Delegate
+///   4. `[]` Token account holding the Master Edition token
+///   5. `[]` Metadata account
+///   6. `[]` Mint of Metadata
+///   7. `[]` System program
+#[allow(clippy::too_many_arguments)]
+pub fn approve_print_delegate(
+    program_id: Pubkey,
+    print_delegate_record: Pubkey,
+    delegate: Pubkey,
+    owner: Pubkey,
+    payer: Pubkey,
+    owner_token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+    number_of_prints: u64,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(print_delegate_record, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(delegate, false),
+            AccountMeta::new_readonly(owner_token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: MetadataInstruction::ApprovePrintDelegate(ApprovePrintDelegateArgs {
+            number_of_prints,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+///# Revoke Print Delegate
+///
+///Revoke a Print Delegate's right to print editions from this Master Edition
+///
+///### Accounts:
+///   0. `[writable]` Print Delegate Record PDA
+///   1. `[signer]` Owner of the Master Edition token
+///   2. `[]` A Print Delegate
+///   3. `[]` Token account holding the Master Edition token
+///   4. `[]` Metadata account
+///   5. `[]` Mint of Metadata
+pub fn revoke_print_delegate(
+    program_id: Pubkey,
+    print_delegate_record: Pubkey,
+    delegate: Pubkey,
+    owner: Pubkey,
+    owner_token_account: Pubkey,
+    metadata: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(print_delegate_record, false),
+            AccountMeta::new(owner, true),
+            AccountMeta::new_readonly(delegate, false),
+            AccountMeta::new_readonly(owner_token_account, false),
+            AccountMeta::new_readonly(metadata, false),
+            AccountMeta::new_readonly(mint, false),
+        ],
+        data: MetadataInstruction::RevokePrintDelegate
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// creates a mint_new_edition_from_master_edition_via_print_delegate instruction
+#[allow(clippy::too_many_arguments)]
+pub fn mint_new_edition_from_master_edition_via_print_delegate(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    print_delegate: Pubkey,
+    print_delegate_record: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let edition_number = edition.checked_div(EDITION_MARKER_BIT_SIZE).unwrap();
+    let as_string = edition_number.to_string();
+    let (edition_mark_pda, _) = Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata_mint.as_ref(),
+            EDITION.as_bytes(),
+            as_string.as_bytes(),
+        ],
+        &program_id,
+    );
+
+    let accounts = vec![
+        AccountMeta::new(new_metadata, false),
+        AccountMeta::new(new_edition, false),
+        AccountMeta::new(master_edition, false),
+        AccountMeta::new(new_mint, false),
+        AccountMeta::new(edition_mark_pda, false),
+        AccountMeta::new_readonly(new_mint_authority, true),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(print_delegate, true),
+        AccountMeta::new(print_delegate_record, false),
+        AccountMeta::new_readonly(token_account, false),
+        AccountMeta::new_readonly(new_metadata_update_authority, false),
+        AccountMeta::new_readonly(metadata, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+    ];
+
+    Instruction {
+        program_id,
+        accounts,
+        data: MetadataInstruction::MintNewEditionFromMasterEditionViaPrintDelegate(
+            MintNewEditionFromMasterEditionViaTokenArgs { edition },
+        )
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+///# Set Reprint Burned Editions
+///
+///Opt a Master Edition in or out of freeing edition numbers when prints are burned
+///
+///### Accounts:
+///   0. `[writable]` Master Edition V2
+///   1. `[signer]` Metadata update authority
+///   2. `[]` Metadata
+pub fn set_reprint_burned_editions(
+    program_id: Pubkey,
+    master_edition: Pubkey,
+    update_authority: Pubkey,
+    metadata: Pubkey,
+    enabled: bool,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(master_edition, false),
+            AccountMeta::new_readonly(update_authority, true),
+            AccountMeta::new_readonly(metadata, false),
+        ],
+        data: MetadataInstruction::SetReprintBurnedEditions(SetReprintBurnedEditionsArgs {
+            enabled,
+        })
+        .try_to_vec()
+        .unwrap(),
+    }
+}
+
+/// creates a mint_freed_edition_from_master_edition_via_token instruction
+#[allow(clippy::too_many_arguments)]
+pub fn mint_freed_edition_from_master_edition_via_token(
+    program_id: Pubkey,
+    new_metadata: Pubkey,
+    new_edition: Pubkey,
+    master_edition: Pubkey,
+    new_mint: Pubkey,
+    new_mint_authority: Pubkey,
+    payer: Pubkey,
+    token_account_owner: Pubkey,
+    token_account: Pubkey,
+    new_metadata_update_authority: Pubkey,
+    metadata: Pubkey,
+    metadata_mint: Pubkey,
+    edition: u64,
+) -> Instruction {
+    let mut instruction = mint_new_edition_from_master_edition_via_token(
+        program_id,
+        new_metadata,
+        new_edition,
+        master_edition,
+        new_mint,
+        new_mint_authority,
+        payer,
+        token_account_owner,
+        token_account,
+        new_metadata_update_authority,
+        metadata,
+        metadata_mint,
+        edition,
+    );
+    instruction.data = MetadataInstruction::MintFreedEditionFromMasterEditionViaToken(
+        MintNewEditionFromMasterEditionViaTokenArgs { edition },
+    )
+    .try_to_vec()
+    .unwrap();
+    instruction
+}
+
+/// migrate metadata account instruction
+pub fn migrate_metadata_account(program_id: Pubkey, metadata_account: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![AccountMeta::new(metadata_account, false)],
+        data: MetadataInstruction::MigrateMetadata.try_to_vec().unwrap(),
+    }
+}
@@ -1,12 +1,15 @@
 //! A Token Metadata program for the Solana blockchain.
 
 pub mod assertions;
+pub mod builders;
 pub mod deprecated_instruction;
 pub mod deprecated_processor;
 mod deser;
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
+#[cfg(test)]
+mod migration_test;
 pub mod pda;
 pub mod processor;
 pub mod state;
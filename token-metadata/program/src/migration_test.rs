@@ -0,0 +1,214 @@
+use borsh::BorshSerialize;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::{
+    deser::{
+        clean_write_metadata, migrate_metadata,
+        tests::{expected_pesky_metadata, pesky_data},
+    },
+    state::{
+        Collection, CollectionDetails, Creator, Data, Key, Metadata, TokenMetadataAccount,
+        TokenStandard, UseMethod, Uses, CURRENT_METADATA_VERSION, MAX_METADATA_LEN,
+    },
+    ID,
+};
+
+mod migrate_metadata {
+    use super::*;
+
+    fn base_metadata() -> Metadata {
+        Metadata {
+            key: Key::MetadataV1,
+            update_authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            data: Data {
+                name: "Historical".to_string(),
+                symbol: "HIST".to_string(),
+                uri: "https://example.com/historical.json".to_string(),
+                seller_fee_basis_points: 250,
+                creators: Some(vec![Creator {
+                    address: Pubkey::new_unique(),
+                    verified: true,
+                    share: 100,
+                }]),
+            },
+            primary_sale_happened: true,
+            is_mutable: true,
+            ..Metadata::default()
+        }
+    }
+
+    /// Fields written since V1.0: key through is_mutable.
+    fn v1_0_layout(metadata: &Metadata) -> Vec<u8> {
+        let mut buf = Vec::new();
+        metadata.key.serialize(&mut buf).unwrap();
+        metadata.update_authority.serialize(&mut buf).unwrap();
+        metadata.mint.serialize(&mut buf).unwrap();
+        metadata.data.serialize(&mut buf).unwrap();
+        metadata.primary_sale_happened.serialize(&mut buf).unwrap();
+        metadata.is_mutable.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    /// V1.1 appended the edition nonce.
+    fn v1_1_layout(metadata: &Metadata) -> Vec<u8> {
+        let mut buf = v1_0_layout(metadata);
+        metadata.edition_nonce.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    /// V1.2 appended token standard, collection and uses.
+    fn v1_2_layout(metadata: &Metadata) -> Vec<u8> {
+        let mut buf = v1_1_layout(metadata);
+        metadata.token_standard.serialize(&mut buf).unwrap();
+        metadata.collection.serialize(&mut buf).unwrap();
+        metadata.uses.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    /// V1.3 appended collection details.
+    fn v1_3_layout(metadata: &Metadata) -> Vec<u8> {
+        let mut buf = v1_2_layout(metadata);
+        metadata.collection_details.serialize(&mut buf).unwrap();
+        buf
+    }
+
+    fn with_v1_2_fields(mut metadata: Metadata) -> Metadata {
+        metadata.edition_nonce = Some(254);
+        metadata.token_standard = Some(TokenStandard::NonFungible);
+        metadata.collection = Some(Collection {
+            verified: true,
+            key: Pubkey::new_unique(),
+        });
+        metadata.uses = Some(Uses {
+            use_method: UseMethod::Multiple,
+            remaining: 3,
+            total: 5,
+        });
+        metadata
+    }
+
+    /// Runs `bytes` through MigrateMetadata and checks the result round-trips as `expected`
+    /// at the current version, and that migrating again changes nothing.
+    fn assert_migrates_to(bytes: &[u8], mut expected: Metadata) {
+        let mut data = bytes.to_vec();
+        data.resize(MAX_METADATA_LEN, 0);
+
+        let pubkey = Pubkey::new_unique();
+        let mut lamports = 1_000_000_000;
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &ID,
+            false,
+            1_000_000_000,
+        );
+
+        let mut metadata = Metadata::from_account_info(&account_info).unwrap();
+        migrate_metadata(&mut metadata);
+        clean_write_metadata(&mut metadata, &account_info).unwrap();
+
+        expected.version = Some(CURRENT_METADATA_VERSION);
+        let migrated = Metadata::from_account_info(&account_info).unwrap();
+        assert_eq!(migrated, expected);
+
+        // Only the serialized struct is left, everything after it is zeroed.
+        let serialized = expected.try_to_vec().unwrap();
+        let migrated_bytes = account_info.data.borrow().to_vec();
+        assert_eq!(&migrated_bytes[..serialized.len()], &serialized[..]);
+        assert!(migrated_bytes[serialized.len()..].iter().all(|b| *b == 0));
+
+        let mut metadata = Metadata::from_account_info(&account_info).unwrap();
+        migrate_metadata(&mut metadata);
+        clean_write_metadata(&mut metadata, &account_info).unwrap();
+        assert_eq!(account_info.data.borrow().to_vec(), migrated_bytes);
+    }
+
+    #[test]
+    fn v1_0_layout_migrates() {
+        let metadata = base_metadata();
+        assert_migrates_to(&v1_0_layout(&metadata), metadata);
+    }
+
+    #[test]
+    fn v1_1_layout_migrates() {
+        let mut metadata = base_metadata();
+        metadata.edition_nonce = Some(255);
+        assert_migrates_to(&v1_1_layout(&metadata), metadata);
+    }
+
+    #[test]
+    fn v1_2_layout_migrates() {
+        let metadata = with_v1_2_fields(base_metadata());
+        assert_migrates_to(&v1_2_layout(&metadata), metadata);
+    }
+
+    #[test]
+    fn v1_3_layout_migrates() {
+        let mut metadata = with_v1_2_fields(base_metadata());
+        metadata.collection_details = Some(CollectionDetails::V1 { size: 42 });
+        assert_migrates_to(&v1_3_layout(&metadata), metadata);
+    }
+
+    #[test]
+    fn current_layout_is_unchanged() {
+        let mut metadata = with_v1_2_fields(base_metadata());
+        metadata.collection_details = Some(CollectionDetails::V1 { size: 7 });
+        metadata.version = Some(CURRENT_METADATA_VERSION);
+        assert_migrates_to(&metadata.try_to_vec().unwrap(), metadata);
+    }
+
+    #[test]
+    fn corrupted_collection_is_dropped() {
+        let metadata = with_v1_2_fields(base_metadata());
+        let mut bytes = v1_1_layout(&metadata);
+        metadata.token_standard.serialize(&mut bytes).unwrap();
+        // Not a valid Option tag.
+        bytes.push(7);
+        bytes.extend_from_slice(&[1; 40]);
+
+        let mut expected = metadata;
+        expected.token_standard = None;
+        expected.collection = None;
+        expected.uses = None;
+        assert_migrates_to(&bytes, expected);
+    }
+
+    #[test]
+    fn corrupted_uses_are_dropped() {
+        let metadata = with_v1_2_fields(base_metadata());
+        let mut bytes = v1_1_layout(&metadata);
+        metadata.token_standard.serialize(&mut bytes).unwrap();
+        metadata.collection.serialize(&mut bytes).unwrap();
+        // Some(Uses) with a use method that does not exist.
+        bytes.extend_from_slice(&[1, 9]);
+        bytes.extend_from_slice(&[0xff; 16]);
+
+        let mut expected = metadata;
+        expected.token_standard = None;
+        expected.collection = None;
+        expected.uses = None;
+        assert_migrates_to(&bytes, expected);
+    }
+
+    #[test]
+    fn stray_version_byte_is_ignored() {
+        let metadata = with_v1_2_fields(base_metadata());
+        let mut bytes = v1_3_layout(&metadata);
+        // Some(u8) holding a version this program never wrote.
+        bytes.extend_from_slice(&[1, CURRENT_METADATA_VERSION + 1, 0xab, 0xcd]);
+
+        let unmigrated = crate::deser::meta_deser_unchecked(&mut bytes.as_slice()).unwrap();
+        assert_eq!(unmigrated.version, None);
+
+        assert_migrates_to(&bytes, metadata);
+    }
+
+    #[test]
+    fn resized_creators_corruption_migrates() {
+        assert_migrates_to(pesky_data(), expected_pesky_metadata());
+    }
+}
@@ -1,6 +1,8 @@
 use solana_program::pubkey::Pubkey;
 
-use crate::state::{BURN, COLLECTION_AUTHORITY, EDITION, PREFIX, USER};
+use crate::state::{
+    BURN, COLLECTION_AUTHORITY, EDITION, EDITION_MARKER_BIT_SIZE, PREFIX, PRINT_DELEGATE, USER,
+};
 
 pub fn find_edition_account(mint: &Pubkey, edition_number: String) -> (Pubkey, u8) {
     Pubkey::find_program_address(
@@ -14,6 +16,11 @@ pub fn find_edition_account(mint: &Pubkey, edition_number: String) -> (Pubkey, u
         &crate::id(),
     )
 }
+/// Derives the edition marker PDA that tracks `edition` for the master edition of `mint`.
+pub fn find_edition_marker_account(mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    find_edition_account(mint, (edition / EDITION_MARKER_BIT_SIZE).to_string())
+}
+
 pub fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
@@ -59,6 +66,19 @@ pub fn find_collection_authority_account(mint: &Pubkey, authority: &Pubkey) -> (
     )
 }
 
+pub fn find_print_delegate_account(mint: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PREFIX.as_bytes(),
+            crate::id().as_ref(),
+            mint.as_ref(),
+            PRINT_DELEGATE.as_bytes(),
+            delegate.as_ref(),
+        ],
+        &crate::id(),
+    )
+}
+
 pub fn find_program_as_burner_account() -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[PREFIX.as_bytes(), crate::id().as_ref(), BURN.as_bytes()],
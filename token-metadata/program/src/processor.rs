@@ -4,21 +4,24 @@ use crate::{
             assert_collection_update_is_valid, assert_collection_verify_is_valid,
             assert_has_collection_authority,
         },
+        print_delegate::{assert_print_delegate_derivation, assert_valid_print_delegate},
         uses::{assert_valid_use, process_use_authority_validation},
     },
     deprecated_processor::{
         process_deprecated_create_metadata_accounts, process_deprecated_update_metadata_accounts,
     },
-    deser::clean_write_metadata,
+    deser::{clean_write_metadata, migrate_metadata},
     error::MetadataError,
-    instruction::{MetadataInstruction, SetCollectionSizeArgs},
+    instruction::{MetadataInstruction, SetCollectionSizeArgs, SetPrintWindowArgs},
     solana_program::program_memory::sol_memset,
     state::{
-        Collection, CollectionAuthorityRecord, CollectionDetails, DataV2, Edition, EditionMarker,
-        Key, MasterEditionV1, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard,
+        get_master_edition, Collection, CollectionAuthorityRecord, CollectionDetails, DataV2,
+        Edition, EditionMarker, Key, MasterEditionPrintConfig, MasterEditionV1, MasterEditionV2,
+        Metadata, PrintDelegateRecord, PrintWindow, TokenMetadataAccount, TokenStandard,
         UseAuthorityRecord, UseMethod, Uses, BURN, COLLECTION_AUTHORITY,
         COLLECTION_AUTHORITY_RECORD_SIZE, EDITION, EDITION_MARKER_BIT_SIZE, MAX_MASTER_EDITION_LEN,
-        MAX_METADATA_LEN, PREFIX, USER, USE_AUTHORITY_RECORD_SIZE,
+        MAX_METADATA_LEN, PREFIX, PRINT_DELEGATE, PRINT_DELEGATE_RECORD_SIZE, USER,
+        USE_AUTHORITY_RECORD_SIZE,
     },
     utils::{
         assert_currently_holding, assert_data_valid, assert_delegated_tokens, assert_derivation,
@@ -42,11 +45,13 @@ use solana_program::rent::Rent;
 use solana_program::sysvar::SysvarId;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 use spl_token::{
     instruction::{approve, freeze_account, revoke, thaw_account},
@@ -247,6 +252,46 @@ pub fn process_instruction<'a>(
             msg!("Instruction: Bubblegum Program Set Collection Size");
             bubblegum_set_collection_size(program_id, accounts, args)
         }
+        MetadataInstruction::SetPrintWindow(args) => {
+            msg!("Instruction: Set Print Window");
+            process_set_print_window(program_id, accounts, args)
+        }
+        MetadataInstruction::ClosePrintWindow => {
+            msg!("Instruction: Close Print Window");
+            process_close_print_window(program_id, accounts)
+        }
+        MetadataInstruction::ApprovePrintDelegate(args) => {
+            msg!("Instruction: Approve Print Delegate");
+            process_approve_print_delegate(program_id, accounts, args.number_of_prints)
+        }
+        MetadataInstruction::RevokePrintDelegate => {
+            msg!("Instruction: Revoke Print Delegate");
+            process_revoke_print_delegate(program_id, accounts)
+        }
+        MetadataInstruction::MintNewEditionFromMasterEditionViaPrintDelegate(args) => {
+            msg!("Instruction: Mint New Edition from Master Edition Via Print Delegate");
+            process_mint_new_edition_from_master_edition_via_print_delegate(
+                program_id,
+                accounts,
+                args.edition,
+            )
+        }
+        MetadataInstruction::SetReprintBurnedEditions(args) => {
+            msg!("Instruction: Set Reprint Burned Editions");
+            process_set_reprint_burned_editions(program_id, accounts, args.enabled)
+        }
+        MetadataInstruction::MintFreedEditionFromMasterEditionViaToken(args) => {
+            msg!("Instruction: Mint Freed Edition from Master Edition Via Token");
+            process_mint_freed_edition_from_master_edition_via_token(
+                program_id,
+                accounts,
+                args.edition,
+            )
+        }
+        MetadataInstruction::MigrateMetadata => {
+            msg!("Instruction: Migrate Metadata");
+            process_migrate_metadata_account(program_id, accounts)
+        }
     }
 }
 
@@ -817,6 +862,21 @@ pub fn process_puff_metadata_account(
     Ok(())
 }
 
+pub fn process_migrate_metadata_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let metadata_account_info = next_account_info(account_info_iter)?;
+    assert_owned_by(metadata_account_info, program_id)?;
+
+    let mut metadata = Metadata::from_account_info(metadata_account_info)?;
+    migrate_metadata(&mut metadata);
+
+    clean_write_metadata(&mut metadata, metadata_account_info)
+}
+
 pub fn verify_collection(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let metadata_info = next_account_info(account_info_iter)?;
@@ -2021,14 +2081,22 @@ pub fn process_burn_edition_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -
 
     //       **EDITION HOUSEKEEPING**
     // Set the particular bit for this edition to 0 to allow reprinting,
-    // IF the print edition owner is also the master edition owner.
+    // IF the print edition owner is also the master edition owner, or the
+    // master edition opted in to reprinting burned editions.
     // Otherwise leave the bit set to 1 to disallow reprinting.
     let mut edition_marker: EditionMarker = EditionMarker::from_account_info(edition_marker_info)?;
     let owner_is_the_same = *owner_info.key == master_edition_token_account.owner;
+    let reprint_burned_editions = if master_edition_info.try_borrow_data()?[0]
+        == Key::MasterEditionV2 as u8
+    {
+        MasterEditionPrintConfig::from_account_info(master_edition_info)?.reprint_burned_editions
+    } else {
+        false
+    };
+    let edition_freed = owner_is_the_same || reprint_burned_editions;
 
-    if owner_is_the_same {
-        let (index, mask) = EditionMarker::get_index_and_mask(print_edition.edition)?;
-        edition_marker.ledger[index] ^= mask;
+    if edition_freed {
+        edition_marker.remove_edition(print_edition.edition)?;
     }
 
     // If the entire edition marker is empty, then we can close the account.
@@ -2052,24 +2120,25 @@ pub fn process_burn_edition_nft(program_id: &Pubkey, accounts: &[AccountInfo]) -
     }
 
     // Decrement the suppply on the master edition now that we've successfully burned a print.
-    // Decrement max_supply if Master Edition owner is not the same as Print Edition owner.
-    let mut master_edition: MasterEditionV2 =
-        MasterEditionV2::from_account_info(master_edition_info)?;
-    master_edition.supply = master_edition
-        .supply
-        .checked_sub(1)
-        .ok_or(MetadataError::NumericalOverflowError)?;
+    // Decrement max_supply if the edition number could not be freed for reprinting.
+    let mut master_edition = get_master_edition(master_edition_info)?;
+    master_edition.set_supply(
+        master_edition
+            .supply()
+            .checked_sub(1)
+            .ok_or(MetadataError::NumericalOverflowError)?,
+    );
 
-    if let Some(max_supply) = master_edition.max_supply {
-        if !owner_is_the_same {
-            master_edition.max_supply = Some(
+    if let Some(max_supply) = master_edition.max_supply() {
+        if !edition_freed {
+            master_edition.set_max_supply(Some(
                 max_supply
                     .checked_sub(1)
                     .ok_or(MetadataError::NumericalOverflowError)?,
-            );
+            ));
         }
     }
-    master_edition.serialize(&mut *master_edition_info.try_borrow_mut_data()?)?;
+    master_edition.save(master_edition_info)?;
 
     Ok(())
 }
@@ -2233,3 +2302,349 @@ pub fn set_token_standard(program_id: &Pubkey, accounts: &[AccountInfo]) -> Prog
     clean_write_metadata(&mut metadata, metadata_account_info)?;
     Ok(())
 }
+
+fn assert_master_edition_authority(
+    program_id: &Pubkey,
+    master_edition_info: &AccountInfo,
+    update_authority_info: &AccountInfo,
+    metadata_info: &AccountInfo,
+) -> ProgramResult {
+    assert_owned_by(master_edition_info, program_id)?;
+    assert_owned_by(metadata_info, program_id)?;
+    assert_signer(update_authority_info)?;
+
+    let metadata = Metadata::from_account_info(metadata_info)?;
+    assert_update_authority_is_correct(&metadata, update_authority_info)?;
+
+    assert_derivation(
+        program_id,
+        master_edition_info,
+        &[
+            PREFIX.as_bytes(),
+            program_id.as_ref(),
+            metadata.mint.as_ref(),
+            EDITION.as_bytes(),
+        ],
+    )
+    .map_err(|_| MetadataError::InvalidMasterEdition)?;
+
+    Ok(())
+}
+
+pub fn process_set_print_window(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetPrintWindowArgs,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+
+    assert_master_edition_authority(
+        program_id,
+        master_edition_info,
+        update_authority_info,
+        metadata_info,
+    )?;
+
+    if args.start >= args.end {
+        return Err(MetadataError::InvalidPrintWindow.into());
+    }
+
+    let mut print_config = MasterEditionPrintConfig::from_account_info(master_edition_info)
+        .map_err(|_| MetadataError::NotAMasterEdition)?;
+    print_config.print_window = Some(PrintWindow {
+        start: args.start,
+        end: args.end,
+    });
+    print_config.save(master_edition_info)?;
+
+    Ok(())
+}
+
+pub fn process_close_print_window(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+
+    assert_master_edition_authority(
+        program_id,
+        master_edition_info,
+        update_authority_info,
+        metadata_info,
+    )?;
+
+    let mut print_config = MasterEditionPrintConfig::from_account_info(master_edition_info)
+        .map_err(|_| MetadataError::MissingPrintWindow)?;
+    let window = print_config
+        .print_window
+        .ok_or(MetadataError::MissingPrintWindow)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    print_config.print_window = Some(PrintWindow {
+        start: window.start.min(now),
+        end: window.end.min(now),
+    });
+    print_config.save(master_edition_info)?;
+
+    // No more prints can ever be minted once the window is closed.
+    let mut master_edition = MasterEditionV2::from_account_info(master_edition_info)?;
+    master_edition.max_supply = Some(master_edition.supply);
+    master_edition.serialize(&mut *master_edition_info.try_borrow_mut_data()?)?;
+
+    Ok(())
+}
+
+pub fn process_approve_print_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    number_of_prints: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let print_delegate_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    assert_signer(owner_info)?;
+    assert_signer(payer)?;
+    assert_currently_holding(
+        program_id,
+        owner_info,
+        metadata_info,
+        &metadata,
+        mint_info,
+        token_account_info,
+    )?;
+    let bump_seed = assert_print_delegate_derivation(
+        program_id,
+        print_delegate_record_info,
+        delegate_info,
+        mint_info.key,
+    )?;
+    if !print_delegate_record_info.data_is_empty() {
+        return Err(MetadataError::PrintDelegateRecordAlreadyExists.into());
+    }
+    let print_delegate_seeds = &[
+        PREFIX.as_bytes(),
+        program_id.as_ref(),
+        mint_info.key.as_ref(),
+        PRINT_DELEGATE.as_bytes(),
+        delegate_info.key.as_ref(),
+        &[bump_seed],
+    ];
+    create_or_allocate_account_raw(
+        *program_id,
+        print_delegate_record_info,
+        system_account_info,
+        payer,
+        PRINT_DELEGATE_RECORD_SIZE,
+        print_delegate_seeds,
+    )?;
+
+    let record = PrintDelegateRecord {
+        key: Key::PrintDelegateRecord,
+        owner: *owner_info.key,
+        allowed_prints: number_of_prints,
+        bump: bump_seed,
+    };
+    record.serialize(&mut *print_delegate_record_info.try_borrow_mut_data()?)?;
+    Ok(())
+}
+
+pub fn process_revoke_print_delegate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let print_delegate_record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+    let metadata = Metadata::from_account_info(metadata_info)?;
+
+    assert_signer(owner_info)?;
+    assert_currently_holding(
+        program_id,
+        owner_info,
+        metadata_info,
+        &metadata,
+        mint_info,
+        token_account_info,
+    )?;
+    assert_owned_by(print_delegate_record_info, program_id)?;
+    assert_print_delegate_derivation(
+        program_id,
+        print_delegate_record_info,
+        delegate_info,
+        mint_info.key,
+    )?;
+    // Make sure the account really is a print delegate record before closing it.
+    PrintDelegateRecord::from_account_info(print_delegate_record_info)?;
+
+    let lamports = print_delegate_record_info.lamports();
+    **print_delegate_record_info.try_borrow_mut_lamports()? = 0;
+    **owner_info.try_borrow_mut_lamports()? = owner_info
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(MetadataError::NumericalOverflowError)?;
+    let data = &mut print_delegate_record_info.try_borrow_mut_data()?;
+    sol_memset(data, 0, PRINT_DELEGATE_RECORD_SIZE);
+    Ok(())
+}
+
+pub fn process_mint_new_edition_from_master_edition_via_print_delegate<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    edition: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let new_metadata_account_info = next_account_info(account_info_iter)?;
+    let new_edition_account_info = next_account_info(account_info_iter)?;
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_marker_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let print_delegate_info = next_account_info(account_info_iter)?;
+    let print_delegate_record_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+
+    assert_signer(print_delegate_info)?;
+    assert_owned_by(master_metadata_account_info, program_id)?;
+    assert_owned_by(print_delegate_record_info, program_id)?;
+    assert_owned_by(token_account_info, &spl_token::id())?;
+
+    let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
+    let canonical_bump = assert_print_delegate_derivation(
+        program_id,
+        print_delegate_record_info,
+        print_delegate_info,
+        &master_metadata.mint,
+    )?;
+    let mut record = PrintDelegateRecord::from_account_info(print_delegate_record_info)?;
+    let token_account: Account = assert_initialized(token_account_info)?;
+    assert_valid_print_delegate(canonical_bump, &record, &token_account.owner)?;
+
+    record.allowed_prints = record
+        .allowed_prints
+        .checked_sub(1)
+        .ok_or(MetadataError::NotEnoughPrints)?;
+    record.serialize(&mut *print_delegate_record_info.try_borrow_mut_data()?)?;
+
+    // The delegate record stands in for the owner's signature.
+    process_mint_new_edition_from_master_edition_via_token_logic(
+        program_id,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs {
+            new_metadata_account_info,
+            new_edition_account_info,
+            master_edition_account_info,
+            mint_info,
+            edition_marker_info,
+            mint_authority_info,
+            payer_account_info,
+            owner_account_info: print_delegate_info,
+            token_account_info,
+            update_authority_info,
+            master_metadata_account_info,
+            token_program_account_info,
+            system_account_info,
+        },
+        edition,
+        true,
+    )
+}
+
+pub fn process_set_reprint_burned_editions(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let master_edition_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let metadata_info = next_account_info(account_info_iter)?;
+
+    assert_master_edition_authority(
+        program_id,
+        master_edition_info,
+        update_authority_info,
+        metadata_info,
+    )?;
+
+    let mut print_config = MasterEditionPrintConfig::from_account_info(master_edition_info)
+        .map_err(|_| MetadataError::NotAMasterEdition)?;
+    print_config.reprint_burned_editions = enabled;
+    print_config.save(master_edition_info)?;
+
+    Ok(())
+}
+
+pub fn process_mint_freed_edition_from_master_edition_via_token<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    edition: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let new_metadata_account_info = next_account_info(account_info_iter)?;
+    let new_edition_account_info = next_account_info(account_info_iter)?;
+    let master_edition_account_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let edition_marker_info = next_account_info(account_info_iter)?;
+    let mint_authority_info = next_account_info(account_info_iter)?;
+    let payer_account_info = next_account_info(account_info_iter)?;
+    let owner_account_info = next_account_info(account_info_iter)?;
+    let token_account_info = next_account_info(account_info_iter)?;
+    let update_authority_info = next_account_info(account_info_iter)?;
+    let master_metadata_account_info = next_account_info(account_info_iter)?;
+    let token_program_account_info = next_account_info(account_info_iter)?;
+    let system_account_info = next_account_info(account_info_iter)?;
+
+    assert_owned_by(master_edition_account_info, program_id)?;
+    let print_config = MasterEditionPrintConfig::from_account_info(master_edition_account_info)
+        .map_err(|_| MetadataError::ReprintingNotEnabled)?;
+    if !print_config.reprint_burned_editions {
+        return Err(MetadataError::ReprintingNotEnabled.into());
+    }
+
+    // Only numbers that were printed can have been burned and freed.
+    if edition > print_config.highest_printed_edition {
+        return Err(MetadataError::EditionNeverPrinted.into());
+    }
+
+    process_mint_new_edition_from_master_edition_via_token_logic(
+        program_id,
+        MintNewEditionFromMasterEditionViaTokenLogicArgs {
+            new_metadata_account_info,
+            new_edition_account_info,
+            master_edition_account_info,
+            mint_info,
+            edition_marker_info,
+            mint_authority_info,
+            payer_account_info,
+            owner_account_info,
+            token_account_info,
+            update_authority_info,
+            master_metadata_account_info,
+            token_program_account_info,
+            system_account_info,
+        },
+        edition,
+        false,
+    )
+}
@@ -35,12 +35,17 @@ pub const BURN: &str = "burn";
 
 pub const COLLECTION_AUTHORITY: &str = "collection_authority";
 
+pub const PRINT_DELEGATE: &str = "print_delegate";
+
 pub const MAX_NAME_LENGTH: usize = 32;
 
 pub const MAX_SYMBOL_LENGTH: usize = 10;
 
 pub const MAX_URI_LENGTH: usize = 200;
 
+/// Layout version written into new and migrated Metadata accounts.
+pub const CURRENT_METADATA_VERSION: u8 = 1;
+
 pub const MAX_METADATA_LEN: usize = 1 //key 
 + 32 // update auth pubkey
 + 32 // mint pubkey
@@ -71,6 +76,10 @@ pub const MAX_EDITION_LEN: usize = 1 + 32 + 8 + 200;
 // by rewriting it.
 pub const MAX_MASTER_EDITION_LEN: usize = 1 + 9 + 8 + 264;
 
+// Past the largest fields any Master Edition version writes, the two pubkeys of V1 included, so
+// these bytes are zero in every account that never had a print configuration.
+pub const MASTER_EDITION_PRINT_CONFIG_OFFSET: usize = 1 + 9 + 8 + 32 + 32;
+
 pub const MAX_CREATOR_LIMIT: usize = 5;
 
 pub const MAX_CREATOR_LEN: usize = 32 + 1 + 1;
@@ -91,6 +100,8 @@ pub const USE_AUTHORITY_RECORD_SIZE: usize = 18; //8 byte padding
 
 pub const COLLECTION_AUTHORITY_RECORD_SIZE: usize = 11; //10 byte padding
 
+pub const PRINT_DELEGATE_RECORD_SIZE: usize = 50; //8 byte padding
+
 pub trait TokenMetadataAccount: BorshDeserialize {
     fn key() -> Key;
 
@@ -154,6 +165,7 @@ pub enum Key {
     EditionMarker,
     UseAuthorityRecord,
     CollectionAuthorityRecord,
+    PrintDelegateRecord,
 }
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -319,6 +331,40 @@ impl CollectionAuthorityRecord {
     }
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, ShankAccount)]
+pub struct PrintDelegateRecord {
+    pub key: Key, //1
+    /// Holder of the master edition token that approved the delegate. The approval lapses
+    /// once the master edition token leaves this owner.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub owner: Pubkey, //32
+    pub allowed_prints: u64, //8
+    pub bump: u8, //1
+}
+
+impl Default for PrintDelegateRecord {
+    fn default() -> Self {
+        PrintDelegateRecord {
+            key: Key::PrintDelegateRecord,
+            owner: Pubkey::default(),
+            allowed_prints: 0,
+            bump: 255,
+        }
+    }
+}
+
+impl TokenMetadataAccount for PrintDelegateRecord {
+    fn key() -> Key {
+        Key::PrintDelegateRecord
+    }
+
+    fn size() -> usize {
+        PRINT_DELEGATE_RECORD_SIZE
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
@@ -349,6 +395,8 @@ pub struct Metadata {
     pub uses: Option<Uses>,
     /// Item Details
     pub collection_details: Option<CollectionDetails>,
+    /// Layout version, None for accounts written before versioning. Set by `MigrateMetadata`.
+    pub version: Option<u8>,
 }
 
 impl Default for Metadata {
@@ -365,6 +413,7 @@ impl Default for Metadata {
             collection: None,
             uses: None,
             collection_details: None,
+            version: None,
         }
     }
 }
@@ -394,6 +443,7 @@ pub trait MasterEdition {
     fn supply(&self) -> u64;
     fn set_supply(&mut self, supply: u64);
     fn max_supply(&self) -> Option<u64>;
+    fn set_max_supply(&mut self, max_supply: Option<u64>);
     fn save(&self, account: &AccountInfo) -> ProgramResult;
 }
 
@@ -464,12 +514,70 @@ impl MasterEdition for MasterEditionV2 {
         self.max_supply
     }
 
+    fn set_max_supply(&mut self, max_supply: Option<u64>) {
+        self.max_supply = max_supply;
+    }
+
     fn save(&self, account: &AccountInfo) -> ProgramResult {
         BorshSerialize::serialize(self, &mut *account.data.borrow_mut())?;
         Ok(())
     }
 }
 
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PrintWindow {
+    /// Unix timestamp at which prints may first be minted.
+    pub start: i64,
+    /// Unix timestamp at which printing closes; prints must be minted strictly before it.
+    pub end: i64,
+}
+
+impl PrintWindow {
+    pub fn is_open(&self, now: i64) -> bool {
+        self.start <= now && now < self.end
+    }
+}
+
+/// Print configuration of a Master Edition V2, kept in the unused end of its account at
+/// `MASTER_EDITION_PRINT_CONFIG_OFFSET`. The account keeps the V2 key and layout, so readers of
+/// Master Edition V2 are unaffected. New fields are only ever appended, so that the zeroed bytes
+/// of accounts that never had a configuration read as the defaults.
+#[repr(C)]
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct MasterEditionPrintConfig {
+    /// If set, prints can only be minted inside this window.
+    pub print_window: Option<PrintWindow>,
+
+    /// If true, burning a print frees its edition number for reprinting, no matter who held it.
+    pub reprint_burned_editions: bool,
+
+    /// Highest edition number ever printed. Only numbers up to it can have been freed.
+    pub highest_printed_edition: u64,
+}
+
+impl MasterEditionPrintConfig {
+    pub fn from_account_info(a: &AccountInfo) -> Result<Self, ProgramError> {
+        assert_owned_by(a, &ID)?;
+
+        let data = a.try_borrow_data()?;
+        if data.len() != MAX_MASTER_EDITION_LEN || data[0] != Key::MasterEditionV2 as u8 {
+            return Err(MetadataError::DataTypeMismatch.into());
+        }
+
+        Self::deserialize(&mut &data[MASTER_EDITION_PRINT_CONFIG_OFFSET..])
+            .map_err(|_| MetadataError::DataTypeMismatch.into())
+    }
+
+    pub fn save(&self, a: &AccountInfo) -> ProgramResult {
+        let mut data = a.try_borrow_mut_data()?;
+        BorshSerialize::serialize(self, &mut &mut data[MASTER_EDITION_PRINT_CONFIG_OFFSET..])?;
+        Ok(())
+    }
+}
+
 #[repr(C)]
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize, ShankAccount)]
@@ -519,6 +627,10 @@ impl MasterEdition for MasterEditionV1 {
         self.max_supply
     }
 
+    fn set_max_supply(&mut self, max_supply: Option<u64>) {
+        self.max_supply = max_supply;
+    }
+
     fn set_supply(&mut self, supply: u64) {
         self.supply = supply;
     }
@@ -938,4 +1050,11 @@ impl EditionMarker {
         self.ledger[index] |= mask;
         Ok(())
     }
+
+    pub fn remove_edition(&mut self, edition: u64) -> ProgramResult {
+        let (index, mask) = EditionMarker::get_index_and_mask(edition)?;
+        // bitwise and a 0 into our position, leaving the others untouched
+        self.ledger[index] &= !mask;
+        Ok(())
+    }
 }
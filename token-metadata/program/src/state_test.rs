@@ -7,8 +7,9 @@ use crate::{
     deser::tests::{expected_pesky_metadata, pesky_data},
     error::MetadataError,
     state::{
-        CollectionAuthorityRecord, Edition, EditionMarker, Key, MasterEditionV2, Metadata,
-        UseAuthorityRecord, MAX_METADATA_LEN,
+        CollectionAuthorityRecord, Edition, EditionMarker, Key, MasterEdition,
+        MasterEditionPrintConfig, MasterEditionV2, Metadata, PrintWindow, UseAuthorityRecord,
+        EDITION_MARKER_BIT_SIZE, MAX_METADATA_LEN,
     },
     ID,
 };
@@ -344,6 +345,65 @@ mod master_edition {
         let error = MasterEditionV2::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn print_config_keeps_master_edition_v2() {
+        let mut master_edition = MasterEditionV2 {
+            key: Key::MasterEditionV2,
+            supply: 7,
+            max_supply: None,
+        };
+
+        let mut buf = Vec::new();
+        master_edition.serialize(&mut buf).unwrap();
+        MasterEditionV2::pad_length(&mut buf).unwrap();
+
+        let pubkey = Keypair::new().pubkey();
+        let owner = &ID;
+        let mut lamports = 1_000_000_000;
+        let mut data = buf.clone();
+
+        let account_info = AccountInfo::new(
+            &pubkey,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            owner,
+            false,
+            1_000_000_000,
+        );
+
+        // Zeroed padding reads as no configuration.
+        let print_config = MasterEditionPrintConfig::from_account_info(&account_info).unwrap();
+        assert_eq!(print_config, MasterEditionPrintConfig::default());
+
+        let expected_config = MasterEditionPrintConfig {
+            print_window: Some(PrintWindow {
+                start: 100,
+                end: 200,
+            }),
+            reprint_burned_editions: true,
+            highest_printed_edition: 3,
+        };
+        expected_config.save(&account_info).unwrap();
+
+        // Setting a max supply writes the longest V2 fields and leaves the configuration alone.
+        master_edition.max_supply = Some(10);
+        master_edition.save(&account_info).unwrap();
+
+        let data = MasterEditionV2::from_account_info(&account_info).unwrap();
+        assert_eq!(data, master_edition);
+
+        let print_config = MasterEditionPrintConfig::from_account_info(&account_info).unwrap();
+        assert_eq!(print_config, expected_config);
+
+        let window = print_config.print_window.unwrap();
+        assert!(!window.is_open(99));
+        assert!(window.is_open(100));
+        assert!(window.is_open(199));
+        assert!(!window.is_open(200));
+    }
 }
 
 mod edition {
@@ -470,6 +530,56 @@ mod edition_marker {
         let error = EditionMarker::from_account_info(&account_info).unwrap_err();
         assert_eq!(error, MetadataError::DataTypeMismatch.into());
     }
+
+    #[test]
+    fn remove_edition_frees_only_that_bit_at_ledger_boundaries() {
+        // Edition 0 and 247 are the first and last bits of marker 0; 248 wraps to the
+        // first bit of marker 1, and 495/496 are the same boundary one marker later.
+        assert_eq!(
+            EditionMarker::get_index_and_mask(0).unwrap(),
+            (0, 0b1000_0000)
+        );
+        assert_eq!(
+            EditionMarker::get_index_and_mask(247).unwrap(),
+            (30, 0b0000_0001)
+        );
+        assert_eq!(
+            EditionMarker::get_index_and_mask(248).unwrap(),
+            (0, 0b1000_0000)
+        );
+        assert_eq!(
+            EditionMarker::get_index_and_mask(495).unwrap(),
+            (30, 0b0000_0001)
+        );
+        assert_eq!(
+            EditionMarker::get_index_and_mask(496).unwrap(),
+            (0, 0b1000_0000)
+        );
+        assert_eq!(247 / EDITION_MARKER_BIT_SIZE, 0);
+        assert_eq!(248 / EDITION_MARKER_BIT_SIZE, 1);
+
+        let mut marker = EditionMarker::default();
+        for edition in 1..EDITION_MARKER_BIT_SIZE {
+            marker.insert_edition(edition).unwrap();
+        }
+        assert_eq!(marker.ledger[0], 0b0111_1111);
+        assert!(marker.ledger[1..].iter().all(|b| *b == 0xFF));
+
+        marker.remove_edition(247).unwrap();
+        assert!(!marker.edition_taken(247).unwrap());
+        assert!(marker.edition_taken(246).unwrap());
+        assert_eq!(marker.ledger[30], 0b1111_1110);
+
+        // Removing an edition that is already free leaves the ledger untouched.
+        marker.remove_edition(247).unwrap();
+        assert_eq!(marker.ledger[30], 0b1111_1110);
+
+        marker.remove_edition(1).unwrap();
+        assert_eq!(marker.ledger[0], 0b0011_1111);
+
+        marker.insert_edition(247).unwrap();
+        assert!(marker.edition_taken(247).unwrap());
+    }
 }
 
 mod use_authority_record {
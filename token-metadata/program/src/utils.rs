@@ -5,10 +5,11 @@ use crate::{
     pda::find_master_edition_account,
     state::{
         get_reservation_list, CollectionDetails, Creator, Data, DataV2, Edition, EditionMarker,
-        Key, MasterEditionV1, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard, Uses,
-        EDITION, EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN,
-        MAX_EDITION_MARKER_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH,
-        MAX_SYMBOL_LENGTH, MAX_URI_LENGTH, PREFIX,
+        Key, MasterEditionPrintConfig, MasterEditionV1, MasterEditionV2, Metadata,
+        TokenMetadataAccount, TokenStandard, Uses, CURRENT_METADATA_VERSION, EDITION,
+        EDITION_MARKER_BIT_SIZE, MAX_CREATOR_LIMIT, MAX_EDITION_LEN, MAX_EDITION_MARKER_SIZE,
+        MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+        MAX_URI_LENGTH, PREFIX,
     },
 };
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
@@ -16,6 +17,7 @@ use borsh::BorshSerialize;
 use solana_program::{
     account_info::AccountInfo,
     borsh::try_from_slice_unchecked,
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
@@ -1023,6 +1025,7 @@ pub fn process_create_metadata_accounts_logic(
     metadata.data = data.to_v1();
     metadata.is_mutable = is_mutable;
     metadata.update_authority = update_authority_key;
+    metadata.version = Some(CURRENT_METADATA_VERSION);
 
     assert_valid_use(&data.uses, &None)?;
     metadata.uses = data.uses;
@@ -1143,6 +1146,7 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
     assert_owned_by(token_account_info, &spl_token::id())?;
     assert_owned_by(master_edition_account_info, program_id)?;
     assert_owned_by(master_metadata_account_info, program_id)?;
+    assert_print_window_open(master_edition_account_info)?;
 
     let master_metadata = Metadata::from_account_info(master_metadata_account_info)?;
     let token_account: Account = assert_initialized(token_account_info)?;
@@ -1230,8 +1234,29 @@ pub fn process_mint_new_edition_from_master_edition_via_token_logic<'a>(
         None,
         Some(edition),
     )?;
+
+    record_printed_edition(master_edition_account_info, edition)
+}
+
+/// Raises the highest printed edition number of a Master Edition V2 to `edition`.
+pub fn record_printed_edition(
+    master_edition_account_info: &AccountInfo,
+    edition: u64,
+) -> ProgramResult {
+    if master_edition_account_info.try_borrow_data()?[0] != Key::MasterEditionV2 as u8 {
+        return Ok(());
+    }
+
+    let mut print_config =
+        MasterEditionPrintConfig::from_account_info(master_edition_account_info)?;
+    if edition > print_config.highest_printed_edition {
+        print_config.highest_printed_edition = edition;
+        print_config.save(master_edition_account_info)?;
+    }
+
     Ok(())
 }
+
 pub fn assert_currently_holding(
     program_id: &Pubkey,
     owner_info: &AccountInfo,
@@ -1432,3 +1457,25 @@ pub fn assert_edition_is_not_mint_authority(mint_account_info: &AccountInfo) ->
 
     Ok(())
 }
+
+pub fn assert_print_window_open(master_edition_account_info: &AccountInfo) -> ProgramResult {
+    // Only Master Edition V2 accounts can carry a print window.
+    if master_edition_account_info.try_borrow_data()?[0] != Key::MasterEditionV2 as u8 {
+        return Ok(());
+    }
+
+    let print_config = MasterEditionPrintConfig::from_account_info(master_edition_account_info)?;
+    if let Some(window) = print_config.print_window {
+        let now = Clock::get()?.unix_timestamp;
+
+        if now < window.start {
+            return Err(MetadataError::PrintWindowNotOpen.into());
+        }
+
+        if now >= window.end {
+            return Err(MetadataError::PrintWindowClosed.into());
+        }
+    }
+
+    Ok(())
+}
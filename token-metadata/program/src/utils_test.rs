@@ -46,6 +46,7 @@ mod puff_out_test {
             uses: None,
             token_standard: None,
             collection_details: None,
+            version: None,
         };
 
         puff_out_data_fields(&mut metadata);
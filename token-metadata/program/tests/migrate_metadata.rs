@@ -0,0 +1,98 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    instruction::migrate_metadata_account,
+    state::{Metadata as ProgramMetadata, CURRENT_METADATA_VERSION, MAX_METADATA_LEN},
+    ID as PROGRAM_ID,
+};
+use solana_program_test::*;
+use solana_sdk::{account::AccountSharedData, signer::Signer, transaction::Transaction};
+use utils::*;
+
+mod migrate_metadata {
+    use super::*;
+
+    async fn migrate(context: &mut ProgramTestContext, metadata: &Metadata) {
+        let tx = Transaction::new_signed_with_payer(
+            &[migrate_metadata_account(PROGRAM_ID, metadata.pubkey)],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn new_metadata_is_created_at_current_version() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert_eq!(metadata.version, Some(CURRENT_METADATA_VERSION));
+    }
+
+    #[tokio::test]
+    async fn unversioned_metadata_is_migrated() {
+        let mut context = program_test().start_with_context().await;
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Rewrite the account as it would have looked before versioning, with stray
+        // bytes left behind after the last field.
+        let mut md_account = get_account(&mut context, &test_metadata.pubkey).await;
+        let mut metadata = ProgramMetadata::deserialize(&mut md_account.data.as_slice()).unwrap();
+        metadata.version = None;
+        let mut data = metadata.try_to_vec().unwrap();
+        data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        data.resize(MAX_METADATA_LEN, 0);
+        md_account.data = data;
+
+        let md_account_shared_data: AccountSharedData = md_account.into();
+        context.set_account(&test_metadata.pubkey, &md_account_shared_data);
+
+        let unversioned = test_metadata.get_data(&mut context).await;
+        assert_eq!(unversioned.version, None);
+
+        migrate(&mut context, &test_metadata).await;
+
+        let md_account = get_account(&mut context, &test_metadata.pubkey).await;
+        let migrated = ProgramMetadata::deserialize(&mut md_account.data.as_slice()).unwrap();
+        metadata.version = Some(CURRENT_METADATA_VERSION);
+        assert_eq!(migrated, metadata);
+
+        // Nothing is left after the migrated fields.
+        let migrated_len = migrated.try_to_vec().unwrap().len();
+        assert!(md_account.data[migrated_len..].iter().all(|b| *b == 0));
+    }
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    instruction,
+    pda::find_print_delegate_account,
+    state::{Key, PrintDelegateRecord},
+};
+use num_traits::FromPrimitive;
+use solana_program::borsh::try_from_slice_unchecked;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use utils::*;
+
+mod print_delegate {
+    use super::*;
+
+    async fn setup(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+        test_metadata
+            .create(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                0,
+            )
+            .await
+            .unwrap();
+
+        test_master_edition.create(context, Some(10)).await.unwrap();
+
+        (test_metadata, test_master_edition)
+    }
+
+    async fn approve(
+        context: &mut ProgramTestContext,
+        test_metadata: &Metadata,
+        delegate: &Pubkey,
+        number_of_prints: u64,
+    ) -> Result<Pubkey, BanksClientError> {
+        let (record, _) = find_print_delegate_account(&test_metadata.mint.pubkey(), delegate);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::approve_print_delegate(
+                mpl_token_metadata::id(),
+                record,
+                *delegate,
+                context.payer.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.token.pubkey(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+                number_of_prints,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+        Ok(record)
+    }
+
+    #[tokio::test]
+    async fn success() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+        let delegate = Keypair::new();
+
+        let record = approve(&mut context, &test_metadata, &delegate.pubkey(), 2)
+            .await
+            .unwrap();
+
+        let account = get_account(&mut context, &record).await;
+        let record_data: PrintDelegateRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_data.key, Key::PrintDelegateRecord);
+        assert_eq!(record_data.owner, context.payer.pubkey());
+        assert_eq!(record_data.allowed_prints, 2);
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker
+            .create_via_print_delegate(&mut context, &delegate)
+            .await
+            .unwrap();
+
+        assert!(test_edition_marker.exists_on_chain(&mut context).await);
+        let master_edition = test_master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition.supply, 1);
+
+        let account = get_account(&mut context, &record).await;
+        let record_data: PrintDelegateRecord = try_from_slice_unchecked(&account.data).unwrap();
+        assert_eq!(record_data.allowed_prints, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_when_prints_exhausted() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+        let delegate = Keypair::new();
+
+        approve(&mut context, &test_metadata, &delegate.pubkey(), 1)
+            .await
+            .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker
+            .create_via_print_delegate(&mut context, &delegate)
+            .await
+            .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 2);
+        let err = test_edition_marker
+            .create_via_print_delegate(&mut context, &delegate)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::NotEnoughPrints);
+    }
+
+    #[tokio::test]
+    async fn fail_when_already_approved() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, _) = setup(&mut context).await;
+        let delegate = Keypair::new();
+
+        approve(&mut context, &test_metadata, &delegate.pubkey(), 1)
+            .await
+            .unwrap();
+
+        let err = approve(&mut context, &test_metadata, &delegate.pubkey(), 5)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintDelegateRecordAlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn fail_after_master_token_transferred() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+        let delegate = Keypair::new();
+
+        approve(&mut context, &test_metadata, &delegate.pubkey(), 1)
+            .await
+            .unwrap();
+
+        let new_owner = Keypair::new();
+        let new_token = Keypair::new();
+        create_token_account(
+            &mut context,
+            &new_token,
+            &test_metadata.mint.pubkey(),
+            &new_owner.pubkey(),
+        )
+        .await
+        .unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[spl_token::instruction::transfer(
+                &spl_token::id(),
+                &test_metadata.token.pubkey(),
+                &new_token.pubkey(),
+                &context.payer.pubkey(),
+                &[],
+                1,
+            )
+            .unwrap()],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let mut test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker.metadata_token_pubkey = new_token.pubkey();
+        let err = test_edition_marker
+            .create_via_print_delegate(&mut context, &delegate)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidPrintDelegateRecord);
+    }
+
+    #[tokio::test]
+    async fn revoke_closes_record() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+        let delegate = Keypair::new();
+
+        let record = approve(&mut context, &test_metadata, &delegate.pubkey(), 1)
+            .await
+            .unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::revoke_print_delegate(
+                mpl_token_metadata::id(),
+                record,
+                delegate.pubkey(),
+                context.payer.pubkey(),
+                test_metadata.token.pubkey(),
+                test_metadata.pubkey,
+                test_metadata.mint.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await.unwrap();
+
+        let account = context.banks_client.get_account(record).await.unwrap();
+        assert!(account.is_none());
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let err = test_edition_marker
+            .create_via_print_delegate(&mut context, &delegate)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::IncorrectOwner);
+    }
+}
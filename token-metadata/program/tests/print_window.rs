@@ -0,0 +1,253 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{
+    error::MetadataError,
+    state::{Collection, Key, PrintWindow},
+};
+use num_traits::FromPrimitive;
+use solana_program::clock::Clock;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use utils::*;
+
+mod print_window {
+    use super::*;
+
+    async fn setup(context: &mut ProgramTestContext) -> (Metadata, MasterEditionV2) {
+        let test_metadata = Metadata::new();
+        let test_master_edition = MasterEditionV2::new(&test_metadata);
+
+        test_metadata
+            .create(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                0,
+            )
+            .await
+            .unwrap();
+
+        test_master_edition
+            .create_v3(context, Some(10))
+            .await
+            .unwrap();
+
+        (test_metadata, test_master_edition)
+    }
+
+    async fn now(context: &mut ProgramTestContext) -> i64 {
+        let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    async fn set_time(context: &mut ProgramTestContext, unix_timestamp: i64) {
+        let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        context.set_sysvar(&clock);
+    }
+
+    #[tokio::test]
+    async fn success_inside_window() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        let now = now(&mut context).await;
+        test_master_edition
+            .set_print_window(&mut context, now - 10, now + 100)
+            .await
+            .unwrap();
+
+        // The account stays a Master Edition V2 for readers that don't know the print window.
+        let master_edition = test_master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition.key, Key::MasterEditionV2);
+        assert_eq!(master_edition.max_supply, Some(10));
+        let print_config = test_master_edition.get_print_config(&mut context).await;
+        assert_eq!(
+            print_config.print_window,
+            Some(PrintWindow {
+                start: now - 10,
+                end: now + 100
+            })
+        );
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        test_edition_marker.create(&mut context).await.unwrap();
+
+        let master_edition = test_master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition.supply, 1);
+    }
+
+    #[tokio::test]
+    async fn fail_before_window_opens() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        let now = now(&mut context).await;
+        test_master_edition
+            .set_print_window(&mut context, now + 100, now + 200)
+            .await
+            .unwrap();
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let err = test_edition_marker.create(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintWindowNotOpen);
+    }
+
+    #[tokio::test]
+    async fn fail_after_window_closes() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        let now = now(&mut context).await;
+        test_master_edition
+            .set_print_window(&mut context, now - 10, now + 100)
+            .await
+            .unwrap();
+
+        set_time(&mut context, now + 100).await;
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 1);
+        let err = test_edition_marker.create(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintWindowClosed);
+    }
+
+    #[tokio::test]
+    async fn fail_invalid_window() {
+        let mut context = program_test().start_with_context().await;
+        let (_, test_master_edition) = setup(&mut context).await;
+
+        let now = now(&mut context).await;
+        let err = test_master_edition
+            .set_print_window(&mut context, now, now)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::InvalidPrintWindow);
+    }
+
+    #[tokio::test]
+    async fn close_window_caps_max_supply() {
+        let mut context = program_test().start_with_context().await;
+        let (test_metadata, test_master_edition) = setup(&mut context).await;
+
+        let now = now(&mut context).await;
+        test_master_edition
+            .set_print_window(&mut context, now - 10, now + 100)
+            .await
+            .unwrap();
+
+        test_master_edition
+            .mint_editions(&mut context, &test_metadata, 2)
+            .await
+            .unwrap();
+
+        test_master_edition
+            .close_print_window(&mut context)
+            .await
+            .unwrap();
+
+        let master_edition = test_master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition.supply, 2);
+        assert_eq!(master_edition.max_supply, Some(2));
+        let print_config = test_master_edition.get_print_config(&mut context).await;
+        assert!(print_config.print_window.unwrap().end <= now + 100);
+
+        let test_edition_marker = EditionMarker::new(&test_metadata, &test_master_edition, 3);
+        let err = test_edition_marker.create(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::PrintWindowClosed);
+    }
+
+    #[tokio::test]
+    async fn fail_close_without_window() {
+        let mut context = program_test().start_with_context().await;
+        let (_, test_master_edition) = setup(&mut context).await;
+
+        let err = test_master_edition
+            .close_print_window(&mut context)
+            .await
+            .unwrap_err();
+
+        assert_custom_error!(err, MetadataError::MissingPrintWindow);
+    }
+
+    #[tokio::test]
+    async fn collection_parent_with_window_still_verifies() {
+        let mut context = program_test().start_with_context().await;
+
+        let test_collection = Metadata::new();
+        test_collection
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let collection_master_edition_account = MasterEditionV2::new(&test_collection);
+        collection_master_edition_account
+            .create_v3(&mut context, Some(0))
+            .await
+            .unwrap();
+
+        let now = now(&mut context).await;
+        collection_master_edition_account
+            .set_print_window(&mut context, now - 10, now + 100)
+            .await
+            .unwrap();
+
+        let test_metadata = Metadata::new();
+        test_metadata
+            .create_v2(
+                &mut context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                Some(Collection {
+                    key: test_collection.mint.pubkey(),
+                    verified: false,
+                }),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let kp = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        test_metadata
+            .verify_collection(
+                &mut context,
+                test_collection.pubkey,
+                &kp,
+                test_collection.mint.pubkey(),
+                collection_master_edition_account.pubkey,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let metadata = test_metadata.get_data(&mut context).await;
+        assert!(metadata.collection.unwrap().verified);
+    }
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_token_metadata::{error::MetadataError, state::Key};
+use num_traits::FromPrimitive;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use spl_associated_token_account::get_associated_token_address;
+use utils::*;
+
+mod reprint_burned_editions {
+    use super::*;
+
+    async fn setup(
+        context: &mut ProgramTestContext,
+        max_supply: u64,
+    ) -> (Metadata, MasterEditionV2) {
+        let original_nft = Metadata::new();
+        original_nft
+            .create_v2(
+                context,
+                "Test".to_string(),
+                "TST".to_string(),
+                "uri".to_string(),
+                None,
+                10,
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let master_edition = MasterEditionV2::new(&original_nft);
+        master_edition
+            .create_v3(context, Some(max_supply))
+            .await
+            .unwrap();
+
+        (original_nft, master_edition)
+    }
+
+    /// Prints `edition`, hands it to `user` and has them burn it.
+    async fn print_and_burn_elsewhere(
+        context: &mut ProgramTestContext,
+        original_nft: &Metadata,
+        master_edition: &MasterEditionV2,
+        user: &Keypair,
+        edition: u64,
+    ) -> EditionMarker {
+        let mut print_edition = EditionMarker::new(original_nft, master_edition, edition);
+        print_edition.create(context).await.unwrap();
+        print_edition
+            .transfer(context, &user.pubkey())
+            .await
+            .unwrap();
+        let user_token_account =
+            get_associated_token_address(&user.pubkey(), &print_edition.mint.pubkey());
+
+        burn_edition(
+            context,
+            print_edition.new_metadata_pubkey,
+            user,
+            print_edition.mint.pubkey(),
+            original_nft.mint.pubkey(),
+            user_token_account,
+            original_nft.token.pubkey(),
+            master_edition.pubkey,
+            print_edition.new_edition_pubkey,
+            print_edition.pubkey,
+        )
+        .await
+        .unwrap();
+
+        print_edition
+    }
+
+    #[tokio::test]
+    async fn burned_editions_can_be_reprinted_across_marker_boundary() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = setup(&mut context, 300).await;
+
+        master_edition
+            .set_reprint_burned_editions(&mut context, true)
+            .await
+            .unwrap();
+        let master_edition_data = master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition_data.key, Key::MasterEditionV2);
+        let print_config = master_edition.get_print_config(&mut context).await;
+        assert!(print_config.reprint_burned_editions);
+
+        let user = Keypair::new();
+        airdrop(&mut context, &user.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        // 247 is the last bit of the first edition marker, 248 the first bit of the second.
+        let last_in_first_marker =
+            print_and_burn_elsewhere(&mut context, &original_nft, &master_edition, &user, 247)
+                .await;
+        let first_in_second_marker =
+            print_and_burn_elsewhere(&mut context, &original_nft, &master_edition, &user, 248)
+                .await;
+        assert_ne!(last_in_first_marker.pubkey, first_in_second_marker.pubkey);
+
+        let print_config = master_edition.get_print_config(&mut context).await;
+        assert_eq!(print_config.highest_printed_edition, 248);
+
+        // Both edition markers emptied out, so they were closed.
+        assert!(context
+            .banks_client
+            .get_account(last_in_first_marker.pubkey)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(context
+            .banks_client
+            .get_account(first_in_second_marker.pubkey)
+            .await
+            .unwrap()
+            .is_none());
+
+        // The series keeps its size even though someone else burned the prints.
+        let master_edition_data = master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition_data.supply, 0);
+        assert_eq!(master_edition_data.max_supply, Some(300));
+
+        for edition in [247, 248] {
+            let reprint = EditionMarker::new(&original_nft, &master_edition, edition);
+            reprint.create_freed(&mut context).await.unwrap();
+            assert!(reprint.exists_on_chain(&mut context).await);
+        }
+
+        let ledger = get_account(&mut context, &last_in_first_marker.pubkey)
+            .await
+            .data;
+        assert_eq!(ledger[31], 0b0000_0001);
+        let ledger = get_account(&mut context, &first_in_second_marker.pubkey)
+            .await
+            .data;
+        assert_eq!(ledger[1], 0b1000_0000);
+
+        let master_edition_data = master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition_data.supply, 2);
+    }
+
+    #[tokio::test]
+    async fn burned_edition_stays_taken_when_not_enabled() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = setup(&mut context, 10).await;
+
+        let user = Keypair::new();
+        airdrop(&mut context, &user.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+
+        print_and_burn_elsewhere(&mut context, &original_nft, &master_edition, &user, 1).await;
+
+        let master_edition_data = master_edition.get_data(&mut context).await;
+        assert_eq!(master_edition_data.max_supply, Some(9));
+
+        let reprint = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = reprint.create_freed(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::ReprintingNotEnabled);
+    }
+
+    #[tokio::test]
+    async fn fail_to_reprint_edition_never_printed() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = setup(&mut context, 10).await;
+
+        master_edition
+            .set_reprint_burned_editions(&mut context, true)
+            .await
+            .unwrap();
+
+        let user = Keypair::new();
+        airdrop(&mut context, &user.pubkey(), 1_000_000_000)
+            .await
+            .unwrap();
+        print_and_burn_elsewhere(&mut context, &original_nft, &master_edition, &user, 1).await;
+
+        // 2 is inside the series but was never printed, so it cannot have been freed.
+        let reprint = EditionMarker::new(&original_nft, &master_edition, 2);
+        let err = reprint.create_freed(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::EditionNeverPrinted);
+    }
+
+    #[tokio::test]
+    async fn fail_to_reprint_edition_that_was_not_burned() {
+        let mut context = program_test().start_with_context().await;
+        let (original_nft, master_edition) = setup(&mut context, 10).await;
+
+        master_edition
+            .set_reprint_burned_editions(&mut context, true)
+            .await
+            .unwrap();
+
+        let print_edition = EditionMarker::new(&original_nft, &master_edition, 1);
+        print_edition.create(&mut context).await.unwrap();
+
+        let reprint = EditionMarker::new(&original_nft, &master_edition, 1);
+        let err = reprint.create_freed(&mut context).await.unwrap_err();
+
+        assert_custom_error!(err, MetadataError::AlreadyInitialized);
+    }
+}
@@ -3,6 +3,7 @@ use borsh::BorshSerialize;
 use mpl_token_metadata::{
     id,
     instruction::{self, MetadataInstruction, MintNewEditionFromMasterEditionViaTokenArgs},
+    pda::find_print_delegate_account,
     state::{EDITION, EDITION_MARKER_BIT_SIZE, PREFIX},
 };
 use solana_program::{
@@ -128,6 +129,107 @@ impl EditionMarker {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn create_freed(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None, 0).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                instruction::mint_freed_edition_from_master_edition_via_token(
+                    id(),
+                    self.new_metadata_pubkey,
+                    self.new_edition_pubkey,
+                    self.master_edition_pubkey,
+                    self.mint.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    self.metadata_token_pubkey,
+                    context.payer.pubkey(),
+                    self.metadata_pubkey,
+                    self.metadata_mint_pubkey,
+                    self.edition,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn create_via_print_delegate(
+        &self,
+        context: &mut ProgramTestContext,
+        print_delegate: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        create_mint(context, &self.mint, &context.payer.pubkey(), None, 0).await?;
+        create_token_account(
+            context,
+            &self.token,
+            &self.mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .await?;
+        mint_tokens(
+            context,
+            &self.mint.pubkey(),
+            &self.token.pubkey(),
+            1,
+            &context.payer.pubkey(),
+            None,
+        )
+        .await?;
+
+        let (print_delegate_record, _) =
+            find_print_delegate_account(&self.metadata_mint_pubkey, &print_delegate.pubkey());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                instruction::mint_new_edition_from_master_edition_via_print_delegate(
+                    id(),
+                    self.new_metadata_pubkey,
+                    self.new_edition_pubkey,
+                    self.master_edition_pubkey,
+                    self.mint.pubkey(),
+                    context.payer.pubkey(),
+                    context.payer.pubkey(),
+                    print_delegate.pubkey(),
+                    print_delegate_record,
+                    self.metadata_token_pubkey,
+                    context.payer.pubkey(),
+                    self.metadata_pubkey,
+                    self.metadata_mint_pubkey,
+                    self.edition,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, print_delegate],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn create_with_invalid_token_program(
         &self,
         context: &mut ProgramTestContext,
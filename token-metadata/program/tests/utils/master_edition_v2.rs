@@ -3,7 +3,7 @@ use borsh::ser::BorshSerialize;
 use mpl_token_metadata::{
     id,
     instruction::{self, CreateMasterEditionArgs, MetadataInstruction},
-    state::{EDITION, PREFIX},
+    state::{EDITION, MASTER_EDITION_PRINT_CONFIG_OFFSET, PREFIX},
 };
 use solana_program::{
     borsh::try_from_slice_unchecked,
@@ -60,6 +60,77 @@ impl MasterEditionV2 {
         try_from_slice_unchecked(&account.data).unwrap()
     }
 
+    pub async fn get_print_config(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> mpl_token_metadata::state::MasterEditionPrintConfig {
+        let account = get_account(context, &self.pubkey).await;
+        try_from_slice_unchecked(&account.data[MASTER_EDITION_PRINT_CONFIG_OFFSET..]).unwrap()
+    }
+
+    pub async fn set_print_window(
+        &self,
+        context: &mut ProgramTestContext,
+        start: i64,
+        end: i64,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_print_window(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                self.metadata_pubkey,
+                start,
+                end,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn close_print_window(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::close_print_window(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                self.metadata_pubkey,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_reprint_burned_editions(
+        &self,
+        context: &mut ProgramTestContext,
+        enabled: bool,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_reprint_burned_editions(
+                id(),
+                self.pubkey,
+                context.payer.pubkey(),
+                self.metadata_pubkey,
+                enabled,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn create_with_invalid_token_program(
         &self,
         context: &mut ProgramTestContext,